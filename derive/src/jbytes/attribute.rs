@@ -1,6 +1,8 @@
 use virtue::prelude::*;
 use virtue::utils::*;
-use super::parse::{AttrValue, AttrValueTrait, parse_value_string};
+use virtue::parse::Fields;
+use super::parse::{AttrValue, AttrValueTrait, parse_value_string, parse_checksum, replace_field_idents, take_args_attribute, trait_kind};
+use super::validate::canonical_key;
 
 
 #[derive(Debug, Default)]
@@ -8,6 +10,8 @@ pub struct ContainerAttributes {
     pub is_use: bool,
 
     pub byteorder: Option<AttrValue>,
+    // The default byte order is the effective byte order of the parent.
+    pub byteorder_inherit: bool,
     pub byte_count_disable: bool,
     pub byte_count: Option<AttrValue>,
 
@@ -24,34 +28,87 @@ pub struct ContainerAttributes {
     pub branch_take_bytes: Option<AttrValue>,
     pub branch_starts_with: bool,
     pub branch_starts_with_untake: bool,
+    // The type of the branch value, eg: `str`/`[u8]`/`u16`/`Method`/`(u8, u8)`
+    pub branch_type: Option<String>,
+
+    pub align_fields: Option<AttrValue>,
+    pub magic: Option<AttrValue>,
+
+    // generics
+    pub bound: Option<String>,
+    // The bounds of the decode/encode traits only, which replace `bound`, eg: `#[jbytes(bound_decode="T: Default")]`
+    pub bound_decode: Option<String>,
+    pub bound_encode: Option<String>,
+    // The type of the user context, eg: `#[jbytes(ctx="MyCtx")]`
+    pub ctx: Option<String>,
+    // The arguments passed from the parent field, eg: `#[jbytes(args(addr_len: usize))]` => (`addr_len`, `usize`)
+    pub args: Vec<(String, String)>,
 
     // custom encode/decode function.
     pub with_encode: Option<String>,
     pub with_decode: Option<String>,
     pub with: Option<String>,
+
+    // The keys and values of the attributes in order, eg: (`byteorder`, `LE`), which are described by `Describe`.
+    pub modifiers: Vec<(String, Option<String>)>,
+    // The spans of attribute keys and values for error messages, eg: (`align_fields`, key span, value span).
+    pub spans: Vec<(String, Span, Option<Span>)>,
 }
 
 
 impl ContainerAttributes {
-    pub fn to_code(&self, is_self: bool) -> String {
+    /// Returns the code of `cattr_new`, the `crate_name` is the derived trait, which selects the arguments, see `ByteArgs`.
+    pub fn to_code(&self, is_self: bool, crate_name: &str) -> String {
         let byteorder = self.byteorder.to_byteorder(is_self);
-        let byte_count = self.byte_count.to_code(is_self, false);
+        let byte_count = self.byte_count.to_code(is_self, false, &self.arg_names());
+
+        let byteorder_inherit = self.byteorder_inherit;
+        let position = if is_self { "buffer.get_position()" } else { "input.get_position()" };
+
+        // The user context is reachable as `ctx` in the modifier expressions.
+        let ctx = match &self.ctx {
+            Some(ctx) => format!("let ctx: &{ctx} = match jbytes::get_ctx::<{ctx}>(cattr) {{
+                Some(ctx) => ctx,
+                None => return Err(jbytes::make_error({position}, jbytes::ErrorKind::MissingContext)),
+            }};"),
+            None => "".to_string(),
+        };
 
-        if self.is_use {
-            format!("let mut cattr_new = jbytes::ContainerAttrModifiers {{
-                byteorder: {byteorder},
-                byte_count: {byte_count},
-                ..Default::default()}}; let cattr_new = Some(&cattr_new);")
-        }
-        else {
-            "let mut cattr_new: Option<&jbytes::ContainerAttrModifiers> = None;".to_string()
-        }
+        // The arguments are reachable by their names in the modifier expressions.
+        let args = match (self.args.first(), trait_kind(crate_name)) {
+            (Some((first, _)), Some(kind)) => {
+                let values = self.args.iter()
+                    .map(|(name, _)| format!("#[allow(unused_variables)] let {name} = Clone::clone(&args_value.{name});"))
+                    .collect::<String>();
+
+                format!("let args_value = match jbytes::args::get_args::<Self, {kind}>(fattr) {{
+                    Some(v) => v,
+                    None => return Err(jbytes::make_error({position}, jbytes::ErrorKind::MissingArgument({first:?}.to_string()))),
+                }}; {values}")
+            },
+            _ => "".to_string(),
+        };
+
+        // The subtree byte order of `byteorder_from` is passed to the nested types without attributes.
+        format!("{ctx} {args} let cattr_nested = jbytes::ContainerAttrModifiers::nested(cattr, fattr, {byteorder}, {byte_count}, {byteorder_inherit});
+            let mut cattr_new = cattr_nested.as_ref();")
+    }
+}
+
+
+impl ContainerAttributes {
+    /// Returns the argument names, which are local variables of the expressions, see `is_field_ident`.
+    pub fn arg_names(&self) -> Vec<String> {
+        self.args.iter().map(|v| v.0.clone()).collect()
     }
 }
 
 
 impl FromAttribute for ContainerAttributes {
     fn parse(group: &Group) -> Result<Option<Self>> {
+        let (group, args) = take_args_attribute(group, ':')?;
+        let group = &group;
+
         let attributes = if let Some(body) = parse_tagged_attribute(group, "jbytes")? {
             body
         }
@@ -73,10 +130,22 @@ impl FromAttribute for ContainerAttributes {
             result.is_use = true;
         }
 
+        if let Some(args) = args {
+            result.is_use = true;
+            result.args = args.into_iter().map(|(name, rtype)| (name.to_string(), rtype.into_iter().collect::<TokenStream>().to_string())).collect();
+            result.modifiers.extend(result.args.iter().map(|(name, rtype)| ("args".to_string(), Some(format!("{name}: {rtype}")))));
+        }
+
         for attribute in attributes {
             match attribute {
                 ParsedAttribute::Tag(i) => {
                     // #xxx[xxx]
+                    match i.to_string().as_str() {
+                        // #[repr(u8)] => `byte_count=1`
+                        "u8" | "u16" | "u32" | "u64" | "usize" => {},
+                        key => result.modifiers.push((canonical_key(key).to_string(), None)),
+                    }
+
                     match i.to_string().as_str() {
                         "default" | "default_value" => result.default_bool = true,
                         "byte_count_disable" => result.byte_count_disable = true,
@@ -93,7 +162,11 @@ impl FromAttribute for ContainerAttributes {
                 }
                 ParsedAttribute::Property(key, val) => {
                     // #xxx[xxx=xxx]
+                    result.modifiers.push((canonical_key(&key.to_string()).to_string(), Some(parse_value_string(&val)?)));
+                    result.spans.push((key.to_string(), key.span(), Some(val.span())));
+
                     match key.to_string().as_str() {
+                        "byteorder" if parse_value_string(&val)? == "inherit" => result.byteorder_inherit = true,
                         "byteorder" => result.byteorder = Some(AttrValue::parse_byteorder(&val)?),
                         "byte_count" => result.byte_count = Some(AttrValue::parse_usize(&val)?),
                         "get_variable_name" => result.get_variable_name = Some(AttrValue::parse_list(&val)?),
@@ -104,6 +177,13 @@ impl FromAttribute for ContainerAttributes {
                         // "branch_func" => result.branch_func = Some(parse_value_string(&val)?),
                         // "branch_enum" => result.branch_enum = Some(parse_value_string(&val)?),
                         "branch_take_bytes" => result.branch_take_bytes = Some(AttrValue::parse_usize(&val)?),
+                        "branch_type" => result.branch_type = Some(parse_value_string(&val)?),
+                        "bound" => result.bound = Some(parse_value_string(&val)?),
+                        "bound_decode" => result.bound_decode = Some(parse_value_string(&val)?),
+                        "bound_encode" => result.bound_encode = Some(parse_value_string(&val)?),
+                        "ctx" => result.ctx = Some(parse_value_string(&val)?),
+                        "align_fields" => result.align_fields = Some(AttrValue::parse_usize(&val)?),
+                        "magic" => result.magic = Some(AttrValue::parse_bytes(&val)?),
 
                         // custom encode/decode
                         "with_encode" | "encode_with" => result.with_encode = Some(parse_value_string(&val)?),
@@ -116,19 +196,84 @@ impl FromAttribute for ContainerAttributes {
             }
         }
 
+        if let (Some(AttrValue::Usize(byte_count)), false) = (&result.byte_count, result.modifiers.iter().any(|v| v.0 == "byte_count")) {
+            result.modifiers.push(("byte_count".to_string(), Some(byte_count.to_string())));
+        }
+
         Ok(Some(result))
     }
 }
 
 
+/// Returns the `encoding_rs` static of a legacy code page label, eg: `gbk`/`cp936` -> `GBK`, `windows-1252` -> `WINDOWS_1252`.
+fn legacy_encoding_name(label: &str) -> Option<String> {
+    let label = label.to_ascii_lowercase().replace('_', "-");
+
+    let name = match label.as_str() {
+        "gbk" | "gb2312" | "cp936" | "x-gbk" => "GBK",
+        "gb18030" => "GB18030",
+        "big5" | "big5-hkscs" | "cn-big5" => "BIG5",
+        "shift-jis" | "sjis" | "ms932" | "cp932" | "windows-31j" => "SHIFT_JIS",
+        "euc-jp" => "EUC_JP",
+        "iso-2022-jp" => "ISO_2022_JP",
+        "euc-kr" | "cp949" | "windows-949" => "EUC_KR",
+        "koi8-r" => "KOI8_R",
+        "koi8-u" => "KOI8_U",
+        "ibm866" | "cp866" => "IBM866",
+        "macintosh" | "mac" => "MACINTOSH",
+        "x-mac-cyrillic" => "X_MAC_CYRILLIC",
+        "x-user-defined" => "X_USER_DEFINED",
+        "iso-8859-8-i" => "ISO_8859_8_I",
+        "windows-874" | "cp874" => "WINDOWS_874",
+        value => {
+            if let Some(page) = value.strip_prefix("windows-").or_else(|| value.strip_prefix("cp")) {
+                return page.parse::<u16>().ok().filter(|v| (1250..=1258).contains(v)).map(|v| format!("WINDOWS_{v}"));
+            }
+
+            let part = value.strip_prefix("iso-8859-")?;
+            return part.parse::<u8>().ok().filter(|v| matches!(v, 2..=8 | 10 | 13..=16)).map(|v| format!("ISO_8859_{v}"));
+        },
+    };
+
+    Some(name.to_string())
+}
+
+
+/// Converts the encoding label to a `jbytes::Encoding` value, the legacy code pages
+/// refer to the `encoding_rs` statics, eg: `gbk` -> `encoding_rs::GBK`, returns None if the label is unknown.
+pub fn encoding_to_code(label: &str) -> Option<String> {
+    match label.to_ascii_lowercase().as_str() {
+        "utf8" | "utf-8" => Some("jbytes::Encoding::Utf8".to_string()),
+        "utf16le" | "utf-16le" => Some("jbytes::Encoding::Utf16Le".to_string()),
+        "utf16be" | "utf-16be" => Some("jbytes::Encoding::Utf16Be".to_string()),
+        "latin1" | "iso-8859-1" => Some("jbytes::Encoding::Latin1".to_string()),
+        // It reports a compile error without the `encoding_rs` feature of `jbytes`.
+        _ => legacy_encoding_name(label).map(|name| format!("jbytes::legacy_encoding!({name}, {label:?})")),
+    }
+}
+
+
 #[derive(Debug, Default)]
 pub struct FieldAttributes {
     pub is_use: bool,
     pub byteorder: Option<AttrValue>,
+    pub byteorder_from: Option<String>,
     pub length: Option<AttrValue>,
     pub offset: Option<AttrValue>,
     pub untake: bool,
+    // The absolute position of the field, eg: `seek="abs(hdr.ifd_offset)"` => `hdr.ifd_offset`
+    pub seek: Option<String>,
+    // The position of the field from the end of the input, eg: `seek_from_end="22"`, the encode doesn't back-patch it.
+    pub seek_from_end: Option<String>,
+    // Restores the position after the field of `seek`/`seek_from_end`.
+    pub restore: bool,
     pub full: Option<AttrValue>,
+    pub align: Option<AttrValue>,
+    pub magic: Option<AttrValue>,
+    pub length_of: Option<String>,
+    pub count_of: Option<String>,
+    pub checksum: Option<String>,
+    pub range: Option<String>,
     pub count: Option<AttrValue>,
     pub try_count: Option<AttrValue>,
     pub bits: Option<AttrValue>,
@@ -151,6 +296,14 @@ pub struct FieldAttributes {
     pub linend: Option<AttrValue>,
 
     pub remaining: bool,
+    pub pad: Option<AttrValue>,
+    pub cstr: bool,
+    pub encoding: Option<String>,
+    pub code_units: bool,
+
+    pub bcd: bool,
+    pub ascii_dec: bool,
+    pub ascii_hex: bool,
 
     // branch
     pub branch: Option<AttrValue>,
@@ -159,12 +312,15 @@ pub struct FieldAttributes {
     pub branch_range: Option<String>,
     pub branch_value: Option<String>,
     pub branch_default: bool,
+    pub discriminant: bool,
 
     // custom encode/decode function.
     pub with_encode: Option<String>,
     pub with_decode: Option<String>,
     pub with: Option<String>,
     pub with_args: Option<String>,
+    // The arguments of the nested type, eg: `#[jbytes(args(addr_len="hdr.alen"))]` => (`addr_len`, `hdr.alen`)
+    pub args: Vec<(String, String)>,
 
     pub if_expr: Option<String>,
 
@@ -177,41 +333,95 @@ pub struct FieldAttributes {
 
     // check
     pub check_value: Option<String>,
+
+    // The spans of attribute keys and values for error messages, eg: (`length`, key span, value span).
+    pub spans: Vec<(String, Span, Option<Span>)>,
+    // The keys and values of the attributes in order, eg: (`length`, `length - 4`), which are described by `Describe`.
+    pub modifiers: Vec<(String, Option<String>)>,
 }
 
 
 impl FieldAttributes {
-    pub fn to_code(&self, is_self: bool, is_deref: bool) -> String {
+    /// Returns the code of `fattr_new`, the `arg_names` are the argument names of the container, see `is_field_ident`.
+    ///
+    /// The `rtype` is the field type, which converts the branch expression as the enum matches it, see `typed_branch_value!`,
+    /// and builds the arguments of the derived trait `crate_name`, see `ByteArgs`.
+    pub fn to_code(&self, crate_name: &str, rtype: Option<&str>, is_self: bool, is_deref: bool, arg_names: &[String]) -> String {
         let byteorder = self.byteorder.to_byteorder(is_self);
 
-        let mut length = self.length.to_code(is_self, is_deref);
+        let mut length = self.length.to_code(is_self, is_deref, arg_names);
         if let Some(length_tmp) = &self.length {
             if let Some(viariable_name) = &self.get_variable_name {
                 if viariable_name.to_string().contains(&length_tmp.to_string()) {
-                    length = self.length.to_code(false, false);
+                    length = self.length.to_code(false, false, arg_names);
                 }
             }
         }
 
-        let count = self.count.to_code(is_self, is_deref);
-        let try_count = self.try_count.to_code(is_self, is_deref);
-        let branch = self.branch.to_code(is_self, is_deref);
-        let key = self.key.to_code(false, false);
-        let split = self.split.to_code(false, false);
-        let linend = self.linend.to_code(false, false);
-        let bits = self.bits.to_code(is_self, is_deref);
+        let count = self.count.to_code(is_self, is_deref, arg_names);
+        let try_count = self.try_count.to_code(is_self, is_deref, arg_names);
+        // The branch expression is borrowed once, and passed as an integer and a typed `BranchValue`.
+        let (branch_code, branch, branch_value) = match &self.branch {
+            Some(AttrValue::Var(value)) => {
+                let value = replace_field_idents(value, is_self, is_deref, arg_names);
+                // The custom functions decode the other types than the field type.
+                let branch_value = match (rtype, &self.with_decode, &self.with_encode, &self.with) {
+                    (Some(rtype), None, None, None) => format!("jbytes::typed_branch_value!({rtype}, branch_value)"),
+                    _ => "jbytes::branch_value!(branch_value)".to_string(),
+                };
+
+                (format!("let branch_value = &({value});"),
+                    "jbytes::branch_value!(branch_value).as_usize()".to_string(),
+                    format!("Some({branch_value})"))
+            },
+            Some(value) => ("".to_string(), format!("Some({})", value.to_code(is_self, is_deref, false, arg_names)),
+                format!("Some(jbytes::BranchValue::Usize({}))", value.to_code(is_self, is_deref, false, arg_names))),
+            None => ("".to_string(), "None".to_string(), "None".to_string()),
+        };
+        // The arguments are built with the declared types of the nested type, so rustc checks the names and the types.
+        let (args_code, args) = match (self.args.is_empty(), rtype, trait_kind(crate_name)) {
+            (false, Some(rtype), Some(kind)) => {
+                let values = self.args.iter()
+                    .map(|(name, value)| format!("{name}: Clone::clone(&({})),", replace_field_idents(value, is_self, is_deref, arg_names)))
+                    .collect::<String>();
+
+                (format!("let args_value = jbytes::args::ArgsOf::<{rtype}, {kind}> {{ {values} }};"), "Some(&args_value as &dyn core::any::Any)".to_string())
+            },
+            _ => ("".to_string(), "None".to_string()),
+        };
+        let byteorder_from = match &self.byteorder_from {
+            Some(value) => format!("Some({})", replace_field_idents(value, is_self, is_deref, arg_names)),
+            None => "None".to_string(),
+        };
+        let key = self.key.to_code(false, false, arg_names);
+        let split = self.split.to_code(false, false, arg_names);
+        let linend = self.linend.to_code(false, false, arg_names);
+        let bits = self.bits.to_code(is_self, is_deref, arg_names);
         let bits_start = self.bits_start;
-        let byte_count = self.byte_count.to_code(is_self, is_deref);
-        let byte_count_outside = self.byte_count_outside.to_code(is_self, is_deref);
+        let byte_count = self.byte_count.to_code(is_self, is_deref, arg_names);
+        let byte_count_outside = self.byte_count_outside.to_code(is_self, is_deref, arg_names);
         let remaining = self.remaining;
-        let loop_skip_starts = self.loop_skip_starts.to_code(false, false);
+        let pad = if let Some(pad) = &self.pad { format!("Some(({}) as u8)", pad.to_code(false, false, false, arg_names)) } else { "None".to_string() };
+        let cstr = self.cstr;
+        // The unknown labels are rejected by `validate_attributes`.
+        let encoding = match self.encoding.as_deref().and_then(encoding_to_code) {
+            Some(encoding) => format!("Some({encoding})"),
+            None => "None".to_string(),
+        };
+        let code_units = self.code_units;
+        let bcd = self.bcd;
+        let ascii_dec = self.ascii_dec;
+        let ascii_hex = self.ascii_hex;
+        let loop_skip_starts = self.loop_skip_starts.to_code(false, false, arg_names);
 
         if self.is_use {
-            let value = format!("let fattr_new = jbytes::FieldAttrModifiers {{
-                byteorder: {byteorder}, branch: {branch}, length: {length}, count: {count}, try_count: {try_count},
+            let value = format!("{branch_code} {args_code} let fattr_new = jbytes::FieldAttrModifiers {{
+                byteorder: {byteorder}, byteorder_from: {byteorder_from}, branch: {branch}, branch_value: {branch_value}, args: {args}, length: {length}, count: {count}, try_count: {try_count},
                 split: {split}, linend_value: {linend}, bits: {bits}, bits_start: {bits_start},
                 key: {key}, byte_count: {byte_count}, byte_count_outside: {byte_count_outside},
-                remaining: {remaining}, loop_skip_starts: {loop_skip_starts},
+                remaining: {remaining}, loop_skip_starts: {loop_skip_starts}, pad: {pad}, cstr: {cstr},
+                encoding: {encoding}, code_units: {code_units},
+                bcd: {bcd}, ascii_dec: {ascii_dec}, ascii_hex: {ascii_hex},
                 ..Default::default()}}; let fattr_new = Some(&fattr_new);");
 
             if value.contains(": Some(") || value.contains(": true") || value.contains("if let Some(") {
@@ -225,6 +435,31 @@ impl FieldAttributes {
 }
 
 
+impl FieldAttributes {
+    /// Returns the start/end position code of the checksum `range`, eg: `start..field`/`field..end`,
+    /// the default range is from the start of the container to the checksum field.
+    pub fn checksum_range_to_code(&self, fields: &Fields, index: usize, end_position: &str) -> Result<(String, String)> {
+        let range = self.range.clone().unwrap_or_else(|| format!("start..{}", fields.names()[index]));
+
+        let position_code = |name: &str| {
+            match name.trim() {
+                "start" => Ok("0".to_string()),
+                "end" => Ok(end_position.to_string()),
+                name => match fields.names().iter().position(|v| v.to_string() == name) {
+                    Some(index) => Ok(format!("field_positions[{index}].0")),
+                    None => Err(Error::custom(format!("Unknown field `{name}` of range"))),
+                },
+            }
+        };
+
+        match range.split_once("..") {
+            Some((start, end)) => Ok((position_code(start)?, position_code(end)?)),
+            None => Err(Error::custom(format!("Invalid range `{range}`, eg: `start..field`"))),
+        }
+    }
+}
+
+
 impl FromAttribute for FieldAttributes {
     fn parse(group: &Group) -> Result<Option<Self>> {
         let mut result = Self::default();
@@ -237,7 +472,9 @@ impl FromAttribute for FieldAttributes {
             }
         }
 
-        let attributes = if let Some(body) = parse_tagged_attribute(group, "jbytes")? {
+        let (group, args) = take_args_attribute(group, '=')?;
+
+        let attributes = if let Some(body) = parse_tagged_attribute(&group, "jbytes")? {
             body
         }
         else {
@@ -248,13 +485,33 @@ impl FromAttribute for FieldAttributes {
             result.is_use = true;
         }
 
+        if let Some(args) = args {
+            result.is_use = true;
+
+            for (name, value) in args {
+                let value = match value.as_slice() {
+                    [TokenTree::Literal(value)] => parse_value_string(value)?,
+                    _ => return Err(Error::custom_at("Expected `name=\"expr\"`", name.span())),
+                };
+
+                result.spans.push(("args".to_string(), name.span(), None));
+                result.modifiers.push(("args".to_string(), Some(format!("{name}={value}"))));
+                result.args.push((name.to_string(), value));
+            }
+        }
+
         for attribute in attributes {
             match attribute {
                 ParsedAttribute::Tag(i) => {
                     // #xxx[xxx]
+                    result.spans.push((i.to_string(), i.span(), None));
+                    result.modifiers.push((canonical_key(&i.to_string()).to_string(), None));
+
                     match i.to_string().as_str() {
                         "enum_default" | "branch_default" => result.branch_default = true,
+                        "discriminant" => result.discriminant = true,
                         "untake" => result.untake = true,
+                        "restore" => result.restore = true,
                         "bits_start" => result.bits_start = true,
                         "skip" => result.skip = true,
                         "skip_encode" => result.skip_encode = true,
@@ -262,18 +519,44 @@ impl FromAttribute for FieldAttributes {
                         "default" | "default_value" => result.default_bool = true,
                         "from_str" => result.from_str_bool = true,
                         "remaining" => result.remaining = true,
+                        "cstr" => result.cstr = true,
+                        "code_units" => result.code_units = true,
+                        "bcd" => result.bcd = true,
+                        "ascii_dec" => result.ascii_dec = true,
+                        "ascii_hex" => result.ascii_hex = true,
                         _ => return Err(Error::custom_at("Unknown field attribute", i.span())),
                     }
                 }
                 ParsedAttribute::Property(key, val) => {
                     // #xxx[xxx=xxx]
+                    result.spans.push((key.to_string(), key.span(), Some(val.span())));
+                    result.modifiers.push((canonical_key(&key.to_string()).to_string(), Some(parse_value_string(&val)?)));
+
                     match key.to_string().as_str() {
                         "byteorder" => result.byteorder = Some(AttrValue::parse_byteorder(&val)?),
+                        "byteorder_from" => result.byteorder_from = Some(parse_value_string(&val)?),
                         "length" => result.length = Some(AttrValue::parse_usize(&val)?),
                         "offset" => result.offset = Some(AttrValue::parse_usize(&val)?),
+                        "seek" => {
+                            let value = parse_value_string(&val)?;
+
+                            match value.trim().strip_prefix("abs(").and_then(|v| v.strip_suffix(')')) {
+                                Some(value) => result.seek = Some(value.trim().to_string()),
+                                None => return Err(Error::custom_at("Expected `seek=\"abs(<expr>)\"`", val.span())),
+                            }
+                        },
+                        "seek_from_end" => result.seek_from_end = Some(parse_value_string(&val)?),
                         "count" => result.count = Some(AttrValue::parse_usize(&val)?),
                         "try_count" => result.try_count = Some(AttrValue::parse_usize(&val)?),
                         "full" => result.full = Some(AttrValue::parse_usize(&val)?),
+                        "align" => result.align = Some(AttrValue::parse_usize(&val)?),
+                        "length_of" => result.length_of = Some(parse_value_string(&val)?),
+                        "count_of" => result.count_of = Some(parse_value_string(&val)?),
+                        "magic" => result.magic = Some(AttrValue::parse_bytes(&val)?),
+                        "checksum" => result.checksum = Some(parse_checksum(&val)?),
+                        "range" => result.range = Some(parse_value_string(&val)?),
+                        "pad" => result.pad = Some(AttrValue::parse_usize(&val)?),
+                        "encoding" => result.encoding = Some(parse_value_string(&val)?),
                         "key" | "starts_with" => result.key = Some(AttrValue::parse_bytes(&val)?),
                         "split" => result.split = Some(AttrValue::parse_bytes(&val)?),
                         "linend" | "end_with" => result.linend = Some(AttrValue::parse_bytes(&val)?),
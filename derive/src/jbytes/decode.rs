@@ -1,4 +1,5 @@
 use virtue::prelude::*;
+use virtue::parse::{Fields, IdentOrIndex};
 #[allow(unused_imports)]
 use super::attribute::{FieldAttributes, ContainerAttributes};
 use super::parse::AttrValue;
@@ -11,6 +12,67 @@ pub fn generate_decode_body2(fn_body: &mut StreamBuilder, attributes: &FieldAttr
         fn_body.push_parsed(format!("input.advance(({}) as usize);", offset.to_string()))?;
     }
 
+    // seek: jumps to the absolute position or the position from the end of the input.
+    let seek_position = match (&attributes.seek, &attributes.seek_from_end) {
+        (Some(seek), _) => format!("let seek_position = ({seek}) as usize;
+            if seek_position > input.get_data().len() {{
+                return Err(jbytes::make_error(input.get_position(), jbytes::ErrorKind::InvalidPosition(seek_position)));
+            }}"),
+        (_, Some(seek)) => format!("let seek_position = match input.get_data().len().checked_sub(({seek}) as usize) {{
+                Some(value) => value,
+                None => return Err(jbytes::make_error(input.get_position(), jbytes::ErrorKind::InvalidPosition(({seek}) as usize))),
+            }};"),
+        _ => return Ok(()),
+    };
+
+    if attributes.restore {
+        fn_body.push_parsed("let seek_restore_position = input.get_position();")?;
+    }
+
+    fn_body.push_parsed(format!("{seek_position} input.set_position(seek_position);"))?;
+
+    Ok(())
+}
+
+
+/// Skips the pad bytes after a field, the alignment is relative to the start of the struct.
+#[inline]
+pub fn generate_decode_align(fn_body: &mut StreamBuilder, attributes: &FieldAttributes, cattr: &ContainerAttributes) -> Result<()> {
+    if let Some(align) = attributes.align.as_ref().or(cattr.align_fields.as_ref()) {
+        let align = align.to_code(false, false, false, &cattr.arg_names());
+
+        fn_body.push_parsed(format!("
+            input.take_bytes((({align}) - (input.get_position() - start_position) % ({align})) % ({align}))?;
+        "))?;
+    }
+
+    Ok(())
+}
+
+
+/// Verifies the `checksum` fields after all fields are decoded, the positions are relative to the start of the container.
+pub fn generate_decode_checksum(fn_body: &mut StreamBuilder, fields: &Fields) -> Result<()> {
+    for (index, field) in fields.names().iter().enumerate() {
+        let attributes = field.attributes().get_attribute::<FieldAttributes>()?.unwrap_or_default();
+
+        if let Some(checksum) = &attributes.checksum {
+            let name = match field { IdentOrIndex::Index { index, .. } => format!("v{index}"), _ => field.to_string() };
+            let (start, end) = attributes.checksum_range_to_code(fields, index, "input.get_position() - start_position")?;
+
+            fn_body.push_parsed(format!("{{
+                let field_position = field_positions[{index}];
+                let checksum_value = match {checksum}.checksum_range(&input.get_data()[start_position..], ({start})..({end}), field_position.0..field_position.1) {{
+                    Some(value) => value,
+                    None => return Err(jbytes::make_error(start_position, jbytes::ErrorKind::InvalidByteLength)),
+                }};
+
+                if checksum_value != {name} as u64 {{
+                    return Err(jbytes::make_error(start_position + field_position.0, jbytes::ErrorKind::InvalidChecksum(checksum_value, {name} as u64)));
+                }}
+            }}"))?;
+        }
+    }
+
     Ok(())
 }
 
@@ -30,6 +92,16 @@ pub fn generate_decode_body(fn_body: &mut StreamBuilder, crate_name: &str, attri
         fn_body.push_parsed(format!("let {name}: {rtype} = {func}::decode(input, cattr_new, fattr_new, {with_args})?;"))?;
         // return Ok(());
     }
+    else if let Some(magic) = &attributes.magic {
+        fn_body.push_parsed(format!("input.take_magic({})?; let {name} = <{rtype}>::default();", magic.to_string()))?;
+    }
+    else if attributes.discriminant {
+        // The enum variant captures the matched branch value.
+        fn_body.push_parsed(format!("let {name} = match <{rtype}>::try_from(value) {{
+            Ok(value) => value,
+            Err(_) => return Err(jbytes::make_error(input.get_position(), jbytes::ErrorKind::Fail)),
+        }};"))?;
+    }
     else if attributes.skip || attributes.skip_decode {
         fn_body.push_parsed(format!("let {name} = {rtype}::default();"))?;
         // return Ok(());
@@ -55,9 +127,11 @@ pub fn generate_decode_body(fn_body: &mut StreamBuilder, crate_name: &str, attri
         }
 
         if let Some(if_expr) = &attributes.if_expr {
+            let default_value = attributes.default_value.clone().unwrap_or_else(|| "Default::default()".to_string());
+
             fn_body.push_parsed(format!("let {name}: {rtype} = if {if_expr} {{ 
                 {crate_name}::decode_inner(input, cattr_new, fattr_new)?
-            }} else {{ None }};"))?;
+            }} else {{ {default_value} }};"))?;
         }
         else {
             fn_body.push_parsed(format!("let {name}: {rtype} = {crate_name}::decode_inner(input, cattr_new, fattr_new)?;"))?;
@@ -80,6 +154,11 @@ pub fn generate_decode_body(fn_body: &mut StreamBuilder, crate_name: &str, attri
         } 
     }
 
+    // The parent continues after the furthest pointed-to data, as the encode lays it out after the inline fields.
+    if attributes.restore {
+        fn_body.push_parsed("seek_end_position = seek_end_position.max(input.get_position()); input.set_position(seek_restore_position);")?;
+    }
+
     // variable_name
     if let Some(value) = &attributes.variable_name {
         if let AttrValue::List(variable_names) = value {
@@ -0,0 +1,151 @@
+use virtue::parse::Fields;
+use virtue::prelude::*;
+use super::attribute::{ContainerAttributes, FieldAttributes};
+use super::derive_enum::iter_variants;
+use super::derive_struct::get_field_type;
+use super::parse::is_int_type;
+use super::validate::{validate_fields, validate_variants};
+
+
+/// The wrapper types whose element type is described, eg: `Vec<Header>` => `Header`.
+const WRAPPER_TYPES: &[&str] = &["Vec<", "Option<", "Box<", "Lazy<"];
+
+
+pub(crate) struct DeriveDescribe {
+    pub fields: Option<Fields>,
+    pub variants: Option<Vec<EnumVariant>>,
+    pub attributes: ContainerAttributes,
+    // The type parameters of the container, which aren't described.
+    pub generics: Vec<String>,
+}
+
+
+/// Returns the nested type of a field type, eg: `Vec<Header>`/`[Header; 2]`/`&'a Header` => `Header`,
+/// it's linked if it implements `Describe`.
+fn get_nested_type(rtype: &str, generics: &[String]) -> Option<String> {
+    let rtype = rtype.trim().trim_start_matches('&');
+    // The lifetime of a reference or a wrapper, eg: `'a Header`/`'a, T`.
+    let rtype = match rtype.strip_prefix('\'') {
+        Some(value) => value.trim_start_matches(|v: char| v.is_alphanumeric() || v == '_').trim_start_matches(',').trim(),
+        None => rtype,
+    };
+
+    if let Some(prefix) = WRAPPER_TYPES.iter().find(|v| rtype.starts_with(*v)) {
+        return get_nested_type(rtype[prefix.len()..].strip_suffix('>')?, generics);
+    }
+
+    if let Some(value) = rtype.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+        return get_nested_type(value.split(';').next()?, generics);
+    }
+
+    // The last segment of the path, eg: `std::net::Ipv4Addr` => `Ipv4Addr`.
+    let ident = rtype.split('<').next()?.rsplit("::").next()?;
+
+    // The recursive types aren't linked, eg: `Box<Self>`.
+    if !ident.starts_with(|v: char| v.is_ascii_uppercase()) || ident == "Self" || generics.iter().any(|v| v == ident) {
+        return None;
+    }
+
+    Some(rtype.to_string())
+}
+
+
+/// Removes the spaces between the type tokens, eg: `Vec < Header >` => `Vec<Header>`, `[u8 ; 4]` => `[u8; 4]`.
+fn normalize_type(rtype: &str) -> String {
+    [(" <", "<"), ("< ", "<"), (" >", ">"), (" ,", ","), (" ;", ";"), ("& ", "&"), (" ::", "::"), (":: ", "::")]
+        .iter()
+        .fold(rtype.trim().to_string(), |rtype, (from, to)| rtype.replace(from, to))
+}
+
+
+/// Returns the code of the modifiers, eg: `&[jbytes::schema::Modifier { key: "length", value: Some("2") }]`.
+fn modifiers_to_code(modifiers: &[(String, Option<String>)]) -> String {
+    let modifiers = modifiers.iter().map(|(key, value)| {
+        let value = match value {
+            Some(value) => format!("Some({value:?})"),
+            None => "None".to_string(),
+        };
+
+        format!("jbytes::schema::Modifier {{ key: {key:?}, value: {value} }}")
+    }).collect::<Vec<String>>().join(", ");
+
+    format!("&[{modifiers}]")
+}
+
+
+impl DeriveDescribe {
+    fn fields_to_code(&self, fields: &Option<Fields>) -> Result<String> {
+        let mut code = vec![];
+
+        let fields = match fields {
+            Some(Fields::Struct(value)) => value.iter().map(|(ident, field)| (ident.to_string(), field)).collect::<Vec<_>>(),
+            Some(Fields::Tuple(value)) => value.iter().enumerate().map(|(index, field)| (index.to_string(), field)).collect::<Vec<_>>(),
+            None => vec![],
+        };
+
+        for (name, field) in fields {
+            let attributes = field.attributes.get_attribute::<FieldAttributes>()?.unwrap_or_default();
+            let rtype = normalize_type(&get_field_type(field));
+
+            let schema = match get_nested_type(&rtype, &self.generics) {
+                Some(nested) => format!("{{ #[allow(unused_imports)] use jbytes::schema::NoDescribe as _; <jbytes::schema::DescribeCheck<{nested}>>::SCHEMA }}"),
+                None => "None".to_string(),
+            };
+
+            code.push(format!("jbytes::schema::FieldSchema {{ name: {:?}, rtype: {rtype:?}, modifiers: {}, schema: {schema} }}",
+                name.trim_start_matches("r#"), modifiers_to_code(&attributes.modifiers)));
+        }
+
+        Ok(format!("&[{}]", code.join(", ")))
+    }
+
+    fn variants_to_code(&self, variants: &[EnumVariant]) -> Result<String> {
+        let mut code = vec![];
+
+        // The integer branch values are encoded by the enum or passed from the `branch` field.
+        let is_int_branch = match &self.attributes.branch_type {
+            Some(branch_type) => is_int_type(branch_type),
+            None => self.attributes.branch_take_bytes.is_none() && !self.attributes.branch_starts_with && !self.attributes.branch_starts_with_untake,
+        };
+
+        for (variant_index, variant) in iter_variants(variants) {
+            let attributes = variant.attributes.get_attribute::<FieldAttributes>()?.unwrap_or_default();
+
+            let branch = match is_int_branch && !attributes.branch_default && attributes.branch_bits.is_none() {
+                true => format!("Some({variant_index})"),
+                false => "None".to_string(),
+            };
+
+            code.push(format!("jbytes::schema::VariantSchema {{ name: {:?}, branch: {branch}, modifiers: {}, fields: {} }}",
+                variant.name.to_string(), modifiers_to_code(&attributes.modifiers), self.fields_to_code(&variant.fields)?));
+        }
+
+        Ok(format!("&[{}]", code.join(", ")))
+    }
+
+    pub fn generate_describe(&self, generator: &mut Generator) -> Result<()> {
+        let body = match &self.variants {
+            Some(variants) => {
+                validate_variants(variants, &self.attributes)?;
+                format!("jbytes::schema::SchemaBody::Enum({})", self.variants_to_code(variants)?)
+            },
+            None => {
+                validate_fields(&self.fields, &self.attributes)?;
+                format!("jbytes::schema::SchemaBody::Struct({})", self.fields_to_code(&self.fields)?)
+            },
+        };
+
+        let name = generator.target_name().to_string();
+        let modifiers = modifiers_to_code(&self.attributes.modifiers);
+
+        generator
+            .impl_for("jbytes::Describe")
+            .generate_const("SCHEMA", "jbytes::schema::Schema")
+            .with_value(|b| {
+                b.push_parsed(format!("jbytes::schema::Schema {{ name: {name:?}, modifiers: {modifiers}, body: {body} }}"))?;
+                Ok(())
+            })?;
+
+        Ok(())
+    }
+}
@@ -3,9 +3,10 @@ use virtue::{generate::Generator, parse::IdentOrIndex};
 use virtue::prelude::*;
 #[allow(unused_imports)]
 use super::attribute::{ContainerAttributes, FieldAttributes};
-use super::parse::AttrValue;
-use super::derive_struct::{generate_decode_struct_body, generate_decode_return};
-use super::encode::{generate_encode_body, generate_encode_body2};
+use super::parse::{AttrValue, is_int_type, branch_type_to_code, branch_type_to_branch, is_typed_branch};
+use super::validate::validate_variants;
+use super::derive_struct::{generate_decode_struct_body, generate_decode_return, has_align_fields, has_checksum_fields, has_length_of_fields, generate_generic_bounds, get_field_type, generate_args, generate_ctx, generate_ctx_check};
+use super::encode::{generate_encode_body, generate_encode_body2, generate_encode_align, generate_encode_length_of, generate_encode_length_of_patch, generate_encode_checksum, generate_encode_if_expr};
 use super::decode::generate_decode_body2;
 
 
@@ -19,18 +20,18 @@ pub(crate) struct DeriveEnum {
 
 impl DeriveEnum {
     fn iter_fields(&self) -> EnumVariantIterator {
-        EnumVariantIterator {
-            idx: 0,
-            variants: &self.variants,
-            curruent_idx: 0,
-        }
+        iter_variants(&self.variants)
     }
 
     pub fn generate_decode(&self, generator: &mut Generator) -> Result<()> {
         let crate_name = "jbytes::ByteDecode";
+        validate_variants(&self.variants, &self.attributes)?;
+        generate_args(generator, &self.attributes, crate_name)?;
+        generate_ctx(generator, &self.attributes, crate_name)?;
+        let mut impl_for = generator.impl_for(crate_name);
+        generate_generic_bounds(&mut impl_for, &self.attributes, crate_name)?;
 
-        generator
-            .impl_for(crate_name)
+        impl_for
             .generate_fn("decode_inner")
             .with_generic_deps("I", ["jbytes::BufRead"])
             .with_arg("input", "&I")
@@ -47,8 +48,28 @@ impl DeriveEnum {
     }
 
 
+    /// Implements `BranchType`, so the `branch` expression of a parent field is checked at compile time.
+    pub fn generate_branch_type(&self, generator: &mut Generator) -> Result<()> {
+        let branch = match &self.attributes.branch_type {
+            // The branch is read by the enum itself.
+            _ if self.attributes.branch_starts_with || self.attributes.branch_starts_with_untake || self.attributes.branch_take_bytes.is_some() => {
+                "jbytes::branch::Unchecked".to_string()
+            },
+            Some(branch_type) => branch_type_to_branch(branch_type),
+            None => "usize".to_string(),
+        };
+
+        generator.impl_for("jbytes::branch::BranchType").impl_type("Branch", branch)?;
+
+        Ok(())
+    }
+
+
     pub fn generate_borrow_decode(&self, generator: &mut Generator) -> Result<()> {
         let crate_name = "jbytes::BorrowByteDecode";
+        validate_variants(&self.variants, &self.attributes)?;
+        generate_args(generator, &self.attributes, crate_name)?;
+        generate_ctx(generator, &self.attributes, crate_name)?;
 
         let mut impl_for = if let Some(lifetimes) = &self.lifetimes {
             generator
@@ -66,6 +87,8 @@ impl DeriveEnum {
             "'de"
         };
 
+        generate_generic_bounds(&mut impl_for, &self.attributes, &format!("{crate_name}<{lifetimes}>"))?;
+
         impl_for
             .generate_fn("decode_inner")
             .with_generic_deps("I", ["jbytes::BufRead"])
@@ -90,7 +113,7 @@ impl DeriveEnum {
             fn_body.push_parsed(format!("{func}::decode(input, cattr, fattr)"))?;
         }
         else {
-            fn_body.push_parsed(self.attributes.to_code(false))?;
+            fn_body.push_parsed(self.attributes.to_code(false, crate_name))?;
 
             if let Some(value) = &self.attributes.get_variable_name {
                 if let AttrValue::List(variable_names) = value {
@@ -109,6 +132,17 @@ impl DeriveEnum {
                 }
             }
 
+            for variant in &self.variants {
+                if has_align_fields(&variant.fields, &self.attributes)? || has_checksum_fields(&variant.fields)? {
+                    fn_body.push_parsed("let start_position = input.get_position();")?;
+                    break;
+                }
+            }
+
+            if let Some(magic) = &self.attributes.magic {
+                fn_body.push_parsed(format!("input.take_magic({})?;", magic.to_string()))?;
+            }
+
             if self.attributes.branch_starts_with || self.attributes.branch_starts_with_untake {
                 self.generate_byte_decode_body2(crate_name, fn_body)?;
             }
@@ -196,7 +230,7 @@ impl DeriveEnum {
                     variant_body.push_parsed(format!("input.advance({branch_value}.len());"))?;
                 }
 
-                variant_body.push_parsed(attributes.to_code(true, true))?;
+                variant_body.push_parsed(attributes.to_code(crate_name, None, true, true, &self.attributes.arg_names()))?;
                 generate_decode_body2(variant_body, &attributes)?;
                 generate_decode_struct_body(variant_body, crate_name, &variant.fields, &self.attributes, true)?;
                 generate_decode_return(variant_body, &variant.fields, Some(variant))?;        
@@ -260,7 +294,16 @@ impl DeriveEnum {
             return Ok(());
         }
         
-        let code = if let Some(branch_take_bytes) = &self.attributes.branch_take_bytes {
+        let code = if let Some(branch_type) = &self.attributes.branch_type {
+            // The typed branch value is matched on the original type, eg: `&str`/`&[u8]`/`(u8, u8)`.
+            let branch_value = branch_type_to_code(branch_type);
+
+            format!("let value = match fattr.and_then(|fr| fr.branch_value){branch_value} {{
+                Some(value) => value,
+                None => return Err(jbytes::make_error(input.get_position(), jbytes::ErrorKind::Fail)),
+            }};")
+        }
+        else if let Some(branch_take_bytes) = &self.attributes.branch_take_bytes {
             let branch_take_bytes = branch_take_bytes.to_code2(false, false);
             format!("let value = input.take_bytes({branch_take_bytes})?;")
         }
@@ -278,6 +321,10 @@ impl DeriveEnum {
                     if let Some(branch) = fr.branch {{
                         value = (branch) as usize;
                     }}
+                    else if fr.branch_value.is_some() {{
+                        // The non-integer branch requires `#[jbytes(branch_type=\"T\")]`.
+                        return Err(jbytes::make_error(input.get_position(), jbytes::ErrorKind::Fail));
+                    }}
                     else if let Some(byte_count) = fr.byte_count {{
                         value = input.take_byteorder_uint(byte_count, jbytes::get_byteorder(cattr, fattr))? as usize;
                     }}
@@ -298,7 +345,11 @@ impl DeriveEnum {
         };
 
         fn_body.push_parsed(code)?;
-        fn_body.push_parsed("match value")?;
+
+        match &self.attributes.branch_type {
+            Some(branch_type) if is_typed_branch(branch_type) => fn_body.push_parsed("match *value")?,
+            _ => fn_body.push_parsed("match value")?,
+        };
         fn_body.group(Delimiter::Brace, |variant_case| {
             let mut branch_default = false;
 
@@ -330,13 +381,22 @@ impl DeriveEnum {
                 else if let Some(branch_range) = &attributes.branch_range {
                     variant_case.push_parsed(branch_range.to_string())?;
                 }
+                else if matches!(&self.attributes.branch_type, Some(branch_type) if !is_int_type(branch_type)) {
+                    return Err(Error::custom_at("`branch_value` is required when `branch_type` isn't an integer type", variant.name.span()));
+                }
                 else {
                     variant_case.push_parsed( variant_index.to_string())?;
                 }
 
                 variant_case.puncts("=>");
                 variant_case.group(Delimiter::Brace, |variant_body| {
-                    variant_body.push_parsed(attributes.to_code(true, true))?;
+                    variant_body.push_parsed(attributes.to_code(crate_name, None, true, true, &self.attributes.arg_names()))?;
+
+                    // The discriminant field owns a copy of the typed branch value.
+                    if matches!(&self.attributes.branch_type, Some(branch_type) if is_typed_branch(branch_type)) && self.get_discriminant_field(variant)?.is_some() {
+                        variant_body.push_parsed("let value = Clone::clone(value);")?;
+                    }
+
                     generate_decode_body2(variant_body, &attributes)?;
                     generate_decode_struct_body(variant_body, crate_name, &variant.fields, &self.attributes, true)?;
                     generate_decode_return(variant_body, &variant.fields, Some(variant))?;
@@ -354,6 +414,30 @@ impl DeriveEnum {
         Ok(())
     }
 
+    /// Returns the name of the `#[jbytes(discriminant)]` field of the variant.
+    fn get_discriminant_field(&self, variant: &EnumVariant) -> Result<Option<String>> {
+        if let Some(fields) = &variant.fields {
+            match fields {
+                Fields::Struct(value) => {
+                    for (ident, field) in value {
+                        if field.attributes.get_attribute::<FieldAttributes>()?.unwrap_or_default().discriminant {
+                            return Ok(Some(ident.to_string()));
+                        }
+                    }
+                },
+                Fields::Tuple(value) => {
+                    for (index, field) in value.iter().enumerate() {
+                        if field.attributes.get_attribute::<FieldAttributes>()?.unwrap_or_default().discriminant {
+                            return Ok(Some(format!("v{index}")));
+                        }
+                    }
+                },
+            }
+        }
+
+        Ok(None)
+    }
+
     pub fn generate_encode(&self, generator: &mut Generator) -> Result<()> {
         self.generate_byte_encode_body("jbytes::ByteEncode", generator)?;
         Ok(())
@@ -365,8 +449,14 @@ impl DeriveEnum {
     }
 
     fn generate_byte_encode_body(&self, crate_name: &str, generator: &mut Generator) -> Result<()> {
-        generator
-            .impl_for(crate_name)
+        validate_variants(&self.variants, &self.attributes)?;
+        generate_args(generator, &self.attributes, crate_name)?;
+        generate_ctx(generator, &self.attributes, crate_name)?;
+
+        let mut impl_for = generator.impl_for(crate_name);
+        generate_generic_bounds(&mut impl_for, &self.attributes, crate_name)?;
+
+        impl_for
             .generate_fn("encode_inner")
             .with_generic_deps("B", ["jbytes::BufWrite"])
             .with_self_arg(FnSelfArg::RefSelf)
@@ -430,7 +520,19 @@ impl DeriveEnum {
                     }
                 }        
 
-                fn_body.push_parsed(self.attributes.to_code(true))?;
+                fn_body.push_parsed(self.attributes.to_code(true, crate_name))?;
+
+                for variant in &self.variants {
+                    if has_checksum_fields(&variant.fields)? || has_length_of_fields(&variant.fields)? {
+                        fn_body.push_parsed("let start_position = buffer.get_position();")?;
+                        break;
+                    }
+                }
+
+                if let Some(magic) = &self.attributes.magic {
+                    fn_body.push_parsed(format!("r_nbytes += buffer.push_bytes({})?;", magic.to_string()))?;
+                }
+
                 fn_body.push_parsed("match self")?;
                 fn_body.group(Delimiter::Brace, |variant_case| {    
                     for (variant_index, variant) in self.iter_fields() {
@@ -466,10 +568,28 @@ impl DeriveEnum {
 
                         variant_case.puncts("=>");
 
+                        // #[jbytes(discriminant)] field writes back the captured branch value.
+                        let discriminant = self.get_discriminant_field(variant)?;
+                        let variant_index = match &discriminant {
+                            Some(name) => format!("(*{name})"),
+                            None => variant_index.to_string(),
+                        };
+
                         variant_case.group(Delimiter::Brace, |variant_body| {
-                            variant_body.push_parsed(attributes.to_code(true, false))?;
+                            // The positions are used to patch the `checksum`/`length_of` fields.
+                            let is_position = has_checksum_fields(&variant.fields)? || has_length_of_fields(&variant.fields)?;
+                            let position_code = |index: usize, end: usize| format!("field_positions[{index}].{end} = r_nbytes;");
+
+                            variant_body.push_parsed(attributes.to_code(crate_name, None, true, false, &self.attributes.arg_names()))?;
 
-                            if self.attributes.branch_starts_with {
+                            if let (true, Some(fields)) = (is_position, variant.fields.as_ref()) {
+                                variant_body.push_parsed(format!("let mut field_positions = [(0usize, 0usize); {}];", fields.names().len()))?;
+                            }
+
+                            if self.attributes.branch_type.is_some() {
+                                // The typed branch value is encoded by the outer field.
+                            }
+                            else if self.attributes.branch_starts_with {
                                 // #[jbytes(branch_vlaue=b"xx")
                                 if let Some(branch_value) = &attributes.branch_value {
                                     variant_body.push_parsed(format!("r_nbytes += buffer.push_bytes({branch_value})?;"))?;
@@ -483,7 +603,10 @@ impl DeriveEnum {
                             }
                             else if self.attributes.branch_take_bytes.is_some() {
                                 // #[jbytes(branch_vlaue=b"xx")
-                                if let Some(branch_value) = &attributes.branch_value {
+                                if let Some(name) = &discriminant {
+                                    variant_body.push_parsed(format!("r_nbytes += buffer.push({name})?;"))?;
+                                }
+                                else if let Some(branch_value) = &attributes.branch_value {
                                     variant_body.push_parsed(format!("r_nbytes += buffer.push_bytes({branch_value})?;"))?;
                                 }                    
                                 else {
@@ -497,7 +620,7 @@ impl DeriveEnum {
                                     let cr_byte_count = if let Some(cr) = cattr_new {{ cr.byte_count }} else {{ None }};
                         
                                     if let Some(fr) = fattr {{
-                                        if let Some(_branch) = fr.branch {{
+                                        if fr.branch.is_some() || fr.branch_value.is_some() {{
                                             // This is a placeholder condition
                                         }}
                                         else if let Some(byte_count) = cr_byte_count {{
@@ -525,15 +648,28 @@ impl DeriveEnum {
                             if let Some(fields) = &variant.fields {
                                 match fields {
                                     Fields::Struct(value) => {
-                                        for (ident, field) in value {
+                                        for (index, (ident, field)) in value.iter().enumerate() {
                                             let mut attributes = field.attributes.get_attribute::<FieldAttributes>()?.unwrap_or_default();
                                             attributes.get_variable_name = self.attributes.get_variable_name.clone();
 
+                                            if attributes.discriminant {
+                                                continue;
+                                            }
+
                                             if attributes.is_use {
-                                                variant_body.push_parsed(attributes.to_code(false, true))?;
+                                                variant_body.push_parsed(attributes.to_code(crate_name, Some(&get_field_type(field)), false, true, &self.attributes.arg_names()))?;
                                             }
 
-                                            generate_encode_body(variant_body, &attributes, crate_name, &ident.to_string(), false)?;
+                                            if is_position { variant_body.push_parsed(position_code(index, 0))?; }
+                                            generate_ctx_check(variant_body, &attributes, &self.attributes, crate_name, &format!("&*{ident}"))?;
+                                            generate_encode_if_expr(variant_body, &attributes, fields, false, |variant_body| {
+                                                match generate_encode_length_of(variant_body, &attributes, &ident.to_string(), fields, false)? {
+                                                    Some(name) => generate_encode_body(variant_body, &attributes, crate_name, &name, false),
+                                                    None => generate_encode_body(variant_body, &attributes, crate_name, &ident.to_string(), false),
+                                                }
+                                            })?;
+                                            if is_position { variant_body.push_parsed(position_code(index, 1))?; }
+                                            generate_encode_align(variant_body, &attributes, &self.attributes, false)?;
                                         }
                                     },
                                     Fields::Tuple(value) => {
@@ -541,14 +677,30 @@ impl DeriveEnum {
                                             let mut attributes = field.attributes.get_attribute::<FieldAttributes>()?.unwrap_or_default();
                                             attributes.get_variable_name = self.attributes.get_variable_name.clone();
 
+                                            if attributes.discriminant {
+                                                continue;
+                                            }
+
                                             if attributes.is_use {
-                                                variant_body.push_parsed(attributes.to_code(false, false))?;
+                                                variant_body.push_parsed(attributes.to_code(crate_name, Some(&get_field_type(field)), false, false, &self.attributes.arg_names()))?;
                                             }
 
-                                            generate_encode_body(variant_body, &attributes, crate_name, &format!("v{index}"), false)?;
+                                            if is_position { variant_body.push_parsed(position_code(index, 0))?; }
+                                            generate_ctx_check(variant_body, &attributes, &self.attributes, crate_name, &format!("&*v{index}"))?;
+                                            generate_encode_if_expr(variant_body, &attributes, fields, false, |variant_body| {
+                                                match generate_encode_length_of(variant_body, &attributes, &index.to_string(), fields, false)? {
+                                                    Some(name) => generate_encode_body(variant_body, &attributes, crate_name, &name, false),
+                                                    None => generate_encode_body(variant_body, &attributes, crate_name, &format!("v{index}"), false),
+                                                }
+                                            })?;
+                                            if is_position { variant_body.push_parsed(position_code(index, 1))?; }
+                                            generate_encode_align(variant_body, &attributes, &self.attributes, false)?;
                                         }
                                     },
                                 }
+
+                                generate_encode_length_of_patch(variant_body, crate_name, fields, false, true, &self.attributes.arg_names())?;
+                                generate_encode_checksum(variant_body, crate_name, fields, false, true, &self.attributes.arg_names())?;
                             }
                             Ok(())
                         })?;
@@ -568,7 +720,17 @@ impl DeriveEnum {
 }
 
 
-struct EnumVariantIterator<'a> {
+/// Iterates the variants with their branch values, eg: `0usize`/`3usize` of `branch_value=3`.
+pub(crate) fn iter_variants(variants: &[EnumVariant]) -> EnumVariantIterator<'_> {
+    EnumVariantIterator {
+        idx: 0,
+        variants,
+        curruent_idx: 0,
+    }
+}
+
+
+pub(crate) struct EnumVariantIterator<'a> {
     variants: &'a [EnumVariant],
     idx: usize,
     curruent_idx: usize,
@@ -598,19 +760,20 @@ impl<'a> Iterator for EnumVariantIterator<'a> {
         } else { None };
 
         if let Some(val_string) = val_string {
+            // The typed patterns aren't integers, eg: `"GET"`/`(1, _)`/`Method::Get`.
             if val_string.starts_with("0x") {
-                self.curruent_idx = usize::from_str_radix(&val_string[2..], 16).unwrap();
+                self.curruent_idx = usize::from_str_radix(&val_string[2..], 16).unwrap_or(self.curruent_idx);
             }
             else if let Some(offset) = val_string.find("..=") {
-                self.curruent_idx = val_string[offset + 3..].parse::<usize>().unwrap();
+                self.curruent_idx = val_string[offset + 3..].parse::<usize>().unwrap_or(self.curruent_idx);
             }
             else if let Some(offset) = val_string.find("..") {
-                self.curruent_idx = val_string[offset + 2..].parse::<usize>().unwrap();
+                self.curruent_idx = val_string[offset + 2..].parse::<usize>().unwrap_or(self.curruent_idx);
             }
             else if val_string.contains("b\"") {
             }
             else {
-                self.curruent_idx = val_string.parse::<usize>().unwrap();
+                self.curruent_idx = val_string.parse::<usize>().unwrap_or(self.curruent_idx);
             }    
         }
 
@@ -0,0 +1,126 @@
+use virtue::parse::Fields;
+use virtue::prelude::*;
+use super::attribute::{ContainerAttributes, FieldAttributes};
+use super::derive_struct::{get_field_type, generate_generic_bounds};
+use super::parse::{AttrValue, is_int_type};
+use super::validate::{validate_fields, error_at};
+
+
+/// The field attributes which keep the size of the field fixed, the others are rejected by `FixedSize`.
+const FIXED_SIZE_ATTRIBUTES: &[&str] = &[
+    "byteorder", "byteorder_from", "length", "count", "offset", "untake", "bits", "bits_start", "skip",
+    "checksum", "range", "length_of", "count_of", "value_encode", "value_decode", "check_value", "default_value", "default",
+];
+
+
+pub(crate) struct DeriveFixedSize {
+    pub fields: Option<Fields>,
+    pub attributes: ContainerAttributes,
+}
+
+
+/// Returns the literal value of an attribute, eg: `length=2`.
+fn literal_value(attributes: &FieldAttributes, key: &str, value: &Option<AttrValue>) -> Result<Option<usize>> {
+    match value {
+        Some(AttrValue::Usize(v)) => Ok(Some(*v)),
+        Some(_) => Err(error_at(attributes, key, true, format!("`FixedSize` only supports the literal `{key}`, eg: `{key}=2`"))),
+        None => Ok(None),
+    }
+}
+
+
+/// Returns the size code of a field type, eg: `4`/`<Header as jbytes::FixedSize>::SIZE`.
+fn type_size(attributes: &FieldAttributes, rtype: &str) -> Result<String> {
+    let length = literal_value(attributes, "length", &attributes.length)?;
+    let count = literal_value(attributes, "count", &attributes.count)?;
+
+    // The bytes types are fixed by `length`, the integers are encoded with `length` bytes.
+    let is_bytes = rtype == "String" || rtype.starts_with('&') && (rtype.ends_with("str") || rtype.ends_with("[u8]"));
+
+    if let Some(length) = length {
+        if is_int_type(rtype) || is_bytes {
+            return Ok(length.to_string());
+        }
+    }
+    else if is_bytes {
+        return Err(error_at(attributes, "length", false, format!("`FixedSize` requires a literal `length` of `{rtype}`")));
+    }
+
+    // The `length`/`count` modifiers are passed to the elements of `Vec<T>`/`[T; N]`.
+    let element = |rtype: &str| match length {
+        Some(length) if is_int_type(rtype) => length.to_string(),
+        _ => format!("<{rtype} as jbytes::FixedSize>::SIZE"),
+    };
+
+    if let Some(element_type) = rtype.strip_prefix("Vec<").and_then(|v| v.strip_suffix('>')) {
+        return match count {
+            Some(count) => Ok(format!("{count} * {}", element(element_type))),
+            None => Err(Error::custom(format!("`FixedSize` requires a literal `count` of `{rtype}`"))),
+        };
+    }
+
+    if let Some((element_type, count)) = rtype.strip_prefix('[').and_then(|v| v.strip_suffix(']')).and_then(|v| v.rsplit_once(';')) {
+        return Ok(format!("({count}) * {}", element(element_type)));
+    }
+
+    Ok(element(rtype))
+}
+
+
+impl DeriveFixedSize {
+    pub fn generate_fixed_size(&self, generator: &mut Generator) -> Result<()> {
+        validate_fields(&self.fields, &self.attributes)?;
+
+        if self.attributes.magic.is_some() || self.attributes.align_fields.is_some() || self.attributes.byte_count.is_some()
+            || self.attributes.with.is_some() || self.attributes.with_decode.is_some() || self.attributes.with_encode.is_some() {
+            return Err(Error::custom("`FixedSize` doesn't support the container attributes `magic`/`align_fields`/`byte_count`/`with`"));
+        }
+
+        let fields = match &self.fields {
+            Some(Fields::Struct(value)) => value.iter().map(|(_, field)| field).collect::<Vec<_>>(),
+            Some(Fields::Tuple(value)) => value.iter().collect::<Vec<_>>(),
+            None => vec![],
+        };
+
+        // The size is the end of the last field, the `untake` fields share the bytes with the next field.
+        let mut offset = String::from("0");
+        let mut size_code = String::new();
+
+        for field in fields {
+            let attributes = field.attributes.get_attribute::<FieldAttributes>()?.unwrap_or_default();
+            let rtype = get_field_type(field).replace(' ', "");
+
+            if let Some((key, span, _)) = attributes.spans.iter().find(|v| !FIXED_SIZE_ATTRIBUTES.contains(&v.0.as_str())) {
+                return Err(Error::custom_at(format!("`{key}` isn't supported by `FixedSize`, the field must have a fixed size"), *span));
+            }
+
+            if attributes.skip {
+                continue;
+            }
+
+            if let Some(value) = literal_value(&attributes, "offset", &attributes.offset)? {
+                offset = format!("{offset} + {value}");
+            }
+
+            let size = type_size(&attributes, &rtype)?;
+            size_code.push_str(&format!("let end = {offset} + {size}; if end > size {{ size = end; }}"));
+
+            if !attributes.untake {
+                offset = format!("{offset} + {size}");
+            }
+        }
+
+        let crate_name = "jbytes::FixedSize";
+        let mut impl_for = generator.impl_for(crate_name);
+        generate_generic_bounds(&mut impl_for, &self.attributes, crate_name)?;
+
+        impl_for
+            .generate_const("SIZE", "usize")
+            .with_value(|b| {
+                b.push_parsed(format!("{{ #[allow(unused_mut)] let mut size = 0; {size_code} size }}"))?;
+                Ok(())
+            })?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,326 @@
+use std::collections::BTreeMap;
+use virtue::generate::ImplFor;
+use virtue::parse::Fields;
+use virtue::prelude::*;
+use super::attribute::{ContainerAttributes, FieldAttributes};
+use super::derive_enum::iter_variants;
+use super::derive_struct::{get_field_type, generate_generic_bounds};
+use super::parse::{AttrValue, is_int_type, replace_expr_idents};
+use super::validate::{error_at, validate_fields, validate_variants};
+
+
+const CRATE_NAME: &str = "jbytes::testing::ByteGenerate";
+
+
+pub(crate) struct DeriveGenerate {
+    pub fields: Option<Fields>,
+    pub attributes: ContainerAttributes,
+}
+
+
+pub(crate) struct DeriveGenerateEnum {
+    pub variants: Vec<EnumVariant>,
+    pub attributes: ContainerAttributes,
+}
+
+
+/// The fields referenced by the modifiers of the other fields, which are generated as the valid values.
+enum Referenced {
+    // `length`/`count`, eg: `#[jbytes(length="length - 2")]` => `length` is a small integer plus the offset `2`.
+    Length(String),
+    // `byteorder`, eg: `#[jbytes(byteorder="order")]` => `order` is `0`/`1`.
+    ByteOrder,
+    // `branch`, eg: `#[jbytes(branch="cmd")]` => `cmd` is a branch value of the enum type.
+    Branch(String),
+}
+
+
+/// Returns the local names, types and attributes of the fields, the tuple fields are named `v{index}`.
+fn get_fields(fields: &Option<Fields>) -> Result<Vec<(String, String, FieldAttributes)>> {
+    let mut result = vec![];
+
+    match fields {
+        Some(Fields::Struct(value)) => {
+            for (ident, field) in value {
+                result.push((ident.to_string(), get_field_type(field), field.attributes.get_attribute::<FieldAttributes>()?.unwrap_or_default()));
+            }
+        },
+        Some(Fields::Tuple(value)) => {
+            for (index, field) in value.iter().enumerate() {
+                result.push((format!("v{index}"), get_field_type(field), field.attributes.get_attribute::<FieldAttributes>()?.unwrap_or_default()));
+            }
+        },
+        None => {},
+    }
+
+    Ok(result)
+}
+
+
+/// Returns the field and the offset of a `length`/`count` expression, eg: `length` => `(length, 0)`,
+/// `length + 2` => `(length, 0)`, `length - 2` => `(length, 2)`.
+fn parse_length_source(expr: &str) -> Option<(String, String)> {
+    let is_ident = |v: &str| v.starts_with(|c: char| c.is_alphabetic() || c == '_') && v.chars().all(|c| c.is_alphanumeric() || c == '_');
+    let is_literal = |v: &str| v.starts_with(|c: char| c.is_ascii_digit()) && v.chars().all(|c| c.is_alphanumeric() || c == '_');
+    let expr = expr.trim();
+
+    if is_ident(expr) {
+        return Some((expr.to_string(), "0".to_string()));
+    }
+
+    let (index, op) = expr.char_indices().find(|(_, c)| *c == '+' || *c == '-')?;
+    let (ident, offset) = (expr[..index].trim(), expr[index + 1..].trim());
+
+    match is_ident(ident) && is_literal(offset) {
+        true if op == '-' => Some((ident.to_string(), offset.to_string())),
+        true => Some((ident.to_string(), "0".to_string())),
+        false => None,
+    }
+}
+
+
+fn get_referenced(fields: &[(String, String, FieldAttributes)]) -> Result<BTreeMap<String, Referenced>> {
+    let mut referenced = BTreeMap::new();
+
+    for (_, rtype, attributes) in fields {
+        for (key, value) in [("length", &attributes.length), ("count", &attributes.count)] {
+            if let Some(AttrValue::Var(expr)) = value {
+                // The integer fields of `length`/`count` are generated as the valid lengths,
+                // so only `ident`/`ident + const`/`ident - const` are supported.
+                let mut int_fields = vec![];
+
+                replace_expr_idents(expr, |ident| {
+                    if fields.iter().any(|v| v.0 == ident && is_int_type(&v.1)) {
+                        int_fields.push(ident.to_string());
+                    }
+                    None
+                });
+
+                if int_fields.is_empty() {
+                    continue;
+                }
+
+                match parse_length_source(expr) {
+                    Some((ident, offset)) if int_fields == [ident.clone()] => {
+                        referenced.insert(ident, Referenced::Length(offset));
+                    },
+                    _ => return Err(error_at(attributes, key, true, format!("`{key}` of `ByteGenerate` only supports a field with a constant offset, eg: `length`, `length + 2`, `length - 2`, but got `{}`", expr.trim()))),
+                }
+            }
+        }
+
+        if let Some(AttrValue::Var(ident)) = &attributes.byteorder {
+            referenced.insert(ident.trim().to_string(), Referenced::ByteOrder);
+        }
+
+        if let Some(AttrValue::Var(ident)) = &attributes.branch {
+            referenced.insert(ident.trim().to_string(), Referenced::Branch(rtype.clone()));
+        }
+    }
+
+    Ok(referenced)
+}
+
+
+/// Returns a value matched by the `branch_value`/`branch_range` pattern, eg: `1 | 2` => `1`, `3..=5` => `3`.
+fn branch_pattern_value(pattern: &str) -> String {
+    let value = pattern.split('|').next().unwrap_or_default().trim();
+
+    match value.split_once("..") {
+        Some((start, _)) if !start.trim().is_empty() => format!("({}) as usize", start.trim()),
+        Some(_) => "0".to_string(),
+        None => format!("({value}) as usize"),
+    }
+}
+
+
+/// Generates the fields in order as the local variables, returns the arguments of the constructor.
+fn generate_fields_body(fields: &Option<Fields>, has_value: bool) -> Result<(String, String)> {
+    let fields_list = get_fields(fields)?;
+    let referenced = get_referenced(&fields_list)?;
+    let mut code = String::new();
+
+    for (name, rtype, attributes) in &fields_list {
+        let generate = format!("<{rtype} as {CRATE_NAME}>::generate_inner(rng, fattr_new)");
+
+        let value = if attributes.magic.is_some() || attributes.skip || attributes.skip_decode || (attributes.discriminant && !has_value) {
+            format!("<{rtype} as Default>::default()")
+        }
+        else if attributes.discriminant {
+            // The branch value is one of the valid values of the variant.
+            format!("value as {rtype}")
+        }
+        else if let Some(check_value) = &attributes.check_value {
+            check_value.to_string()
+        }
+        else {
+            let value = match referenced.get(name) {
+                Some(Referenced::Length(offset)) if is_int_type(rtype) => match offset.as_str() {
+                    "0" => format!("{generate}.rem_euclid(jbytes::testing::MAX_GENERATE_LENGTH as {rtype} + 1)"),
+                    _ => format!("({offset} as {rtype}) + {generate}.rem_euclid(jbytes::testing::MAX_GENERATE_LENGTH as {rtype} + 1)"),
+                },
+                Some(Referenced::ByteOrder) if is_int_type(rtype) => format!("{generate}.rem_euclid(2)"),
+                Some(Referenced::Branch(branch_type)) if is_int_type(rtype) => format!("match <{branch_type} as {CRATE_NAME}>::generate_branch(rng) {{
+                    Some(value) => value as {rtype},
+                    None => {generate},
+                }}"),
+                _ => generate,
+            };
+
+            code.push_str(&attributes.to_code(CRATE_NAME, Some(rtype), false, false, &[]));
+
+            match &attributes.if_expr {
+                Some(if_expr) => format!("if {if_expr} {{ {value} }} else {{ {} }}", attributes.default_value.as_deref().unwrap_or("Default::default()")),
+                None => value,
+            }
+        };
+
+        code.push_str(&format!("let {name}: {rtype} = {value};"));
+    }
+
+    let args = fields_list.iter().map(|v| v.0.as_str()).collect::<Vec<&str>>().join(", ");
+
+    let args = match fields {
+        Some(Fields::Struct(_)) => format!("{{{args}}}"),
+        Some(Fields::Tuple(_)) => format!("({args})"),
+        None => "".to_string(),
+    };
+
+    Ok((code, args))
+}
+
+
+/// Names the unused arguments with `_`, eg: the structs don't use `fattr`.
+fn generate_fn(impl_for: &mut ImplFor<'_, Generator>, body: String) -> Result<()> {
+    let rng = if body.contains("(rng") || body.contains("rng.gen_range") { "rng" } else { "_rng" };
+    let fattr = if body.contains("fattr)") || body.contains("fattr.") { "fattr" } else { "_fattr" };
+
+    impl_for
+        .generate_fn("generate_inner")
+        .with_arg(rng, "&mut jbytes::testing::Rng")
+        .with_arg(fattr, "Option<&jbytes::FieldAttrModifiers>")
+        .with_return_type("Self")
+        .body(|fn_body| {
+            fn_body.push_parsed(body)?;
+            Ok(())
+        })?;
+
+    Ok(())
+}
+
+
+impl DeriveGenerate {
+    pub fn generate_generate(&self, generator: &mut Generator) -> Result<()> {
+        validate_fields(&self.fields, &self.attributes)?;
+
+        let mut impl_for = generator.impl_for(CRATE_NAME);
+        generate_generic_bounds(&mut impl_for, &self.attributes, CRATE_NAME)?;
+
+        let (code, args) = generate_fields_body(&self.fields, false)?;
+        generate_fn(&mut impl_for, format!("{code} Self {args}"))?;
+
+        Ok(())
+    }
+}
+
+
+impl DeriveGenerateEnum {
+    pub fn generate_generate(&self, generator: &mut Generator) -> Result<()> {
+        validate_variants(&self.variants, &self.attributes)?;
+
+        if self.variants.is_empty() {
+            return Err(Error::custom("`ByteGenerate` requires at least one variant"));
+        }
+
+        let mut impl_for = generator.impl_for(CRATE_NAME);
+        generate_generic_bounds(&mut impl_for, &self.attributes, CRATE_NAME)?;
+
+        let is_self_branch = self.attributes.branch_take_bytes.is_some() || self.attributes.branch_starts_with || self.attributes.branch_starts_with_untake;
+
+        // The typed branch value of the parent field can't be generated from the chosen variant.
+        if let (Some(branch_type), false) = (&self.attributes.branch_type, is_self_branch) {
+            if !is_int_type(branch_type) {
+                return Err(Error::custom(format!("`ByteGenerate` only supports the integer `branch_type`, found `{branch_type}`")));
+            }
+        }
+
+        // The integer branch values are matched as the decoder, the other branches are chosen at random.
+        let is_int_branch = !is_self_branch;
+
+        let mut arms = String::new();
+        let mut values = vec![];
+        let mut branch_default = false;
+
+        for (variant_index, variant) in iter_variants(&self.variants) {
+            let attributes = variant.attributes.get_attribute::<FieldAttributes>()?.unwrap_or_default();
+            let (code, args) = generate_fields_body(&variant.fields, is_int_branch)?;
+            let body = format!("{{ {code} Self::{}{args} }}", variant.name);
+
+            if !is_int_branch {
+                // The default variant is chosen only if there isn't any other variant.
+                if !attributes.branch_default || self.variants.len() == 1 {
+                    arms.push_str(&format!("{} => {body},", values.len()));
+                    values.push(values.len().to_string());
+                }
+                continue;
+            }
+
+            if attributes.branch_default {
+                arms.push_str(&format!("_ => {body},"));
+                branch_default = true;
+            }
+            else if let Some(branch_bits) = &attributes.branch_bits {
+                let branch_bits_value = attributes.branch_bits_value.as_ref().unwrap_or(branch_bits);
+                arms.push_str(&format!("value if (value & {branch_bits}) == {branch_bits_value} => {body},"));
+                values.push(format!("({branch_bits_value}) as usize"));
+            }
+            else {
+                match attributes.branch_value.as_ref().or(attributes.branch_range.as_ref()) {
+                    Some(pattern) => {
+                        arms.push_str(&format!("{pattern} => {body},"));
+                        values.push(branch_pattern_value(pattern));
+                    },
+                    None => {
+                        arms.push_str(&format!("{variant_index} => {body},"));
+                        values.push(variant_index.to_string());
+                    },
+                }
+            }
+        }
+
+        let values = values.join(", ");
+
+        let body = if is_int_branch {
+            // The unmatched branch value of the parent is replaced by a valid one.
+            if !branch_default {
+                arms.push_str(&format!("_ => <Self as {CRATE_NAME}>::generate_inner(rng, None),"));
+            }
+
+            format!("let value = match fattr.and_then(|fr| fr.branch) {{
+                Some(value) => value,
+                None => <Self as {CRATE_NAME}>::generate_branch(rng).unwrap_or_default(),
+            }};
+            match value {{ {arms} }}")
+        }
+        else {
+            format!("let values: &[usize] = &[{values}];
+            match values[rng.gen_range(0..values.len() as u64) as usize] {{ {arms} _ => unreachable!() }}")
+        };
+
+        generate_fn(&mut impl_for, body)?;
+
+        if is_int_branch {
+            impl_for
+                .generate_fn("generate_branch")
+                .with_arg("rng", "&mut jbytes::testing::Rng")
+                .with_return_type("Option<usize>")
+                .body(|fn_body| {
+                    fn_body.push_parsed(format!("let values: &[usize] = &[{values}];
+                        values.get(rng.gen_range(0..values.len() as u64) as usize).copied()"))?;
+                    Ok(())
+                })?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,12 +1,14 @@
 #[allow(unused_imports)]
 use virtue::{generate::Generator, parse::IdentOrIndex};
+use virtue::generate::ImplFor;
 use virtue::parse::Fields;
 use virtue::prelude::*;
 #[allow(unused_imports)]
 use super::attribute::{ContainerAttributes, FieldAttributes};
-use super::decode::generate_decode_body;
-use super::encode::generate_encode_body;
-use super::parse::AttrValue;
+use super::decode::{generate_decode_body, generate_decode_align, generate_decode_checksum};
+use super::encode::{generate_encode_body, generate_encode_align, generate_encode_length_of, generate_encode_length_of_patch, generate_encode_checksum, generate_encode_if_expr, generate_encode_seek, is_seek_deferred};
+use super::parse::{AttrValue, trait_kind};
+use super::validate::validate_fields;
 
 
 pub(crate) struct DeriveStruct {
@@ -25,6 +27,15 @@ pub fn get_field_type(field: &UnnamedField) -> String {
 }
 
 
+/// Returns the field types in the order of `Fields::names`.
+pub fn get_field_types(fields: &Fields) -> Vec<String> {
+    match fields {
+        Fields::Struct(value) => value.iter().map(|(_, field)| get_field_type(field)).collect(),
+        Fields::Tuple(value) => value.iter().map(get_field_type).collect(),
+    }
+}
+
+
 pub fn generate_decode_return(fn_body: &mut StreamBuilder, fields: &Option<Fields>, variant: Option<&EnumVariant>) -> Result<()> {
     if variant.is_some() {
         fn_body.push_parsed("return")?;
@@ -77,26 +88,214 @@ pub fn generate_decode_return(fn_body: &mut StreamBuilder, fields: &Option<Field
 }
 
 
-pub fn generate_decode_struct_body(fn_body: &mut StreamBuilder, crate_name: &str, fields: &Option<Fields>, _cattr: &ContainerAttributes, _is_enum: bool) -> Result<()> {
+/// Adds the trait bound to every type parameter, eg: `T: jbytes::ByteDecode`,
+/// the `bound` of the container replaces the automatic bounds, eg: `#[jbytes(bound="T: jbytes::ByteDecode + Default")]`.
+pub fn generate_generic_bounds(impl_for: &mut ImplFor<'_, Generator>, cattr: &ContainerAttributes, trait_name: &str) -> Result<()> {
+    // The decode/encode traits prefer their own bounds, eg: `bound_decode`/`bound_encode`.
+    let bound = match trait_name {
+        v if v.contains("ByteDecode") => cattr.bound_decode.as_ref().or(cattr.bound.as_ref()),
+        v if v.contains("ByteEncode") => cattr.bound_encode.as_ref().or(cattr.bound.as_ref()),
+        _ => cattr.bound.as_ref(),
+    };
+
+    impl_for.modify_generic_constraints(|generics, constraints| {
+        match bound {
+            Some(bound) if bound.trim().is_empty() => {},
+            Some(bound) => constraints.push_parsed_constraint(bound)?,
+            None => {
+                for generic in generics.iter_generics() {
+                    constraints.push_constraint(generic, trait_name)?;
+                }
+            },
+        }
+
+        Ok(())
+    })?;
+
+    Ok(())
+}
+
+
+/// Implements `ByteArgs` of the derived trait, the arguments are passed as a struct of the declared names and types.
+pub fn generate_args(generator: &mut Generator, cattr: &ContainerAttributes, crate_name: &str) -> Result<()> {
+    let kind = match trait_kind(crate_name) {
+        Some(kind) if !cattr.args.is_empty() => kind,
+        _ => return Ok(()),
+    };
+    let name = format!("{}{}Args", generator.target_name(), crate_name.trim_start_matches("jbytes::"));
+
+    {
+        let mut args_struct = generator.generate_struct(&name);
+        args_struct.make_pub().with_parsed_attribute("doc(hidden)")?.with_parsed_attribute("allow(dead_code)")?;
+
+        for (arg_name, rtype) in &cattr.args {
+            args_struct.add_field(arg_name, rtype).make_pub();
+        }
+    }
+
+    generator.impl_for(format!("jbytes::args::ByteArgs<{kind}>")).impl_type("Args", name)?;
+
+    Ok(())
+}
+
+
+/// Implements `ByteCtx` of the derived trait, so the user context is checked at compile time.
+pub fn generate_ctx(generator: &mut Generator, cattr: &ContainerAttributes, crate_name: &str) -> Result<()> {
+    if let (Some(ctx), Some(kind)) = (&cattr.ctx, trait_kind(crate_name)) {
+        generator.impl_for(format!("jbytes::ctx::ByteCtx<{kind}>")).impl_type("Ctx", ctx)?;
+    }
+
+    Ok(())
+}
+
+
+/// Checks the context of a field type against the context of the container, see `assert_ctx!`.
+pub fn generate_ctx_check(fn_body: &mut StreamBuilder, attributes: &FieldAttributes, cattr: &ContainerAttributes, crate_name: &str, value: &str) -> Result<()> {
+    // The custom functions and the skipped fields don't pass the context to the field type.
+    if attributes.with.is_some() || attributes.with_decode.is_some() || attributes.with_encode.is_some() || attributes.skip {
+        return Ok(());
+    }
+
+    if let Some(kind) = trait_kind(crate_name) {
+        let ctx = cattr.ctx.as_deref().unwrap_or("jbytes::ctx::NoCtx");
+        fn_body.push_parsed(format!("jbytes::assert_ctx!({value}, {kind}, {ctx});"))?;
+    }
+
+    Ok(())
+}
+
+
+/// Returns true if any field is aligned by `align` or `align_fields`.
+pub fn has_align_fields(fields: &Option<Fields>, cattr: &ContainerAttributes) -> Result<bool> {
+    if cattr.align_fields.is_some() {
+        return Ok(true);
+    }
+
+    if let Some(fields) = fields.as_ref() {
+        for field in fields.names() {
+            if field.attributes().get_attribute::<FieldAttributes>()?.unwrap_or_default().align.is_some() {
+                return Ok(true);
+            }
+        }
+    }
+
+    Ok(false)
+}
+
+
+/// Returns true if any field is verified by `checksum`.
+pub fn has_checksum_fields(fields: &Option<Fields>) -> Result<bool> {
+    if let Some(fields) = fields.as_ref() {
+        for field in fields.names() {
+            if field.attributes().get_attribute::<FieldAttributes>()?.unwrap_or_default().checksum.is_some() {
+                return Ok(true);
+            }
+        }
+    }
+
+    Ok(false)
+}
+
+
+/// Returns true if any field is patched by `length_of`.
+pub fn has_length_of_fields(fields: &Option<Fields>) -> Result<bool> {
+    if let Some(fields) = fields.as_ref() {
+        for field in fields.names() {
+            if field.attributes().get_attribute::<FieldAttributes>()?.unwrap_or_default().length_of.is_some() {
+                return Ok(true);
+            }
+        }
+    }
+
+    Ok(false)
+}
+
+
+/// Returns true if any field is positioned by `seek`/`seek_from_end`.
+pub fn has_seek_fields(fields: &Option<Fields>) -> Result<bool> {
+    if let Some(fields) = fields.as_ref() {
+        for field in fields.names() {
+            let attributes = field.attributes().get_attribute::<FieldAttributes>()?.unwrap_or_default();
+
+            if attributes.seek.is_some() || attributes.seek_from_end.is_some() {
+                return Ok(true);
+            }
+        }
+    }
+
+    Ok(false)
+}
+
+
+/// Returns true if any field of `seek`/`seek_from_end` restores the position.
+pub fn has_seek_deferred_fields(fields: &Option<Fields>) -> Result<bool> {
+    if let Some(fields) = fields.as_ref() {
+        for field in fields.names() {
+            if is_seek_deferred(&field.attributes().get_attribute::<FieldAttributes>()?.unwrap_or_default()) {
+                return Ok(true);
+            }
+        }
+    }
+
+    Ok(false)
+}
+
+
+/// Records the start/end positions of every field for `checksum` fields.
+pub fn generate_checksum_positions(fn_body: &mut StreamBuilder, fields: &Option<Fields>) -> Result<()> {
+    if let (true, Some(fields)) = (has_checksum_fields(fields)?, fields.as_ref()) {
+        fn_body.push_parsed(format!("let mut field_positions = [(0usize, 0usize); {}];", fields.names().len()))?;
+    }
+
+    Ok(())
+}
+
+
+pub fn generate_decode_struct_body(fn_body: &mut StreamBuilder, crate_name: &str, fields: &Option<Fields>, cattr: &ContainerAttributes, _is_enum: bool) -> Result<()> {
+    let is_checksum = has_checksum_fields(fields)?;
+    let position_code = |index: usize, end: usize| format!("field_positions[{index}].{end} = input.get_position() - start_position;");
+
+    generate_checksum_positions(fn_body, fields)?;
+
+    let is_seek_deferred_fields = has_seek_deferred_fields(fields)?;
+
+    if is_seek_deferred_fields {
+        fn_body.push_parsed("let mut seek_end_position = 0;")?;
+    }
+
     if let Some(fields) = fields.as_ref() {
         match fields {
             Fields::Struct(value) => {
-                for (ident, field) in value {
+                for (index, (ident, field)) in value.iter().enumerate() {
                     let attributes = field.attributes.get_attribute::<FieldAttributes>()?.unwrap_or_default();
-                    fn_body.push_parsed(attributes.to_code(false, false))?;
+                    fn_body.push_parsed(attributes.to_code(crate_name, Some(&get_field_type(field)), false, false, &cattr.arg_names()))?;
+                    if is_checksum { fn_body.push_parsed(position_code(index, 0))?; }
                     generate_decode_body(fn_body, crate_name, &attributes, ident.to_string(), &get_field_type(field), false)?;
+                    generate_ctx_check(fn_body, &attributes, cattr, crate_name, &format!("&{ident}"))?;
+                    if is_checksum { fn_body.push_parsed(position_code(index, 1))?; }
+                    generate_decode_align(fn_body, &attributes, cattr)?;
                 }
             },
             Fields::Tuple(value) => {
                 for (index, field) in value.iter().enumerate() {
                     let attributes = field.attributes.get_attribute::<FieldAttributes>()?.unwrap_or_default();
                     if attributes.is_use {
-                        fn_body.push_parsed(attributes.to_code(false, false))?;
+                        fn_body.push_parsed(attributes.to_code(crate_name, Some(&get_field_type(field)), false, false, &cattr.arg_names()))?;
                     }
+                    if is_checksum { fn_body.push_parsed(position_code(index, 0))?; }
                     generate_decode_body(fn_body, crate_name, &attributes, index.to_string(), &get_field_type(field), true)?;
+                    generate_ctx_check(fn_body, &attributes, cattr, crate_name, &format!("&v{index}"))?;
+                    if is_checksum { fn_body.push_parsed(position_code(index, 1))?; }
+                    generate_decode_align(fn_body, &attributes, cattr)?;
                 }
             },
-        }   
+        }
+
+        generate_decode_checksum(fn_body, fields)?;
+    }
+
+    if is_seek_deferred_fields {
+        fn_body.push_parsed("if seek_end_position > input.get_position() { input.set_position(seek_end_position); }")?;
     }
 
     Ok(())
@@ -106,9 +305,13 @@ pub fn generate_decode_struct_body(fn_body: &mut StreamBuilder, crate_name: &str
 impl DeriveStruct {
     pub fn generate_decode(&self, generator: &mut Generator) -> Result<()> {
         let crate_name = "jbytes::ByteDecode";
+        validate_fields(&self.fields, &self.attributes)?;
+        generate_args(generator, &self.attributes, crate_name)?;
+        generate_ctx(generator, &self.attributes, crate_name)?;
+        let mut impl_for = generator.impl_for(crate_name);
+        generate_generic_bounds(&mut impl_for, &self.attributes, crate_name)?;
 
-        generator
-            .impl_for(crate_name)
+        impl_for
             .generate_fn("decode_inner")
             .with_generic_deps("I", ["jbytes::BufRead"])
             .with_arg("input", "&I")
@@ -116,7 +319,7 @@ impl DeriveStruct {
             .with_arg("fattr", "Option<&jbytes::FieldAttrModifiers>")
             .with_return_type("jbytes::JResult<Self>")
             .body(|fn_body| {
-                // fn_body.push_parsed(self.attributes.to_code(false))?;
+                // fn_body.push_parsed(self.attributes.to_code(false, crate_name))?;
                 self.generate_byte_decode_body(crate_name, fn_body)?;
 
                 Ok(())
@@ -127,6 +330,9 @@ impl DeriveStruct {
 
     pub fn generate_borrow_decode(&self, generator: &mut Generator) -> Result<()> {
         let crate_name = "jbytes::BorrowByteDecode";
+        validate_fields(&self.fields, &self.attributes)?;
+        generate_args(generator, &self.attributes, crate_name)?;
+        generate_ctx(generator, &self.attributes, crate_name)?;
 
         let mut impl_for = if let Some(lifetimes) = &self.lifetimes {
             generator
@@ -144,6 +350,8 @@ impl DeriveStruct {
             "'de"
         };
 
+        generate_generic_bounds(&mut impl_for, &self.attributes, &format!("{crate_name}<{lifetimes}>"))?;
+
         impl_for
             .generate_fn("decode_inner")
             .with_generic_deps("I", ["jbytes::BufRead"])
@@ -152,7 +360,7 @@ impl DeriveStruct {
             .with_arg("fattr", "Option<&jbytes::FieldAttrModifiers>")
             .with_return_type("jbytes::JResult<Self>")
             .body(|fn_body| {
-                // fn_body.push_parsed(self.attributes.to_code(false))?;
+                // fn_body.push_parsed(self.attributes.to_code(false, crate_name))?;
                 self.generate_byte_decode_body(crate_name, fn_body)?;
 
                 Ok(())
@@ -169,7 +377,7 @@ impl DeriveStruct {
             fn_body.push_parsed(format!("{func}::decode(input, cattr, fattr)"))?;
         }
         else {
-            fn_body.push_parsed(self.attributes.to_code(false))?;
+            fn_body.push_parsed(self.attributes.to_code(false, crate_name))?;
 
             if let Some(value) = &self.attributes.get_variable_name {
                 if let AttrValue::List(variable_names) = value {
@@ -187,6 +395,14 @@ impl DeriveStruct {
                 }
             }
 
+            if has_align_fields(&self.fields, &self.attributes)? || has_checksum_fields(&self.fields)? {
+                fn_body.push_parsed("let start_position = input.get_position();")?;
+            }
+
+            if let Some(magic) = &self.attributes.magic {
+                fn_body.push_parsed(format!("input.take_magic({})?;", magic.to_string()))?;
+            }
+
             generate_decode_struct_body(fn_body, crate_name, &self.fields, &self.attributes, false)?;
             generate_decode_return(fn_body, &self.fields, None)?;
         }
@@ -205,8 +421,14 @@ impl DeriveStruct {
     }
 
     fn generate_byte_encode(&self, crate_name: &str, generator: &mut Generator) -> Result<()> {
-        generator
-            .impl_for(crate_name)
+        validate_fields(&self.fields, &self.attributes)?;
+        generate_args(generator, &self.attributes, crate_name)?;
+        generate_ctx(generator, &self.attributes, crate_name)?;
+
+        let mut impl_for = generator.impl_for(crate_name);
+        generate_generic_bounds(&mut impl_for, &self.attributes, crate_name)?;
+
+        impl_for
             .generate_fn("encode_inner")
             .with_generic_deps("B", ["jbytes::BufWrite"])
             .with_self_arg(FnSelfArg::RefSelf)
@@ -224,7 +446,19 @@ impl DeriveStruct {
                     fn_body.push_parsed(format!("r_nbytes += {func}::encode(buffer, cattr, fattr, self)?;"))?;
                 }
                 else {
-                    fn_body.push_parsed(self.attributes.to_code(true))?;
+                    fn_body.push_parsed(self.attributes.to_code(true, crate_name))?;
+
+                    // The positions are used to patch the `checksum`/`length_of` fields and the pointer fields of `seek`.
+                    let is_position = has_checksum_fields(&self.fields)? || has_length_of_fields(&self.fields)? || has_seek_fields(&self.fields)?;
+
+                    if let (true, Some(fields)) = (is_position, self.fields.as_ref()) {
+                        fn_body.push_parsed("let start_position = buffer.get_position();")?;
+                        fn_body.push_parsed(format!("let mut field_positions = [(0usize, 0usize); {}];", fields.names().len()))?;
+                    }
+
+                    if let Some(magic) = &self.attributes.magic {
+                        fn_body.push_parsed(format!("r_nbytes += buffer.push_bytes({})?;", magic.to_string()))?;
+                    }
 
                     if let Some(value) = &self.attributes.get_variable_name {
                         if let AttrValue::List(variable_names) = value {
@@ -244,12 +478,43 @@ impl DeriveStruct {
                     }
 
                     if let Some(fields) = self.fields.as_ref() {
-                        for field in fields.names() {
+                        let names = fields.names();
+                        let rtypes = get_field_types(fields);
+                        let mut deferred_indexes = vec![];
+
+                        for (index, field) in names.iter().enumerate() {
+                            if is_seek_deferred(&field.attributes().get_attribute::<FieldAttributes>()?.unwrap_or_default()) {
+                                deferred_indexes.push(index);
+                            }
+                        }
+
+                        // The fields of `seek` with `restore` are laid out after the inline fields.
+                        let indexes = (0..names.len()).filter(|v| !deferred_indexes.contains(v)).chain(deferred_indexes.iter().copied());
+
+                        for index in indexes {
+                            let field = &names[index];
                             let mut attributes = field.attributes().get_attribute::<FieldAttributes>()?.unwrap_or_default();
                             attributes.get_variable_name = self.attributes.get_variable_name.clone();
-                            fn_body.push_parsed(attributes.to_code(true, false))?;
-                            generate_encode_body(fn_body, &attributes, crate_name, &field.to_string(), true)?;
+                            fn_body.push_parsed(attributes.to_code(crate_name, Some(&rtypes[index]), true, false, &self.attributes.arg_names()))?;
+
+                            if is_position { fn_body.push_parsed(format!("field_positions[{index}].0 = r_nbytes;"))?; }
+                            generate_ctx_check(fn_body, &attributes, &self.attributes, crate_name, &format!("&self.{field}"))?;
+
+                            generate_encode_if_expr(fn_body, &attributes, fields, true, |fn_body| {
+                                match generate_encode_length_of(fn_body, &attributes, &field.to_string(), fields, true)? {
+                                    Some(name) => generate_encode_body(fn_body, &attributes, crate_name, &name, false)?,
+                                    None => generate_encode_body(fn_body, &attributes, crate_name, &field.to_string(), true)?,
+                                }
+
+                                generate_encode_seek(fn_body, &attributes, crate_name, fields, index, &self.attributes.arg_names())
+                            })?;
+
+                            if is_position { fn_body.push_parsed(format!("field_positions[{index}].1 = r_nbytes;"))?; }
+                            generate_encode_align(fn_body, &attributes, &self.attributes, true)?;
                         }
+
+                        generate_encode_length_of_patch(fn_body, crate_name, fields, true, false, &self.attributes.arg_names())?;
+                        generate_encode_checksum(fn_body, crate_name, fields, true, false, &self.attributes.arg_names())?;
                     }
                 }
 
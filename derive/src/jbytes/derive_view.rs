@@ -0,0 +1,418 @@
+use virtue::generate::Parent;
+use virtue::parse::Fields;
+use virtue::prelude::*;
+use super::attribute::{ContainerAttributes, FieldAttributes};
+use super::derive_struct::get_field_type;
+use super::parse::{AttrValue, is_int_type};
+use super::validate::{validate_fields, error_at};
+
+
+/// The field attributes which keep a fixed layout, the others are rejected by `ByteView`.
+const VIEW_ATTRIBUTES: &[&str] = &["byteorder", "length", "offset", "untake", "bits", "bits_start", "skip", "skip_decode", "skip_encode", "checksum", "range"];
+
+
+pub(crate) struct DeriveView {
+    pub fields: Option<Fields>,
+    pub attributes: ContainerAttributes,
+}
+
+
+/// The accessors of a field, which read/write the value at a compile-time-computed offset.
+struct ViewField {
+    name: String,
+    // The value type of the field, eg: `u16`/`[u8; 4]`/`Header`.
+    rtype: String,
+    // The read/write code of the value, `{offset}` is replaced by the offset of the field.
+    read: String,
+    write: String,
+    // The number of bytes of the field, eg: `2`/`HeaderView::SIZE`.
+    size: String,
+    byteorder: &'static str,
+    // The nested struct which derives `ByteView`/`ByteViewMut`, eg: `Header` -> `HeaderView<'a>`.
+    is_nested: bool,
+    // The offset of the field, eg: `3 + HeaderView::SIZE`.
+    offset: String,
+    checksum: Option<String>,
+    range: Option<String>,
+}
+
+
+/// Returns the byte order code of a literal byte order attribute, the byte order of expressions is unknown to the view.
+fn byteorder_to_code(value: &AttrValue) -> Option<&'static str> {
+    match value {
+        AttrValue::String(v) if v == "LE" || v == "1" || v == "<" => Some("jbytes::ByteOrder::Le"),
+        AttrValue::String(_) => Some("jbytes::ByteOrder::Be"),
+        _ => None,
+    }
+}
+
+
+/// Returns the literal value of an attribute, eg: `length=2`, or `None` if the value is an expression.
+fn usize_value(value: &Option<AttrValue>) -> Option<Option<usize>> {
+    match value {
+        Some(AttrValue::Usize(v)) => Some(Some(*v)),
+        Some(_) => None,
+        None => Some(None),
+    }
+}
+
+
+/// Returns the size of the integer types.
+fn int_size(rtype: &str) -> String {
+    match rtype {
+        "usize" | "isize" => format!("core::mem::size_of::<{rtype}>()"),
+        _ => (rtype[1..].parse::<usize>().unwrap_or_default() / 8).to_string(),
+    }
+}
+
+
+impl DeriveView {
+    fn view_field(&self, ident: &Ident, field: &UnnamedField, byteorder: &'static str, suffix: &str) -> Result<Option<ViewField>> {
+        let attributes = field.attributes.get_attribute::<FieldAttributes>()?.unwrap_or_default();
+        let name = ident.to_string();
+        let rtype = get_field_type(field).replace(' ', "");
+        let segment = rtype.rsplit("::").next().unwrap_or_default();
+
+        if let Some((key, span, _)) = attributes.spans.iter().find(|v| !VIEW_ATTRIBUTES.contains(&v.0.as_str())) {
+            return Err(Error::custom_at(format!("`{key}` isn't supported by `ByteView`, the fields must have a fixed layout"), *span));
+        }
+
+        if attributes.skip || attributes.skip_decode {
+            return Ok(None);
+        }
+
+        let byteorder = match &attributes.byteorder {
+            Some(value) => match byteorder_to_code(value) {
+                Some(value) => value,
+                None => return Err(error_at(&attributes, "byteorder", true, "`ByteView` only supports the literal `byteorder`, eg: `BE`/`LE`".to_string())),
+            },
+            None => byteorder,
+        };
+        let read_uint = |nbytes: &str| format!("jbytes::view::read_uint(self.data, {{offset}}, {nbytes}, {byteorder})");
+        let write_uint = |nbytes: &str, value: &str| format!("jbytes::view::write_uint(self.data, {{offset}}, {nbytes}, {byteorder}, {value});");
+
+        let (read, write, size) = if is_int_type(&rtype) {
+            let size = match usize_value(&attributes.length) {
+                Some(Some(length)) => length.to_string(),
+                Some(None) => int_size(&rtype),
+                None => return Err(error_at(&attributes, "length", true, "`ByteView` only supports the literal `length`, eg: `length=3`".to_string())),
+            };
+
+            match usize_value(&attributes.bits) {
+                // The other bits of the bytes are kept on write, eg: `flags`/`fragment_offset`.
+                Some(Some(bits)) => {
+                    let shift = bits.trailing_zeros();
+
+                    (format!("(({} as {rtype}) & ({bits}u128 as {rtype})) >> {shift}", read_uint(&size)),
+                        format!("let value = ({} & !{bits}u128) | (((value as u128) << {shift}) & {bits}u128); {}", read_uint(&size), write_uint(&size, "value")),
+                        size)
+                },
+                Some(None) => (format!("{} as {rtype}", read_uint(&size)), write_uint(&size, "value as u128"), size),
+                None => return Err(error_at(&attributes, "bits", true, "`ByteView` only supports the literal `bits`, eg: `bits=0x0f`".to_string())),
+            }
+        }
+        else if rtype == "bool" {
+            (format!("{} != 0", read_uint("1")), write_uint("1", "value as u128"), "1".to_string())
+        }
+        else if rtype == "f32" || rtype == "f64" {
+            let (bits_type, size) = if rtype == "f32" { ("u32", "4") } else { ("u64", "8") };
+
+            (format!("{rtype}::from_bits({} as {bits_type})", read_uint(size)), write_uint(size, "value.to_bits() as u128"), size.to_string())
+        }
+        else if segment == "Ipv4Addr" {
+            (format!("{rtype}::from({} as u32)", read_uint("4")), write_uint("4", "u32::from(value) as u128"), "4".to_string())
+        }
+        else if segment == "Ipv6Addr" {
+            (format!("{rtype}::from({})", read_uint("16")), write_uint("16", "u128::from(value)"), "16".to_string())
+        }
+        else if segment == "MacAddress" {
+            (format!("{rtype}::from_bits({} as u64)", read_uint("6")), write_uint("6", "value.to_bits() as u128"), "6".to_string())
+        }
+        else if let Some(length) = rtype.strip_prefix("[u8;").and_then(|v| v.strip_suffix(']')) {
+            (format!("jbytes::view::read_array::<{length}>(self.data, {{offset}})"),
+                format!("self.data[{{offset}}..{{offset}} + {length}].copy_from_slice(value);"),
+                length.to_string())
+        }
+        else if segment.starts_with(|v: char| v.is_ascii_uppercase()) && segment.chars().all(|v| v.is_ascii_alphanumeric() || v == '_') && segment != "String" {
+            (String::new(), String::new(), format!("{rtype}{suffix}::SIZE"))
+        }
+        else {
+            return Err(Error::custom_at(format!("`ByteView` only supports fixed-size fields, found `{rtype}`"), ident.span()));
+        };
+
+        Ok(Some(ViewField {
+            name,
+            is_nested: read.is_empty(),
+            rtype,
+            read,
+            write,
+            size,
+            byteorder,
+            offset: String::new(),
+            checksum: attributes.checksum.clone(),
+            range: attributes.range.clone(),
+        }))
+    }
+
+    /// Returns the accessors of fields and the code of the minimum number of bytes.
+    fn view_fields(&self, suffix: &str) -> Result<(Vec<ViewField>, String)> {
+        validate_fields(&self.fields, &self.attributes)?;
+
+        let fields = match &self.fields {
+            Some(Fields::Struct(value)) => value,
+            Some(Fields::Tuple(_)) => return Err(Error::custom("`ByteView` only supports structs with named fields")),
+            None => return Err(Error::custom("`ByteView` requires at least one field")),
+        };
+
+        if self.attributes.magic.is_some() || self.attributes.align_fields.is_some() || self.attributes.byte_count.is_some()
+            || self.attributes.with.is_some() || self.attributes.with_decode.is_some() {
+            return Err(Error::custom("`ByteView` only supports the container attribute `byteorder`, the fields must have a fixed layout"));
+        }
+
+        let byteorder = match &self.attributes.byteorder {
+            Some(value) => match byteorder_to_code(value) {
+                Some(value) => value,
+                None => return Err(Error::custom("`ByteView` only supports the literal `byteorder`, eg: `BE`/`LE`")),
+            },
+            None => "jbytes::ByteOrder::Be",
+        };
+
+        // The constant parts of offsets are folded, eg: `3 + HeaderView::SIZE`.
+        let mut offset = 0;
+        let mut offset_exprs: Vec<String> = vec![];
+        let mut size_code = String::new();
+        let mut view_fields = vec![];
+
+        for (ident, field) in fields {
+            let attributes = field.attributes.get_attribute::<FieldAttributes>()?.unwrap_or_default();
+
+            match usize_value(&attributes.offset) {
+                Some(value) => offset += value.unwrap_or_default(),
+                None => return Err(error_at(&attributes, "offset", true, "`ByteView` only supports the literal `offset`, eg: `offset=2`".to_string())),
+            }
+
+            let mut view_field = match self.view_field(ident, field, byteorder, suffix)? {
+                Some(view_field) => view_field,
+                None => continue,
+            };
+
+            view_field.offset = match offset {
+                0 if !offset_exprs.is_empty() => offset_exprs.join(" + "),
+                offset => offset_exprs.iter().fold(offset.to_string(), |code, v| format!("{code} + {v}")),
+            };
+            view_field.read = view_field.read.replace("{offset}", &view_field.offset);
+            view_field.write = view_field.write.replace("{offset}", &view_field.offset);
+            size_code.push_str(&format!("let end = {} + {}; if end > size {{ size = end; }}", view_field.offset, view_field.size));
+
+            // The `untake` field shares the bytes with the next field, eg: `bits`.
+            if !attributes.untake {
+                match view_field.size.parse::<usize>() {
+                    Ok(size) => offset += size,
+                    Err(_) => offset_exprs.push(view_field.size.clone()),
+                }
+            }
+
+            view_fields.push(view_field);
+        }
+
+        Ok((view_fields, size_code))
+    }
+
+    /// Returns the start/end offset code of the checksum `range`, eg: `start..field`/`field..end`.
+    fn checksum_range(fields: &[ViewField], field: &ViewField) -> Result<(String, String)> {
+        let range = field.range.clone().unwrap_or_else(|| format!("start..{}", field.name));
+
+        let offset_code = |name: &str| {
+            match name.trim() {
+                "start" => Ok("0".to_string()),
+                "end" => Ok("Self::SIZE".to_string()),
+                name => match fields.iter().find(|v| v.name == name) {
+                    Some(field) => Ok(field.offset.clone()),
+                    None => Err(Error::custom(format!("Unknown field `{name}` of range"))),
+                },
+            }
+        };
+
+        match range.split_once("..") {
+            Some((start, end)) => Ok((offset_code(start)?, offset_code(end)?)),
+            None => Err(Error::custom(format!("Invalid range `{range}`, eg: `start..field`"))),
+        }
+    }
+
+    pub fn generate_view(&self, generator: &mut Generator) -> Result<()> {
+        let (fields, size_code) = self.view_fields("View")?;
+        let name = generator.target_name().to_string();
+        let view_name = format!("{name}View");
+        let mut accessors = String::new();
+
+        for ViewField { name, rtype, read, is_nested, offset, .. } in &fields {
+            if *is_nested {
+                accessors.push_str(&format!("#[inline] pub fn {name}(&self) -> {rtype}View<'a> {{ {rtype}View::new_unchecked(&self.data[{offset}..]) }}"));
+            }
+            else if rtype.starts_with('[') {
+                accessors.push_str(&format!("#[inline] pub fn {name}(&self) -> &'a {rtype} {{ {read} }}"));
+            }
+            else {
+                accessors.push_str(&format!("#[inline] pub fn {name}(&self) -> {rtype} {{ {read} }}"));
+            }
+        }
+
+        let mut builder = StreamBuilder::new();
+
+        builder.push_parsed(format!("
+            #[doc = \"A zero-copy view of `{name}` over the bytes, which is generated by `#[derive(ByteView)]`.\"]
+            #[derive(Debug, Clone, Copy)]
+            pub struct {view_name}<'a> {{
+                data: &'a [u8],
+            }}
+
+            impl<'a> {view_name}<'a> {{
+                #[doc = \"The minimum number of bytes of the view.\"]
+                pub const SIZE: usize = {{ let mut size = 0; {size_code} size }};
+
+                #[doc = \"Constructs a new view, the length of the bytes is validated once.\"]
+                #[inline]
+                pub fn new(data: &'a [u8]) -> jbytes::JResult<Self> {{
+                    if data.len() < Self::SIZE {{
+                        return Err(jbytes::make_error(data.len(), jbytes::ErrorKind::InvalidByteLength));
+                    }}
+
+                    Ok(Self {{ data }})
+                }}
+
+                #[doc(hidden)]
+                #[inline]
+                pub fn new_unchecked(data: &'a [u8]) -> Self {{
+                    Self {{ data }}
+                }}
+
+                #[doc = \"Returns the bytes of the view.\"]
+                #[inline]
+                pub fn as_bytes(&self) -> &'a [u8] {{
+                    self.data
+                }}
+
+                {accessors}
+            }}
+        "))?;
+
+        generator.append(builder);
+
+        Ok(())
+    }
+
+    pub fn generate_view_mut(&self, generator: &mut Generator) -> Result<()> {
+        let (fields, size_code) = self.view_fields("ViewMut")?;
+        let name = generator.target_name().to_string();
+        let view_name = format!("{name}ViewMut");
+        let mut accessors = String::new();
+
+        for ViewField { name, rtype, read, write, is_nested, offset, .. } in &fields {
+            let setter = format!("set_{}", name.trim_start_matches("r#"));
+
+            if *is_nested {
+                accessors.push_str(&format!("#[inline] pub fn {name}(&mut self) -> {rtype}ViewMut<'_> {{ {rtype}ViewMut::new_unchecked(&mut self.data[{offset}..]) }}"));
+            }
+            else if rtype.starts_with('[') {
+                accessors.push_str(&format!("#[inline] pub fn {name}(&self) -> &{rtype} {{ {read} }}"));
+                accessors.push_str(&format!("#[inline] pub fn {setter}(&mut self, value: &{rtype}) {{ {write} }}"));
+            }
+            else {
+                accessors.push_str(&format!("#[inline] pub fn {name}(&self) -> {rtype} {{ {read} }}"));
+                accessors.push_str(&format!("#[inline] pub fn {setter}(&mut self, value: {rtype}) {{ {write} }}"));
+            }
+        }
+
+        // The checksum fields are recalculated over the range, and the internet checksums are also updated
+        // incrementally by the setters of the fields, RFC 1624.
+        let mut update_code = String::new();
+
+        for field in fields.iter().filter(|v| v.checksum.is_some()) {
+            let (start, end) = Self::checksum_range(&fields, field)?;
+            let ViewField { name, checksum, offset, size, byteorder, .. } = field;
+            let checksum = checksum.clone().unwrap_or_default();
+
+            accessors.push_str(&format!("
+                #[doc = \"Recalculates the checksum field `{name}` over the bytes of the view.\"]
+                #[inline]
+                pub fn update_{}(&mut self) {{
+                    let value = {checksum}.checksum_range(self.data, ({start})..({end}), ({offset})..({offset} + {size})).unwrap_or_default();
+                    jbytes::view::write_uint(self.data, {offset}, {size}, {byteorder}, value as u128);
+                }}", name.trim_start_matches("r#")));
+
+            if checksum == "jbytes::Checksum::Internet" {
+                update_code.push_str(&format!("jbytes::view::update_internet_checksum(self.data, ({start})..({end}), {offset}, {byteorder}, offset, &old);"));
+            }
+        }
+
+        if !update_code.is_empty() {
+            for ViewField { name, rtype, size, is_nested, offset, checksum, .. } in &fields {
+                if *is_nested || checksum.is_some() {
+                    continue;
+                }
+
+                let name = name.trim_start_matches("r#");
+                let value_type = if rtype.starts_with('[') { format!("&{rtype}") } else { rtype.clone() };
+
+                accessors.push_str(&format!("
+                    #[doc = \"Sets the field `{name}`, and updates the internet checksum fields incrementally.\"]
+                    #[inline]
+                    pub fn set_{name}_with_checksum(&mut self, value: {value_type}) {{
+                        let offset = {offset};
+                        let old = *jbytes::view::read_array::<{{ {size} }}>(self.data, offset);
+                        self.set_{name}(value);
+                        {update_code}
+                    }}"));
+            }
+        }
+
+        let mut builder = StreamBuilder::new();
+
+        builder.push_parsed(format!("
+            #[doc = \"A zero-copy mutable view of `{name}` over the bytes, which is generated by `#[derive(ByteViewMut)]`.\"]
+            #[derive(Debug)]
+            pub struct {view_name}<'a> {{
+                data: &'a mut [u8],
+            }}
+
+            impl<'a> {view_name}<'a> {{
+                #[doc = \"The minimum number of bytes of the view.\"]
+                pub const SIZE: usize = {{ let mut size = 0; {size_code} size }};
+
+                #[doc = \"Constructs a new mutable view, the length of the bytes is validated once.\"]
+                #[inline]
+                pub fn new(data: &'a mut [u8]) -> jbytes::JResult<Self> {{
+                    if data.len() < Self::SIZE {{
+                        return Err(jbytes::make_error(data.len(), jbytes::ErrorKind::InvalidByteLength));
+                    }}
+
+                    Ok(Self {{ data }})
+                }}
+
+                #[doc(hidden)]
+                #[inline]
+                pub fn new_unchecked(data: &'a mut [u8]) -> Self {{
+                    Self {{ data }}
+                }}
+
+                #[doc = \"Returns the bytes of the view.\"]
+                #[inline]
+                pub fn as_bytes(&self) -> &[u8] {{
+                    self.data
+                }}
+
+                #[doc = \"Returns the mutable bytes of the view.\"]
+                #[inline]
+                pub fn as_bytes_mut(&mut self) -> &mut [u8] {{
+                    self.data
+                }}
+
+                {accessors}
+            }}
+        "))?;
+
+        generator.append(builder);
+
+        Ok(())
+    }
+}
@@ -1,6 +1,10 @@
 use virtue::prelude::*;
+use virtue::parse::{Fields, IdentOrIndex};
 #[allow(unused_imports)]
 use super::attribute::{FieldAttributes, ContainerAttributes};
+use super::parse::replace_expr_idents;
+use super::derive_struct::get_field_type;
+use super::validate::error_at;
 
 
 #[inline]
@@ -18,6 +22,237 @@ pub fn generate_encode_body2(fn_body: &mut StreamBuilder, attributes: &FieldAttr
 }
 
 
+/// Pushes the pad bytes after a field, the alignment is relative to the start of the struct.
+#[inline]
+pub fn generate_encode_align(fn_body: &mut StreamBuilder, attributes: &FieldAttributes, cattr: &ContainerAttributes, is_self: bool) -> Result<()> {
+    if let Some(align) = attributes.align.as_ref().or(cattr.align_fields.as_ref()) {
+        let align = align.to_code2(is_self, false);
+        let full_value = if let Some(full_tmp) = &attributes.full { full_tmp.to_string() } else { "0x00".to_string() };
+
+        fn_body.push_parsed(format!("
+            for _ in 0..(({align}) - r_nbytes % ({align})) % ({align}) {{ r_nbytes += buffer.push_u8({full_value})?; }}
+        "))?;
+    }
+
+    Ok(())
+}
+
+
+/// Encodes the field only if the `if_expr` condition is true, the field names of the condition are evaluated against `self`.
+pub fn generate_encode_if_expr<F>(fn_body: &mut StreamBuilder, attributes: &FieldAttributes, fields: &Fields, is_self: bool, f: F) -> Result<()>
+where
+    F: FnOnce(&mut StreamBuilder) -> Result<()>,
+{
+    let if_expr = match &attributes.if_expr {
+        Some(if_expr) => if_expr,
+        None => return f(fn_body),
+    };
+
+    let names = fields.names();
+    let if_expr = replace_expr_idents(if_expr, |ident| {
+        names.iter().find_map(|name| {
+            let (local_name, self_name) = match name {
+                IdentOrIndex::Index { index, .. } => (format!("v{index}"), format!("self.{index}")),
+                _ => (name.to_string(), format!("self.{name}")),
+            };
+
+            match local_name == ident {
+                true if is_self => Some(self_name),
+                true => Some(format!("(*{local_name})")),
+                false => None,
+            }
+        })
+    });
+
+    fn_body.push_parsed(format!("if {if_expr}"))?;
+    fn_body.group(Delimiter::Brace, f)?;
+
+    Ok(())
+}
+
+
+/// Shadows a `count_of` field with the actual element count of the referenced field,
+/// returns the local variable name to encode, the `length_of` fields are patched by `generate_encode_length_of_patch`.
+pub fn generate_encode_length_of(fn_body: &mut StreamBuilder, attributes: &FieldAttributes, field: &String,
+                                 fields: &Fields, is_self: bool) -> Result<Option<String>> {
+    let name = match &attributes.count_of {
+        Some(name) => name,
+        None => return Ok(None),
+    };
+
+    let ref_name = match fields.names().iter().find(|v| v.to_string() == *name) {
+        Some(IdentOrIndex::Index { index, .. }) if !is_self => format!("v{index}"),
+        Some(v) if is_self => format!("self.{v}"),
+        Some(v) => v.to_string(),
+        None => return Err(Error::custom(format!("Unknown field `{name}` of count_of"))),
+    };
+
+    let name = if field.starts_with(|v: char| v.is_ascii_digit()) { format!("v{field}") } else { field.to_string() };
+    let value = if is_self { format!("self.{field}") } else { format!("*{name}") };
+
+    // The field values are evaluated before any binding, so the local names don't shadow the field names.
+    fn_body.push_parsed(format!("
+        let {name} = &match ({value}, {ref_name}.len()) {{
+            (mut value, count) => {{
+                value = match core::convert::TryInto::try_into(count) {{
+                    Ok(v) => v,
+                    Err(_) => return Err(jbytes::make_error(r_nbytes, jbytes::ErrorKind::InvalidByteLength)),
+                }};
+                value
+            }},
+        }};
+    "))?;
+
+    Ok(Some(name))
+}
+
+
+/// Patches the `length_of` fields after all fields are encoded, the byte length is the encoded size of
+/// the referenced field, so the field is encoded once.
+pub fn generate_encode_length_of_patch(fn_body: &mut StreamBuilder, crate_name: &str, fields: &Fields, is_self: bool, is_deref: bool, arg_names: &[String]) -> Result<()> {
+    let names = fields.names();
+
+    for (index, field) in names.iter().enumerate() {
+        let attributes = field.attributes().get_attribute::<FieldAttributes>()?.unwrap_or_default();
+
+        if let Some(name) = &attributes.length_of {
+            let ref_index = match names.iter().position(|v| v.to_string() == *name) {
+                Some(ref_index) => ref_index,
+                None => return Err(Error::custom(format!("Unknown field `{name}` of length_of"))),
+            };
+            let value = match field {
+                _ if is_self => format!("self.{field}"),
+                IdentOrIndex::Index { index, .. } => format!("*v{index}"),
+                _ => format!("*{field}"),
+            };
+
+            // The field isn't encoded if its `if_expr` is false.
+            fn_body.push_parsed(format!("if field_positions[{index}].1 > field_positions[{index}].0 {{
+                {}
+                let field_position = field_positions[{index}];
+                let length_value = field_positions[{ref_index}].1 - field_positions[{ref_index}].0;
+                let mut length_field = {value};
+                length_field = match core::convert::TryInto::try_into(length_value) {{
+                    Ok(v) => v,
+                    Err(_) => return Err(jbytes::make_error(start_position + field_position.0, jbytes::ErrorKind::InvalidByteLength)),
+                }};
+                buffer.set_position(start_position + field_position.0);
+                let nbytes = {crate_name}::encode_inner(&length_field, buffer, cattr_new, fattr_new)?;
+                buffer.set_position(start_position + r_nbytes);
+
+                if nbytes != field_position.1 - field_position.0 {{
+                    return Err(jbytes::make_error(start_position + field_position.0, jbytes::ErrorKind::InvalidByteLength));
+                }}
+            }}", attributes.to_code(crate_name, None, is_self, is_deref, arg_names)))?;
+        }
+    }
+
+    Ok(())
+}
+
+
+/// Returns true if the field of `seek`/`seek_from_end` is laid out after the inline fields, eg: `#[jbytes(seek="abs(offset)", restore)]`.
+#[inline]
+pub fn is_seek_deferred(attributes: &FieldAttributes) -> bool {
+    attributes.restore && (attributes.seek.is_some() || attributes.seek_from_end.is_some())
+}
+
+
+/// Back-patches the pointer field of `seek="abs(<field>)"` with the absolute position of the encoded field,
+/// the struct field of a nested pointer is cloned and encoded again in place, eg: `abs(hdr.ifd_offset)`.
+pub fn generate_encode_seek(fn_body: &mut StreamBuilder, attributes: &FieldAttributes, crate_name: &str, fields: &Fields, index: usize, arg_names: &[String]) -> Result<()> {
+    let seek = match &attributes.seek {
+        Some(seek) => seek.replace(' ', ""),
+        None => return Ok(()),
+    };
+
+    let (root, path) = match seek.split_once('.') {
+        Some((root, path)) => (root, format!(".{path}")),
+        None => (seek.as_str(), "".to_string()),
+    };
+    let is_path = !seek.is_empty() && seek.chars().all(|v| v.is_alphanumeric() || v == '_' || v == '.');
+
+    let names = fields.names();
+    let root_index = names.iter().position(|name| match name {
+        IdentOrIndex::Index { index, .. } => format!("v{index}") == root,
+        _ => name.to_string() == root,
+    });
+
+    let (root_index, root_field) = match root_index {
+        Some(root_index) if is_path && root_index < index => (root_index, &names[root_index]),
+        _ => return Err(error_at(attributes, "seek", true, "The encode of `seek` requires a preceding field to back-patch, eg: `abs(hdr.ifd_offset)`".to_string())),
+    };
+
+    let root_attributes = root_field.attributes().get_attribute::<FieldAttributes>()?.unwrap_or_default();
+    let pointer_field = match path.is_empty() {
+        true => {
+            let rtype = match fields {
+                Fields::Struct(value) => get_field_type(&value[root_index].1),
+                Fields::Tuple(value) => get_field_type(&value[root_index]),
+            };
+
+            format!("let pointer_field: {rtype} = match core::convert::TryInto::try_into(seek_position) {{
+                Ok(v) => v,
+                Err(_) => return Err(jbytes::make_error(seek_position, jbytes::ErrorKind::InvalidValue(format!(\"{{seek_position:#x}}\")))),
+            }};")
+        },
+        false => format!("let mut pointer_field = self.{root_field}.clone();
+            pointer_field{path} = match core::convert::TryInto::try_into(seek_position) {{
+                Ok(v) => v,
+                Err(_) => return Err(jbytes::make_error(seek_position, jbytes::ErrorKind::InvalidValue(format!(\"{{seek_position:#x}}\")))),
+            }};"),
+    };
+
+    fn_body.push_parsed(format!("{{
+        let seek_position = start_position + field_positions[{index}].0;
+        {pointer_field}
+        {}
+        buffer.set_position(start_position + field_positions[{root_index}].0);
+        {crate_name}::encode_inner(&pointer_field, buffer, cattr_new, fattr_new)?;
+        buffer.set_position(start_position + r_nbytes);
+    }}", root_attributes.to_code(crate_name, None, true, false, arg_names)))?;
+
+    Ok(())
+}
+
+
+/// Patches the `checksum` fields after all fields are encoded, the checksum is calculated over the written bytes
+/// with the checksum field zeroed and encoded again in place.
+pub fn generate_encode_checksum(fn_body: &mut StreamBuilder, crate_name: &str, fields: &Fields, is_self: bool, is_deref: bool, arg_names: &[String]) -> Result<()> {
+    for (index, field) in fields.names().iter().enumerate() {
+        let attributes = field.attributes().get_attribute::<FieldAttributes>()?.unwrap_or_default();
+
+        if let Some(checksum) = &attributes.checksum {
+            let value = match field {
+                _ if is_self => format!("self.{field}"),
+                IdentOrIndex::Index { index, .. } => format!("*v{index}"),
+                _ => format!("*{field}"),
+            };
+            let (start, end) = attributes.checksum_range_to_code(fields, index, "r_nbytes")?;
+
+            fn_body.push_parsed(format!("{{
+                {}
+                let field_position = field_positions[{index}];
+                let checksum_value = match {checksum}.checksum_range(&buffer.get_data()[start_position..], ({start})..({end}), field_position.0..field_position.1) {{
+                    Some(value) => value,
+                    None => return Err(jbytes::make_error(start_position, jbytes::ErrorKind::InvalidByteLength)),
+                }};
+                let mut checksum_field = {value};
+                checksum_field = match core::convert::TryInto::try_into(checksum_value) {{
+                    Ok(v) => v,
+                    Err(_) => return Err(jbytes::make_error(start_position + field_position.0, jbytes::ErrorKind::InvalidValue(format!(\"{{checksum_value:#x}}\")))),
+                }};
+                buffer.set_position(start_position + field_position.0);
+                {crate_name}::encode_inner(&checksum_field, buffer, cattr_new, fattr_new)?;
+                buffer.set_position(start_position + r_nbytes);
+            }}", attributes.to_code(crate_name, None, is_self, is_deref, arg_names)))?;
+        }
+    }
+
+    Ok(())
+}
+
+
 #[inline]
 pub fn generate_encode_body(fn_body: &mut StreamBuilder, attributes: &FieldAttributes, crate_name: &str, field: &String, is_self: bool) -> Result<()> {
     let der_arg = if is_self {"&"} else {""};
@@ -25,7 +260,11 @@ pub fn generate_encode_body(fn_body: &mut StreamBuilder, attributes: &FieldAttri
 
     let with_args = if let Some(value) = &attributes.with_args {format!("{self_arg}{value}")} else { "".to_string() };
 
-    if let Some(func) = &attributes.with_encode {
+    if let Some(magic) = &attributes.magic {
+        fn_body.push_parsed(format!("r_nbytes += buffer.push_bytes({})?;", magic.to_string()))?;
+        return Ok(());
+    }
+    else if let Some(func) = &attributes.with_encode {
         fn_body.push_parsed(format!("r_nbytes += {func}(buffer, cattr_new, fattr_new, {der_arg}{self_arg}{field}, {with_args})?;"))?;
         return Ok(());
     }
@@ -1,6 +1,11 @@
 pub mod attribute;
 pub mod derive_enum;
 pub mod derive_struct;
+pub mod derive_view;
+pub mod derive_fixed_size;
+pub mod derive_generate;
+pub mod derive_describe;
 mod parse;
 mod decode;
 mod encode;
+mod validate;
@@ -1,4 +1,15 @@
 use virtue::prelude::*;
+use super::validate::is_field_ident;
+
+
+/// The integer types which are passed as `BranchValue::Usize`.
+const INT_TYPES: &[&str] = &["u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize"];
+
+
+#[inline]
+pub fn is_int_type(rtype: &str) -> bool {
+    INT_TYPES.contains(&rtype.trim())
+}
 
 
 #[derive(Debug, Clone)]
@@ -24,6 +35,228 @@ pub fn parse_value_string(s: &Literal) -> Result<String> {
 }
 
 
+/// Replaces the identifiers of an expression, eg: `version >= 2` => `self.version >= 2`,
+/// string/char literals, paths, fields, function/method calls and macros are left unchanged.
+pub fn replace_expr_idents<F: FnMut(&str) -> Option<String>>(expr: &str, mut replace: F) -> String {
+    let chars = expr.chars().collect::<Vec<char>>();
+    let mut result = String::new();
+    let mut index = 0;
+
+    while index < chars.len() {
+        let c = chars[index];
+
+        if c == '"' || c == '\'' {
+            let start = index;
+            index += 1;
+
+            while index < chars.len() && chars[index] != c {
+                index += if chars[index] == '\\' { 2 } else { 1 };
+            }
+
+            index = (index + 1).min(chars.len());
+            result.extend(&chars[start..index]);
+        }
+        else if c.is_alphabetic() || c == '_' {
+            let start = index;
+
+            // raw identifier, eg: `r#type`
+            if c == 'r' && chars.get(index + 1) == Some(&'#') {
+                index += 2;
+            }
+
+            while index < chars.len() && (chars[index].is_alphanumeric() || chars[index] == '_') {
+                index += 1;
+            }
+
+            let ident = chars[start..index].iter().collect::<String>();
+            let is_number = result.ends_with(|v: char| v.is_ascii_digit());
+            let prev = result.trim_end().chars().last();
+            let next = chars[index..].iter().collect::<String>();
+            let next = next.trim_start();
+            let is_macro = next.starts_with('!') && !next.starts_with("!=");
+            // The prefix of byte string/char literals, eg: `b"II"`/`b'a'`.
+            let is_prefix = matches!(chars.get(index), Some('"' | '\''));
+            let is_ident = !is_number && !is_macro && !is_prefix && prev != Some('.') && prev != Some(':') && !next.starts_with([':', '(']);
+
+            match if is_ident { replace(&ident) } else { None } {
+                Some(value) => result.push_str(&value),
+                None => result.push_str(&ident),
+            }
+        }
+        else {
+            result.push(c);
+            index += 1;
+        }
+    }
+
+    result
+}
+
+
+/// Replaces the field names of an expression, eg: `length - 4` => `self.length - 4`/`(*length) - 4`,
+/// the user context `ctx` and the argument names of the container aren't fields.
+pub fn replace_field_idents(expr: &str, is_self: bool, is_deref: bool, arg_names: &[String]) -> String {
+    replace_expr_idents(expr, |ident| match is_field_ident(ident, arg_names) {
+        true if is_self => Some(format!("self.{ident}")),
+        true if is_deref => Some(format!("(*{ident})")),
+        _ => None,
+    })
+}
+
+
+/// The items of `args(..)`, eg: `addr_len: usize` => (`addr_len`, [`usize`]).
+pub type ArgsItems = Vec<(Ident, Vec<TokenTree>)>;
+
+
+/// Takes the `args(..)` list out of `#[jbytes(..)]`, which isn't supported by `parse_tagged_attribute`,
+/// returns the remaining attribute and the items split at `separator`, eg: `addr_len: usize`/`addr_len="hdr.alen"`.
+pub fn take_args_attribute(group: &Group, separator: char) -> Result<(Group, Option<ArgsItems>)> {
+    let mut stream = group.stream().into_iter().collect::<Vec<TokenTree>>();
+
+    let body = match (stream.first(), stream.get(1)) {
+        (Some(TokenTree::Ident(ident)), Some(TokenTree::Group(body))) if ident.to_string() == "jbytes" => body.clone(),
+        _ => return Ok((group.clone(), None)),
+    };
+
+    let mut tokens = Vec::new();
+    let mut args = None;
+    let mut iter = body.stream().into_iter().peekable();
+
+    while let Some(token) = iter.next() {
+        match (&token, iter.peek()) {
+            (TokenTree::Ident(key), Some(TokenTree::Group(value))) if key.to_string() == "args" && value.delimiter() == Delimiter::Parenthesis => {
+                args = Some(parse_args_items(value.stream(), separator)?);
+                iter.next();
+
+                if matches!(iter.peek(), Some(TokenTree::Punct(p)) if p.as_char() == ',') {
+                    iter.next();
+                }
+            },
+            _ => tokens.push(token),
+        }
+    }
+
+    let mut body_new = Group::new(body.delimiter(), tokens.into_iter().collect());
+    body_new.set_span(body.span());
+    stream[1] = TokenTree::Group(body_new);
+
+    let mut group_new = Group::new(group.delimiter(), stream.into_iter().collect());
+    group_new.set_span(group.span());
+
+    Ok((group_new, args))
+}
+
+
+/// Splits the comma separated items of `args(..)`, eg: `a: u8, b: HashMap<u8, u8>` => [(a, [u8]), (b, [HashMap<u8, u8>])].
+fn parse_args_items(stream: TokenStream, separator: char) -> Result<ArgsItems> {
+    let mut items: Vec<Vec<TokenTree>> = vec![vec![]];
+    let mut depth = 0;
+
+    for token in stream {
+        match &token {
+            TokenTree::Punct(p) if p.as_char() == ',' && depth == 0 => {
+                items.push(vec![]);
+                continue;
+            },
+            TokenTree::Punct(p) if p.as_char() == '<' => depth += 1,
+            TokenTree::Punct(p) if p.as_char() == '>' && depth > 0 => depth -= 1,
+            _ => {},
+        }
+
+        if let Some(item) = items.last_mut() {
+            item.push(token);
+        }
+    }
+
+    let mut result = Vec::new();
+
+    for mut item in items.into_iter().filter(|v| !v.is_empty()) {
+        let rest = item.split_off(item.len().min(2));
+
+        match (item.first(), item.get(1)) {
+            (Some(TokenTree::Ident(name)), Some(TokenTree::Punct(p))) if p.as_char() == separator && !rest.is_empty() => result.push((name.clone(), rest)),
+            (Some(token), _) => return Err(Error::custom_at(if separator == ':' { "Expected `name: type`" } else { "Expected `name=\"expr\"`" }, token.span())),
+            _ => {},
+        }
+    }
+
+    Ok(result)
+}
+
+
+/// Returns the `BranchType::Branch` of a `branch_type`, eg: `String` => `str`, `u16` => `usize`, `(u8, u8)` => `Typed<(u8, u8)>`.
+pub fn branch_type_to_branch(branch_type: &str) -> String {
+    let rtype = branch_type.replace(' ', "");
+
+    match rtype.trim_start_matches('&') {
+        "str" | "String" => "str".to_string(),
+        "[u8]" | "Vec<u8>" => "[u8]".to_string(),
+        "usize" | "bool" => "usize".to_string(),
+        value if is_int_type(value) => "usize".to_string(),
+        _ => format!("jbytes::branch::Typed<{branch_type}>"),
+    }
+}
+
+
+/// Returns the marker of a derived trait for `ByteArgs`/`ByteCtx`, eg: `jbytes::ByteDecode` => `jbytes::args::ByteDecode`.
+pub fn trait_kind(crate_name: &str) -> Option<String> {
+    match crate_name {
+        "jbytes::ByteDecode" | "jbytes::BorrowByteDecode" | "jbytes::ByteEncode" | "jbytes::BorrowByteEncode" => {
+            Some(crate_name.replace("jbytes::", "jbytes::args::"))
+        },
+        _ => None,
+    }
+}
+
+
+/// Returns the code which gets the typed value from `Option<BranchValue>`, eg: `.and_then(|v| v.as_str())`.
+///
+/// It follows `branch_type_to_branch`, which selects the conversion of the field, see `BranchFrom`.
+pub fn branch_type_to_code(branch_type: &str) -> String {
+    let rtype = branch_type.replace(' ', "");
+
+    match (branch_type_to_branch(branch_type).as_str(), rtype.trim_start_matches('&')) {
+        ("str", _) => ".and_then(|v| v.as_str())".to_string(),
+        ("[u8]", _) => ".and_then(|v| v.as_bytes())".to_string(),
+        ("usize", "usize") => ".and_then(|v| v.as_usize())".to_string(),
+        ("usize", "bool") => ".and_then(|v| v.as_usize()).map(|v| v != 0)".to_string(),
+        ("usize", value) => format!(".and_then(|v| v.as_usize()).map(|v| v as {value})"),
+        // The reference is matched by `match *value`, so the patterns bind the fields by value.
+        _ => format!(".and_then(|v| v.downcast_ref::<{branch_type}>())"),
+    }
+}
+
+
+/// Returns true if the typed branch value is a reference of `branch_type`, see `branch_type_to_code`.
+pub fn is_typed_branch(branch_type: &str) -> bool {
+    !matches!(branch_type_to_branch(branch_type).as_str(), "str" | "[u8]" | "usize")
+}
+
+
+/// Parses the checksum name of `checksum="xxx"`, returns the path of `jbytes::Checksum` variant.
+pub fn parse_checksum(s: &Literal) -> Result<String> {
+    let name = parse_value_string(s)?;
+
+    let variant = match name.to_ascii_lowercase().as_str() {
+        "internet" => "Internet",
+        "crc8" => "Crc8",
+        "crc8_maxim" => "Crc8Maxim",
+        "crc16_arc" => "Crc16Arc",
+        "crc16_modbus" => "Crc16Modbus",
+        "crc16_ccitt" => "Crc16Ccitt",
+        "crc16_xmodem" => "Crc16Xmodem",
+        "crc32" => "Crc32",
+        "crc32c" => "Crc32c",
+        "adler32" => "Adler32",
+        "xor" => "Xor",
+        "sum8" => "Sum8",
+        _ => return Err(Error::custom_at(format!("Unknown checksum `{name}`"), s.span())),
+    };
+
+    Ok(format!("jbytes::Checksum::{variant}"))
+}
+
+
 impl AttrValue {
     #[allow(dead_code)]
     #[inline]
@@ -82,7 +315,7 @@ impl AttrValue {
         }
     }
 
-    pub fn to_code(&self, is_self: bool, is_deref: bool, is_string: bool) -> String {
+    pub fn to_code(&self, is_self: bool, is_deref: bool, is_string: bool, arg_names: &[String]) -> String {
         let self_arg = if is_self { "self." } else { "" };
         let deref_arg = if is_deref { "*" } else { "" };
         let is_string = if is_string { "\"" } else { "" };
@@ -90,11 +323,11 @@ impl AttrValue {
         let code = match self {
             Self::String(v) => format!("{deref_arg}{self_arg}{is_string}{v}{is_string}.into()"),
             Self::Bytes(v) => format!("{deref_arg}{self_arg}{is_string}{v}{is_string}"),
-            Self::Var(v) => format!("({deref_arg}{self_arg}{is_string}{v}{is_string}) as usize"),
+            Self::Var(v) => format!("({}) as usize", replace_field_idents(v, is_self, is_deref, arg_names)),
             Self::Usize(v) => format!("{v} as usize"),
-            Self::Option(v) => format!("if let Some(v) = {deref_arg}{self_arg}{is_string}{v} {{Some(v as usize)}} else {{None}}"),
+            Self::Option(v) => format!("if let Some(v) = {} {{Some(v as usize)}} else {{None}}", replace_field_idents(v, is_self, is_deref, arg_names)),
             Self::List(v) =>  {
-                let value = v.iter().map(|v| format!("{}", v.to_code(is_self, is_deref, true))).collect::<Vec<String>>().join(", ");
+                let value = v.iter().map(|v| format!("{}", v.to_code(is_self, is_deref, true, arg_names))).collect::<Vec<String>>().join(", ");
 
                 format!("vec![{value}]")
             },
@@ -154,13 +387,13 @@ impl ToString for AttrValue {
 pub trait AttrValueTrait {
     type Value;
 
-    fn to_code(&self, is_self: bool, is_deref: bool) -> String;
+    fn to_code(&self, is_self: bool, is_deref: bool, arg_names: &[String]) -> String;
 
     #[allow(dead_code)]
-    fn to_code_string(&self, is_self: bool, is_deref: bool, is_string: bool) -> String;
+    fn to_code_string(&self, is_self: bool, is_deref: bool, is_string: bool, arg_names: &[String]) -> String;
 
     #[allow(dead_code)]
-    fn to_code_option_string(&self, is_self: bool, is_deref: bool, is_string: bool) -> String;
+    fn to_code_option_string(&self, is_self: bool, is_deref: bool, is_string: bool, arg_names: &[String]) -> String;
 
     fn to_byteorder(&self, is_self: bool) -> String;
 }
@@ -170,27 +403,27 @@ impl AttrValueTrait for Option<AttrValue> {
     type Value = AttrValue;
 
     #[inline]
-    fn to_code(&self, is_self: bool, is_deref: bool) -> String {
+    fn to_code(&self, is_self: bool, is_deref: bool, arg_names: &[String]) -> String {
         if let Some(value) = self {
-            return format!("Some({})", value.to_code(is_self, is_deref, false));
+            return format!("Some({})", value.to_code(is_self, is_deref, false, arg_names));
         }
 
         "None".to_string()
     }
 
     #[inline]
-    fn to_code_string(&self, is_self: bool, is_deref: bool, is_string: bool) -> String {
+    fn to_code_string(&self, is_self: bool, is_deref: bool, is_string: bool, arg_names: &[String]) -> String {
         if let Some(value) = self {
-            return format!("Some({})", value.to_code(is_self, is_deref, is_string));
+            return format!("Some({})", value.to_code(is_self, is_deref, is_string, arg_names));
         }
 
         "None".to_string()
     }
 
     #[inline]
-    fn to_code_option_string(&self, is_self: bool, is_deref: bool, is_string: bool) -> String {
+    fn to_code_option_string(&self, is_self: bool, is_deref: bool, is_string: bool, arg_names: &[String]) -> String {
         if let Some(value) = self {
-            return value.to_code(is_self, is_deref, is_string);
+            return value.to_code(is_self, is_deref, is_string, arg_names);
         }
 
         "None".to_string()
@@ -218,10 +451,21 @@ mod tests {
             AttrValue::String("jkc".to_string()),
         ]));
 
-        println!("{:?}", value.to_code(false, false));
-        assert_eq!(value.to_code(false, false), r#"Some(vec!["jkc".into(), "jkc".into()])"#);
+        println!("{:?}", value.to_code(false, false, &[]));
+        assert_eq!(value.to_code(false, false, &[]), r#"Some(vec!["jkc".into(), "jkc".into()])"#);
 
         let value = Some(AttrValue::String("jkc".to_string()));
-        assert_eq!(value.to_code(false, false), r#"Some(jkc.into())"#);
+        assert_eq!(value.to_code(false, false, &[]), r#"Some(jkc.into())"#);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_replace_expr_idents() {
+        let replace = |v: &str| if v == "version" || v == "flags" { Some(format!("self.{v}")) } else { None };
+
+        assert_eq!(replace_expr_idents("version >= 2", replace), "self.version >= 2");
+        assert_eq!(replace_expr_idents("flags & 0x01 != 0 && x.version == 0", replace), "self.flags & 0x01 != 0 && x.version == 0");
+        assert_eq!(replace_expr_idents("version(1) || flags::A || \"version\" == 1u8", replace), "version(1) || flags::A || \"version\" == 1u8");
+        assert_eq!(replace_expr_idents("r#version + version!() + flags!=0", |v| Some(format!("self.{v}"))), "self.r#version + version!() + self.flags!=0");
+        assert_eq!(replace_expr_idents("flags == *b\"II\" || version == b'a'", replace), "self.flags == *b\"II\" || self.version == b'a'");
+    }
+}
@@ -0,0 +1,266 @@
+use virtue::prelude::*;
+use virtue::parse::{Fields, EnumVariant};
+use super::attribute::{ContainerAttributes, FieldAttributes, encoding_to_code};
+use super::derive_struct::get_field_type;
+use super::parse::{AttrValue, replace_expr_idents};
+
+
+/// The attributes which are meaningless together.
+const CONFLICT_ATTRIBUTES: &[(&str, &str)] = &[
+    ("length", "byte_count"),
+    ("byteorder", "byteorder_from"),
+    ("seek", "seek_from_end"),
+    ("count", "try_count"),
+    ("length_of", "count_of"),
+    ("length_of", "value_encode"),
+    ("count_of", "value_encode"),
+    ("magic", "length"),
+    ("magic", "count"),
+    ("magic", "byte_count"),
+    ("with", "with_encode"),
+    ("with", "with_decode"),
+    ("skip", "skip_encode"),
+    ("skip", "skip_decode"),
+    ("branch_default", "branch_value"),
+    ("branch_default", "branch_range"),
+    ("branch_default", "branch_bits_value"),
+    ("branch_value", "branch_range"),
+    ("branch_value", "branch_bits_value"),
+    ("branch_range", "branch_bits_value"),
+];
+
+/// The attributes which only support integer types.
+const INT_ATTRIBUTES: &[&str] = &["bits", "bits_start", "bcd", "ascii_dec", "ascii_hex"];
+
+/// The attributes whose expressions reference the preceding fields.
+const EXPR_ATTRIBUTES: &[&str] = &["length", "count", "try_count", "byte_count", "offset", "branch", "if_expr", "byteorder_from", "args", "seek", "seek_from_end"];
+
+/// The identifiers of expressions which aren't field names.
+const EXPR_KEYWORDS: &[&str] = &["as", "if", "else", "match", "true", "false", "self", "crate", "super", "ctx"];
+
+/// The primitive types, which are also skipped in expressions, eg: `length as usize`.
+const PRIMITIVE_TYPES: &[&str] = &[
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+    "f32", "f64", "bool", "char", "str",
+];
+
+
+/// Returns true if the identifier of an expression may be a field name, eg: not `as`/`usize`/`Self`/arguments,
+/// the argument names of the container are local variables, eg: `#[jbytes(args(version: u8))]`.
+#[inline]
+pub fn is_field_ident(ident: &str, arg_names: &[String]) -> bool {
+    !EXPR_KEYWORDS.contains(&ident) && !PRIMITIVE_TYPES.contains(&ident) && !ident.starts_with(|v: char| v.is_ascii_uppercase())
+        && !arg_names.iter().any(|v| v == ident)
+}
+
+
+/// Returns the canonical key of an attribute alias, eg: `byte_size` => `byte_count`.
+#[inline]
+pub fn canonical_key(key: &str) -> &str {
+    match key {
+        "enum_default" => "branch_default",
+        "byte_size" => "byte_count",
+        "byte_size_outside" => "byte_count_outside",
+        "encode_with" => "with_encode",
+        "decode_with" => "with_decode",
+        "encode_value" => "value_encode",
+        "decode_value" => "value_decode",
+        "starts_with" => "key",
+        "end_with" => "linend",
+        "default" => "default_value",
+        key => key,
+    }
+}
+
+
+/// Returns an error at the span of the attribute key, or its value if `is_value` is true.
+pub fn error_at(attributes: &FieldAttributes, key: &str, is_value: bool, message: String) -> Error {
+    error_at_spans(&attributes.spans, key, is_value, message)
+}
+
+
+/// Returns an error at the key or value span of a container attribute.
+#[inline]
+fn container_error_at(attributes: &ContainerAttributes, key: &str, is_value: bool, message: String) -> Error {
+    error_at_spans(&attributes.spans, key, is_value, message)
+}
+
+
+fn error_at_spans(spans: &[(String, Span, Option<Span>)], key: &str, is_value: bool, message: String) -> Error {
+    match spans.iter().find(|v| canonical_key(&v.0) == key) {
+        Some((_, span, value_span)) => Error::custom_at(message, if is_value { value_span.unwrap_or(*span) } else { *span }),
+        None => Error::custom(message),
+    }
+}
+
+
+#[inline]
+fn has_attribute(attributes: &FieldAttributes, key: &str) -> bool {
+    attributes.spans.iter().any(|v| canonical_key(&v.0) == key)
+}
+
+
+/// Returns the attribute expression of a field, eg: `length="header.length - 4"`.
+fn get_expr(attributes: &FieldAttributes, key: &str) -> Option<String> {
+    match key {
+        "if_expr" => attributes.if_expr.clone(),
+        "byteorder_from" => attributes.byteorder_from.clone(),
+        "seek" => attributes.seek.clone(),
+        "seek_from_end" => attributes.seek_from_end.clone(),
+        "args" if !attributes.args.is_empty() => Some(attributes.args.iter().map(|v| v.1.as_str()).collect::<Vec<&str>>().join(", ")),
+        "length" => attributes.length.as_ref().map(|v| v.to_string()),
+        "count" => attributes.count.as_ref().map(|v| v.to_string()),
+        "try_count" => attributes.try_count.as_ref().map(|v| v.to_string()),
+        "byte_count" => attributes.byte_count.as_ref().map(|v| v.to_string()),
+        "offset" => attributes.offset.as_ref().map(|v| v.to_string()),
+        "branch" => attributes.branch.as_ref().map(|v| v.to_string()),
+        _ => None,
+    }
+}
+
+
+/// Returns true if the type is a builtin non-integer type, eg: `String`/`&str`/`Vec<T>`/`[T; N]`/`f32`.
+fn is_non_int_type(rtype: &str) -> bool {
+    rtype == "String" || rtype == "f32" || rtype == "f64" || rtype.starts_with("Vec<") || rtype.starts_with('[')
+        || rtype.starts_with('&') && (rtype.ends_with("str") || rtype.contains('['))
+}
+
+
+/// Returns true if the type is a builtin type which can't be matched by `branch`.
+fn is_non_enum_type(rtype: &str) -> bool {
+    is_non_int_type(rtype) || PRIMITIVE_TYPES.contains(&rtype) || rtype.starts_with("HashMap<")
+}
+
+
+/// Validates the attributes combinations of a field or an enum variant.
+pub fn validate_attributes(attributes: &FieldAttributes) -> Result<()> {
+    for (key1, key2) in CONFLICT_ATTRIBUTES {
+        if has_attribute(attributes, key1) && has_attribute(attributes, key2) {
+            return Err(error_at(attributes, key2, false, format!("`{key2}` can't be used together with `{key1}`")));
+        }
+    }
+
+    if attributes.restore && attributes.seek.is_none() && attributes.seek_from_end.is_none() {
+        return Err(error_at(attributes, "restore", false, "`restore` requires `seek` or `seek_from_end`".to_string()));
+    }
+
+    if let Some(label) = &attributes.encoding {
+        if encoding_to_code(label).is_none() {
+            return Err(error_at(attributes, "encoding", true, format!("unknown encoding `{label}`, eg: `utf8`/`utf16le`/`utf16be`/`latin1`/`gbk`/`shift_jis`/`windows-1252`")));
+        }
+    }
+
+    if let Some(AttrValue::Usize(0)) = attributes.align {
+        return Err(error_at(attributes, "align", true, "`align` must be greater than 0".to_string()));
+    }
+
+    Ok(())
+}
+
+
+/// Validates the attributes of fields, the types and the referenced field names.
+pub fn validate_fields(fields: &Option<Fields>, cattr: &ContainerAttributes) -> Result<()> {
+    if let Some(AttrValue::Usize(0)) = cattr.align_fields {
+        return Err(container_error_at(cattr, "align_fields", true, "`align_fields` must be greater than 0".to_string()));
+    }
+
+    // The local names are used by expressions, eg: `v0`, the field names are used by `length_of`/`range`, eg: `0`.
+    let fields = match fields {
+        Some(Fields::Struct(value)) => value.iter().map(|(ident, field)| (ident.to_string(), ident.to_string(), field)).collect::<Vec<_>>(),
+        Some(Fields::Tuple(value)) => value.iter().enumerate().map(|(index, field)| (format!("v{index}"), index.to_string(), field)).collect::<Vec<_>>(),
+        None => return Ok(()),
+    };
+
+    let variable_names = cattr.get_variable_name.as_ref().map(|v| v.to_string()).unwrap_or_default();
+    let arg_names = cattr.arg_names();
+    let names = fields.iter().map(|(name, _, _)| name.as_str()).collect::<Vec<&str>>();
+    let field_names = fields.iter().map(|(_, name, _)| name.as_str()).collect::<Vec<&str>>();
+
+    for (index, (name, _, field)) in fields.iter().enumerate() {
+        let attributes = field.attributes.get_attribute::<FieldAttributes>()?.unwrap_or_default();
+        let rtype = get_field_type(field).replace(' ', "");
+
+        validate_attributes(&attributes)?;
+
+        for key in INT_ATTRIBUTES {
+            if has_attribute(&attributes, key) && is_non_int_type(&rtype) {
+                return Err(error_at(&attributes, key, false, format!("`{key}` only supports integer types, found `{rtype}`")));
+            }
+        }
+
+        if attributes.branch.is_some() && is_non_enum_type(&rtype) {
+            return Err(error_at(&attributes, "branch", false, format!("`branch` only supports enum types, found `{rtype}`")));
+        }
+
+        for key in EXPR_ATTRIBUTES {
+            if let Some(expr) = get_expr(&attributes, key) {
+                let mut error = None;
+
+                replace_expr_idents(&expr, |ident| {
+                    let is_skip = !is_field_ident(ident, &arg_names) || variable_names.split(',').any(|v| v.trim() == ident);
+
+                    if error.is_none() && !is_skip {
+                        match names.iter().position(|v| *v == ident) {
+                            Some(position) if position < index => {},
+                            Some(_) => error = Some(format!("`{key}` references the field `{ident}` which is decoded after `{name}`")),
+                            None => error = Some(format!("`{key}` references an unknown field `{ident}`")),
+                        }
+                    }
+
+                    None
+                });
+
+                if let Some(error) = error {
+                    return Err(error_at(&attributes, key, true, error));
+                }
+            }
+        }
+
+        for (key, value) in [("length_of", &attributes.length_of), ("count_of", &attributes.count_of)] {
+            if let Some(value) = value {
+                if !field_names.contains(&value.as_str()) {
+                    return Err(error_at(&attributes, key, true, format!("`{key}` references an unknown field `{value}`")));
+                }
+            }
+        }
+
+        if let Some(range) = &attributes.range {
+            for value in range.split("..") {
+                let value = value.trim();
+
+                if value != "start" && value != "end" && !field_names.contains(&value) {
+                    return Err(error_at(&attributes, "range", true, format!("`range` references an unknown field `{value}`, eg: `start..field`")));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+
+/// Validates the attributes of enum variants and their fields.
+pub fn validate_variants(variants: &[EnumVariant], cattr: &ContainerAttributes) -> Result<()> {
+    for variant in variants {
+        validate_attributes(&variant.attributes.get_attribute::<FieldAttributes>()?.unwrap_or_default())?;
+        validate_fields(&variant.fields, cattr)?;
+
+        let fields = match &variant.fields {
+            Some(Fields::Struct(value)) => value.iter().map(|v| &v.1).collect::<Vec<_>>(),
+            Some(Fields::Tuple(value)) => value.iter().collect::<Vec<_>>(),
+            None => vec![],
+        };
+
+        for field in fields {
+            let attributes = field.attributes.get_attribute::<FieldAttributes>()?.unwrap_or_default();
+
+            for key in ["seek", "seek_from_end"] {
+                if has_attribute(&attributes, key) {
+                    return Err(error_at(&attributes, key, false, format!("`{key}` is only supported by the struct fields")));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -43,6 +43,10 @@ mod jbytes;
 use jbytes::attribute::ContainerAttributes;
 use jbytes::derive_enum;
 use jbytes::derive_struct;
+use jbytes::derive_view;
+use jbytes::derive_fixed_size;
+use jbytes::derive_generate;
+use jbytes::derive_describe;
 
 use proc_macro::TokenStream;
 use quote::ToTokens;
@@ -72,12 +76,13 @@ fn derive_decode_inner(input: TokenStream) -> Result<TokenStream> {
             }.generate_decode(&mut generator)?;
         }
         Body::Enum(body) => {
-            derive_enum::DeriveEnum {
+            let derive = derive_enum::DeriveEnum {
                 variants: body.variants,
                 attributes,
                 lifetimes: None,
-            }
-            .generate_decode(&mut generator)?;
+            };
+            derive.generate_decode(&mut generator)?;
+            derive.generate_branch_type(&mut generator)?;
         }
     }
 
@@ -90,7 +95,8 @@ fn derive_decode_inner(input: TokenStream) -> Result<TokenStream> {
 pub fn derive_borrow_decode(input: TokenStream) -> TokenStream {
     let input_tmp = input.clone();
     let derive_input = parse_macro_input!(input_tmp as DeriveInput);
-    let lifetimes = derive_input.generics.to_token_stream().to_string();
+    // The input lifetime of `BorrowByteDecode<'de>`, type and const parameters are added by the generator.
+    let lifetimes = derive_input.generics.lifetimes().next().map(|v| format!("<{}>", v.lifetime.to_token_stream())).unwrap_or_default();
 
     derive_borrow_decode_inner(input, lifetimes).unwrap_or_else(|e|e.into_token_stream())
 }
@@ -196,3 +202,157 @@ fn derive_borrow_encode_inner(input: TokenStream) -> Result<TokenStream> {
     generator.export_to_file("jbytes", "Encode");
     generator.finish()
 }
+
+
+#[proc_macro_derive(ByteView, attributes(jbytes))]
+pub fn derive_view(input: TokenStream) -> TokenStream {
+    derive_view_inner(input).unwrap_or_else(|e|e.into_token_stream())
+}
+
+
+fn derive_view_inner(input: TokenStream) -> Result<TokenStream> {
+    let parse = Parse::new(input)?;
+    let (mut generator, attributes, body) = parse.into_generator();
+    let attributes = attributes
+        .get_attribute::<ContainerAttributes>()?
+        .unwrap_or_default();
+
+    match body {
+        Body::Struct(body) => {
+            derive_view::DeriveView {
+                fields: body.fields,
+                attributes,
+            }.generate_view(&mut generator)?;
+        }
+        Body::Enum(_) => return Err(Error::custom("`ByteView` only supports structs")),
+    }
+
+    generator.export_to_file("jbytes", "View");
+    generator.finish()
+}
+
+
+#[proc_macro_derive(ByteViewMut, attributes(jbytes))]
+pub fn derive_view_mut(input: TokenStream) -> TokenStream {
+    derive_view_mut_inner(input).unwrap_or_else(|e|e.into_token_stream())
+}
+
+
+fn derive_view_mut_inner(input: TokenStream) -> Result<TokenStream> {
+    let parse = Parse::new(input)?;
+    let (mut generator, attributes, body) = parse.into_generator();
+    let attributes = attributes
+        .get_attribute::<ContainerAttributes>()?
+        .unwrap_or_default();
+
+    match body {
+        Body::Struct(body) => {
+            derive_view::DeriveView {
+                fields: body.fields,
+                attributes,
+            }.generate_view_mut(&mut generator)?;
+        }
+        Body::Enum(_) => return Err(Error::custom("`ByteViewMut` only supports structs")),
+    }
+
+    generator.export_to_file("jbytes", "ViewMut");
+    generator.finish()
+}
+
+
+#[proc_macro_derive(FixedSize, attributes(jbytes))]
+pub fn derive_fixed_size(input: TokenStream) -> TokenStream {
+    derive_fixed_size_inner(input).unwrap_or_else(|e|e.into_token_stream())
+}
+
+
+fn derive_fixed_size_inner(input: TokenStream) -> Result<TokenStream> {
+    let parse = Parse::new(input)?;
+    let (mut generator, attributes, body) = parse.into_generator();
+    let attributes = attributes
+        .get_attribute::<ContainerAttributes>()?
+        .unwrap_or_default();
+
+    match body {
+        Body::Struct(body) => {
+            derive_fixed_size::DeriveFixedSize {
+                fields: body.fields,
+                attributes,
+            }.generate_fixed_size(&mut generator)?;
+        }
+        Body::Enum(_) => return Err(Error::custom("`FixedSize` only supports structs")),
+    }
+
+    generator.export_to_file("jbytes", "FixedSize");
+    generator.finish()
+}
+
+
+#[proc_macro_derive(ByteGenerate, attributes(jbytes))]
+pub fn derive_generate(input: TokenStream) -> TokenStream {
+    derive_generate_inner(input).unwrap_or_else(|e|e.into_token_stream())
+}
+
+
+fn derive_generate_inner(input: TokenStream) -> Result<TokenStream> {
+    let parse = Parse::new(input)?;
+    let (mut generator, attributes, body) = parse.into_generator();
+    let attributes = attributes
+        .get_attribute::<ContainerAttributes>()?
+        .unwrap_or_default();
+
+    match body {
+        Body::Struct(body) => {
+            derive_generate::DeriveGenerate {
+                fields: body.fields,
+                attributes,
+            }.generate_generate(&mut generator)?;
+        }
+        Body::Enum(body) => {
+            derive_generate::DeriveGenerateEnum {
+                variants: body.variants,
+                attributes,
+            }.generate_generate(&mut generator)?;
+        }
+    }
+
+    generator.export_to_file("jbytes", "ByteGenerate");
+    generator.finish()
+}
+
+
+#[proc_macro_derive(Describe, attributes(jbytes))]
+pub fn derive_describe(input: TokenStream) -> TokenStream {
+    derive_describe_inner(input).unwrap_or_else(|e|e.into_token_stream())
+}
+
+
+fn derive_describe_inner(input: TokenStream) -> Result<TokenStream> {
+    let parse = Parse::new(input)?;
+    // The type parameters aren't described, eg: `value: T`.
+    let generics = match &parse {
+        Parse::Struct { generics, .. } | Parse::Enum { generics, .. } => generics.as_ref()
+            .map(|v| v.iter_generics().map(|v| v.name().to_string()).collect())
+            .unwrap_or_default(),
+        _ => vec![],
+    };
+    let (mut generator, attributes, body) = parse.into_generator();
+    let attributes = attributes
+        .get_attribute::<ContainerAttributes>()?
+        .unwrap_or_default();
+
+    let (fields, variants) = match body {
+        Body::Struct(body) => (body.fields, None),
+        Body::Enum(body) => (None, Some(body.variants)),
+    };
+
+    derive_describe::DeriveDescribe {
+        fields,
+        variants,
+        attributes,
+        generics,
+    }.generate_describe(&mut generator)?;
+
+    generator.export_to_file("jbytes", "Describe");
+    generator.finish()
+}
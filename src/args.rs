@@ -0,0 +1,69 @@
+use core::any::Any;
+use crate::std::Vec;
+use crate::FieldAttrModifiers;
+
+
+/// This is the arguments declared by `#[jbytes(args(<name>: <type>, ..))]`, it's implemented by the derive macros
+/// for each derived trait `K`, so the parent field builds the arguments with the declared types.
+///
+/// The argument types must be `'static`, because the arguments are passed as `&dyn Any`.
+///
+/// # Example
+///
+/// ```
+/// use jbytes::ByteDecode;
+/// use jbytes::args::ArgsOf;
+///
+/// #[derive(jbytes_derive::ByteDecode)]
+/// #[jbytes(args(addr_len: usize))]
+/// pub struct Address {
+///     #[jbytes(count="addr_len")]
+///     pub value: Vec<u8>,
+/// }
+///
+/// let args = ArgsOf::<Address, jbytes::args::ByteDecode> { addr_len: 2 };
+/// let fattr = jbytes::FieldAttrModifiers { args: Some(&args), ..Default::default() };
+/// let bytes = jbytes::Bytes::new(b"\x01\x02");
+/// assert_eq!(<Address as ByteDecode>::decode_inner(&bytes, None, Some(&fattr)).unwrap().value, [1, 2]);
+/// ```
+pub trait ByteArgs<K> {
+    type Args: Any;
+}
+
+
+/// The arguments of `T` for the derived trait `K`, eg: `ArgsOf::<Body, jbytes::args::ByteDecode> { version: 1 }`.
+pub type ArgsOf<T, K> = <T as ByteArgs<K>>::Args;
+
+
+/// The marker of `ByteDecode` for `ByteArgs`.
+pub struct ByteDecode;
+
+
+/// The marker of `BorrowByteDecode` for `ByteArgs`.
+pub struct BorrowByteDecode;
+
+
+/// The marker of `ByteEncode` for `ByteArgs`.
+pub struct ByteEncode;
+
+
+/// The marker of `BorrowByteEncode` for `ByteArgs`.
+pub struct BorrowByteEncode;
+
+
+// The arguments are passed to the elements.
+impl<K, E: ByteArgs<K>> ByteArgs<K> for Option<E> {
+    type Args = E::Args;
+}
+
+
+impl<K, E: ByteArgs<K>> ByteArgs<K> for Vec<E> {
+    type Args = E::Args;
+}
+
+
+/// Returns the arguments of `#[jbytes(args(..))]` which are passed from the parent field, or None if they're missing.
+#[inline]
+pub fn get_args<'a, T: ByteArgs<K> + ?Sized, K>(fattr: Option<&FieldAttrModifiers<'a>>) -> Option<&'a T::Args> {
+    fattr.and_then(|v| v.args).and_then(|v| v.downcast_ref::<T::Args>())
+}
@@ -0,0 +1,329 @@
+use core::any::Any;
+use core::marker::PhantomData;
+use crate::std::Vec;
+
+
+/// This is a branch value passed from the `branch` expression of a field to the enum.
+///
+/// Integers are passed as `usize`, `str`/`String` as `Str`, `[u8]`/`Vec<u8>` as `Bytes`,
+/// any other type (eg: C-like enums/tuples) is passed as `Any` and matched by `#[jbytes(branch_type="T")]`.
+///
+/// # Example
+///
+/// ```
+/// use jbytes::BranchValue;
+///
+/// let method = "GET".to_string();
+/// assert_eq!(jbytes::branch_value!(&method).as_str(), Some("GET"));
+/// assert_eq!(jbytes::branch_value!(&0x0800_u16).as_usize(), Some(0x0800));
+/// assert_eq!(jbytes::branch_value!(&(1_u8, 2_u8)).downcast_ref::<(u8, u8)>(), Some(&(1, 2)));
+/// assert_eq!(matches!(jbytes::branch_value!(&b"\x01\x02"[..]), BranchValue::Bytes(b"\x01\x02")), true);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub enum BranchValue<'a> {
+    Usize(usize),
+    Str(&'a str),
+    Bytes(&'a [u8]),
+    Any(&'a dyn Any),
+}
+
+
+impl<'a> BranchValue<'a> {
+    #[inline]
+    pub fn as_usize(&self) -> Option<usize> {
+        match self {
+            Self::Usize(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn as_str(&self) -> Option<&'a str> {
+        match self {
+            Self::Str(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the bytes of `Bytes`, or the UTF-8 bytes of `Str`.
+    #[inline]
+    pub fn as_bytes(&self) -> Option<&'a [u8]> {
+        match self {
+            Self::Bytes(value) => Some(value),
+            Self::Str(value) => Some(value.as_bytes()),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn downcast_ref<T: Any>(&self) -> Option<&'a T> {
+        match self {
+            Self::Any(value) => value.downcast_ref::<T>(),
+            _ => None,
+        }
+    }
+}
+
+
+/// This is an integer type which is passed as `BranchValue::Usize`.
+pub trait BranchInt: Copy {
+    fn to_branch_usize(self) -> usize;
+}
+
+
+macro_rules! impl_branch_int {
+    ($($typ:ty),*) => {
+        $(
+            impl BranchInt for $typ {
+                #[inline]
+                fn to_branch_usize(self) -> usize {
+                    self as usize
+                }
+            }
+        )*
+    };
+}
+
+
+impl_branch_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, bool, char);
+
+
+// The conversions are selected by autoref specialization, see `branch_value!`.
+#[doc(hidden)]
+pub struct BranchRef<'a, T: ?Sized>(pub &'a T);
+
+
+#[doc(hidden)]
+pub trait ToBranchUsize<'a> {
+    fn to_branch_value(&self) -> BranchValue<'a>;
+}
+
+
+#[doc(hidden)]
+pub trait ToBranchStr<'a> {
+    fn to_branch_value(&self) -> BranchValue<'a>;
+}
+
+
+#[doc(hidden)]
+pub trait ToBranchBytes<'a> {
+    fn to_branch_value(&self) -> BranchValue<'a>;
+}
+
+
+#[doc(hidden)]
+pub trait ToBranchAny<'a> {
+    fn to_branch_value(&self) -> BranchValue<'a>;
+}
+
+
+impl<'a, T: BranchInt> ToBranchUsize<'a> for &&&BranchRef<'a, T> {
+    #[inline]
+    fn to_branch_value(&self) -> BranchValue<'a> {
+        BranchValue::Usize(self.0.to_branch_usize())
+    }
+}
+
+
+impl<'a, T: AsRef<str> + ?Sized> ToBranchStr<'a> for &&BranchRef<'a, T> {
+    #[inline]
+    fn to_branch_value(&self) -> BranchValue<'a> {
+        BranchValue::Str(self.0.as_ref())
+    }
+}
+
+
+impl<'a, T: AsRef<[u8]> + ?Sized> ToBranchBytes<'a> for &BranchRef<'a, T> {
+    #[inline]
+    fn to_branch_value(&self) -> BranchValue<'a> {
+        BranchValue::Bytes(self.0.as_ref())
+    }
+}
+
+
+impl<'a, T: Any> ToBranchAny<'a> for BranchRef<'a, T> {
+    #[inline]
+    fn to_branch_value(&self) -> BranchValue<'a> {
+        BranchValue::Any(self.0)
+    }
+}
+
+
+/// Converts a reference of the `branch` expression to [BranchValue], it's used by the derive macros.
+#[macro_export]
+macro_rules! branch_value {
+    ($value:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::branch::{ToBranchUsize as _, ToBranchStr as _, ToBranchBytes as _, ToBranchAny as _};
+        (&&&&$crate::branch::BranchRef($value)).to_branch_value()
+    }};
+}
+
+
+/// This is the branch type of an enum, it's implemented by `#[derive(ByteDecode)]`, so the `branch` expression
+/// of a field is checked at compile time, eg: a `String` branch requires `#[jbytes(branch_type="str")]`.
+#[doc(hidden)]
+pub trait BranchType {
+    /// `usize` for the integer branches, `str`/`[u8]` for the string/bytes branches, `Typed<T>` for the other types.
+    type Branch: ?Sized;
+}
+
+
+/// This is a branch type which accepts the values of `T` only.
+#[doc(hidden)]
+pub struct Typed<T: ?Sized>(PhantomData<T>);
+
+
+/// This is a branch type which isn't checked at compile time, eg: the generic types.
+#[doc(hidden)]
+pub struct Unchecked;
+
+
+/// Converts the `branch` expressions of `T` as the branch type expects, so the field and the enum share one classification.
+///
+/// The `fallback` is the conversion of `branch_value!`, which is used by the unchecked branch types.
+#[doc(hidden)]
+pub trait BranchFrom<T: ?Sized> {
+    fn branch_from<'a>(value: &'a T, fallback: BranchValue<'a>) -> BranchValue<'a>;
+}
+
+
+impl<T: BranchInt> BranchFrom<T> for usize {
+    #[inline]
+    fn branch_from<'a>(value: &'a T, _fallback: BranchValue<'a>) -> BranchValue<'a> {
+        BranchValue::Usize(value.to_branch_usize())
+    }
+}
+
+
+impl<T: AsRef<str> + ?Sized> BranchFrom<T> for str {
+    #[inline]
+    fn branch_from<'a>(value: &'a T, _fallback: BranchValue<'a>) -> BranchValue<'a> {
+        BranchValue::Str(value.as_ref())
+    }
+}
+
+
+impl<T: AsRef<[u8]> + ?Sized> BranchFrom<T> for [u8] {
+    #[inline]
+    fn branch_from<'a>(value: &'a T, _fallback: BranchValue<'a>) -> BranchValue<'a> {
+        BranchValue::Bytes(value.as_ref())
+    }
+}
+
+
+// The other types are matched by `downcast_ref`, which requires `T: 'static`.
+impl<T: Any> BranchFrom<T> for Typed<T> {
+    #[inline]
+    fn branch_from<'a>(value: &'a T, _fallback: BranchValue<'a>) -> BranchValue<'a> {
+        BranchValue::Any(value)
+    }
+}
+
+
+impl<T: ?Sized> BranchFrom<T> for Unchecked {
+    #[inline]
+    fn branch_from<'a>(_value: &'a T, fallback: BranchValue<'a>) -> BranchValue<'a> {
+        fallback
+    }
+}
+
+
+// The branch value is passed to the elements.
+impl<E: BranchType> BranchType for Option<E> {
+    type Branch = E::Branch;
+}
+
+
+impl<E: BranchType> BranchType for Vec<E> {
+    type Branch = E::Branch;
+}
+
+
+// The branch type of a field is selected by autoref specialization, see `typed_branch_value!`.
+#[doc(hidden)]
+pub struct BranchCheck<E: ?Sized, T: ?Sized>(PhantomData<fn(&E, &T)>);
+
+
+impl<E: ?Sized, T: ?Sized> BranchCheck<E, T> {
+    #[inline]
+    pub fn new(_value: &T) -> Self {
+        Self(PhantomData)
+    }
+}
+
+
+#[doc(hidden)]
+pub struct BranchMarker<B: ?Sized, T: ?Sized>(PhantomData<fn(&B, &T)>);
+
+
+#[doc(hidden)]
+pub trait ViaBranchType<T: ?Sized> {
+    type Branch: ?Sized;
+
+    #[inline]
+    fn branch_marker(&self) -> BranchMarker<Self::Branch, T> {
+        BranchMarker(PhantomData)
+    }
+}
+
+
+#[doc(hidden)]
+pub trait ViaUnchecked<T: ?Sized> {
+    #[inline]
+    fn branch_marker(&self) -> BranchMarker<Unchecked, T> {
+        BranchMarker(PhantomData)
+    }
+}
+
+
+impl<E: BranchType + ?Sized, T: ?Sized> ViaBranchType<T> for &BranchCheck<E, T> {
+    type Branch = E::Branch;
+}
+
+
+impl<E: ?Sized, T: ?Sized> ViaUnchecked<T> for BranchCheck<E, T> {}
+
+
+#[doc(hidden)]
+#[inline]
+pub fn branch_from<'a, B: BranchFrom<T> + ?Sized, T: ?Sized>(_marker: BranchMarker<B, T>, value: &'a T, fallback: BranchValue<'a>) -> BranchValue<'a> {
+    B::branch_from(value, fallback)
+}
+
+
+/// Checks the `branch` expression of a field against the branch type of the field type at compile time,
+/// and converts it to [BranchValue] as the enum matches it, it's used by the derive macros.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! typed_branch_value {
+    ($rtype:ty, $value:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::branch::{ViaBranchType as _, ViaUnchecked as _};
+        let value = $value;
+        $crate::branch::branch_from((&&$crate::branch::BranchCheck::<$rtype, _>::new(value)).branch_marker(), value, $crate::branch_value!(value))
+    }};
+}
+
+
+#[cfg(test)]
+mod tests {
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    enum Method {
+        Get,
+    }
+
+    #[test]
+    fn test_branch_value() {
+        assert_eq!(branch_value!(&3_u8).as_usize(), Some(3));
+        assert_eq!(branch_value!(&-1_i8).as_usize().map(|v| v as i8), Some(-1));
+        assert_eq!(branch_value!(&true).as_usize(), Some(1));
+        assert_eq!(branch_value!(&"abc").as_str(), Some("abc"));
+        assert_eq!(branch_value!(&"abc").as_bytes(), Some(&b"abc"[..]));
+        assert_eq!(branch_value!(&[1_u8, 2].to_vec()).as_bytes(), Some(&[1, 2][..]));
+        assert_eq!(branch_value!(&[1_u8, 2]).as_bytes(), Some(&[1, 2][..]));
+        assert_eq!(branch_value!(&Method::Get).downcast_ref::<Method>(), Some(&Method::Get));
+        assert_eq!(branch_value!(&Method::Get).downcast_ref::<u8>(), None);
+        assert_eq!(branch_value!(&Some(1_u8)).as_usize(), None);
+    }
+}
@@ -106,6 +106,26 @@ pub trait BufRead {
         }
     }
 
+    /// Reads the magic bytes from `self`, returns an `InvalidMagic` error with the actual bytes on mismatch.
+    #[inline]
+    fn take_magic<V: AsRef<[u8]>>(&self, value: V) -> JResult<()> {
+        let data = value.as_ref();
+
+        if self.remaining_len() < data.len() {
+            return Err(make_error(self.get_position(), ErrorKind::InvalidByteLength));
+        }
+
+        let magic = &self.remaining()[..data.len()];
+
+        if magic != data {
+            return Err(make_error(self.get_position(), ErrorKind::InvalidMagic(magic.to_vec())));
+        }
+
+        self.advance(data.len());
+
+        Ok(())
+    }
+
     /// Reads a bool from `self`.
     #[inline]
     fn take_bool(&self) -> JResult<bool> {
@@ -125,4 +125,14 @@ mod tests {
         assert_eq!(buffer.take_bytes_starts(b"\x01\x02\x03"), Ok(()));
         assert_eq!(buffer.remaining_len(), 2);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_bytes_take_magic() {
+        let buffer = Bytes::new([0x01, 0x02, 0x03, 0x04, 0x05]);
+        assert_eq!(buffer.take_magic(b"\x01\x02\x04").unwrap_err().code, crate::ErrorKind::InvalidMagic([0x01, 0x02, 0x03].to_vec()));
+        assert_eq!(buffer.remaining_len(), 5);
+        assert_eq!(buffer.take_magic(b"\x01\x02\x03"), Ok(()));
+        assert_eq!(buffer.remaining_len(), 2);
+        assert_eq!(buffer.take_magic(b"\x04\x05\x06").is_err(), true);
+    }
+}
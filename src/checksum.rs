@@ -0,0 +1,297 @@
+use core::ops::Range;
+use crate::std::*;
+use crate::errors::ThisError;
+
+
+#[derive(Debug, ThisError)]
+pub enum ChecksumParseError {
+    #[error("invalid checksum: `{0}`")]
+    InvalidChecksum(String),
+}
+
+
+/// This is a checksum algorithm type of checksum fields.
+///
+/// # Example
+///
+/// ```
+/// use jbytes::Checksum;
+/// assert_eq!(Checksum::Crc16Modbus.checksum(b"123456789"), 0x4b37);
+/// assert_eq!(Checksum::Crc32.checksum(b"123456789"), 0xcbf43926);
+///
+/// // The TCP/UDP pseudo-header checksum is updated piecewise.
+/// let mut hasher = Checksum::Internet.hasher();
+/// hasher.update(&[0x00, 0x01]);
+/// hasher.update(&[0xf2, 0x03, 0xf4, 0xf5, 0xf6, 0xf7]);
+/// assert_eq!(hasher.finish(), 0x220d);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Checksum {
+    /// RFC 1071 internet checksum, eg: IPv4/ICMP/TCP/UDP.
+    Internet,
+    /// CRC-8/SMBUS.
+    Crc8,
+    /// CRC-8/MAXIM-DOW, eg: 1-Wire.
+    Crc8Maxim,
+    /// CRC-16/ARC.
+    Crc16Arc,
+    /// CRC-16/MODBUS, eg: Modbus RTU.
+    Crc16Modbus,
+    /// CRC-16/CCITT-FALSE.
+    Crc16Ccitt,
+    /// CRC-16/XMODEM.
+    Crc16Xmodem,
+    /// CRC-32/ISO-HDLC, eg: PNG/Ethernet/zlib.
+    Crc32,
+    /// CRC-32/ISCSI (Castagnoli), eg: SCTP.
+    Crc32c,
+    /// Adler-32, eg: zlib.
+    Adler32,
+    /// XOR of all bytes.
+    Xor,
+    /// Sum of all bytes modulo 256.
+    Sum8,
+}
+
+
+/// The CRC parameters: width, poly, init, reflected, xorout.
+struct CrcParams(u32, u64, u64, bool, u64);
+
+
+impl Checksum {
+    /// Parses the checksum name, eg: `internet`/`crc16_modbus`/`crc32`/`adler32`/`xor`/`sum8`.
+    pub fn parse(name: &str) -> Result<Self, ChecksumParseError> {
+        match name.to_ascii_lowercase().as_str() {
+            "internet" => Ok(Self::Internet),
+            "crc8" => Ok(Self::Crc8),
+            "crc8_maxim" => Ok(Self::Crc8Maxim),
+            "crc16_arc" => Ok(Self::Crc16Arc),
+            "crc16_modbus" => Ok(Self::Crc16Modbus),
+            "crc16_ccitt" => Ok(Self::Crc16Ccitt),
+            "crc16_xmodem" => Ok(Self::Crc16Xmodem),
+            "crc32" => Ok(Self::Crc32),
+            "crc32c" => Ok(Self::Crc32c),
+            "adler32" => Ok(Self::Adler32),
+            "xor" => Ok(Self::Xor),
+            "sum8" => Ok(Self::Sum8),
+            _ => Err(ChecksumParseError::InvalidChecksum(name.to_string())),
+        }
+    }
+
+    #[inline]
+    fn crc_params(&self) -> Option<CrcParams> {
+        match self {
+            Self::Crc8 => Some(CrcParams(8, 0x07, 0x00, false, 0x00)),
+            Self::Crc8Maxim => Some(CrcParams(8, 0x31, 0x00, true, 0x00)),
+            Self::Crc16Arc => Some(CrcParams(16, 0x8005, 0x0000, true, 0x0000)),
+            Self::Crc16Modbus => Some(CrcParams(16, 0x8005, 0xffff, true, 0x0000)),
+            Self::Crc16Ccitt => Some(CrcParams(16, 0x1021, 0xffff, false, 0x0000)),
+            Self::Crc16Xmodem => Some(CrcParams(16, 0x1021, 0x0000, false, 0x0000)),
+            Self::Crc32 => Some(CrcParams(32, 0x04c11db7, 0xffffffff, true, 0xffffffff)),
+            Self::Crc32c => Some(CrcParams(32, 0x1edc6f41, 0xffffffff, true, 0xffffffff)),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of bytes of the checksum value.
+    #[inline]
+    pub fn size(&self) -> usize {
+        match self {
+            Self::Internet => 2,
+            Self::Adler32 => 4,
+            Self::Xor | Self::Sum8 => 1,
+            _ => self.crc_params().map(|v| v.0 as usize / 8).unwrap_or_default(),
+        }
+    }
+
+    /// Returns a streaming hasher of the checksum.
+    pub fn hasher(&self) -> ChecksumHasher {
+        let state = match self.crc_params() {
+            Some(CrcParams(width, _, init, true, _)) => init.reverse_bits() >> (64 - width),
+            Some(CrcParams(_, _, init, false, _)) => init,
+            None if *self == Self::Adler32 => 1,
+            None => 0,
+        };
+
+        ChecksumHasher { checksum: *self, state, odd: None }
+    }
+
+    /// Calculates the checksum of the bytes.
+    #[inline]
+    pub fn checksum(&self, data: &[u8]) -> u64 {
+        let mut hasher = self.hasher();
+        hasher.update(data);
+        hasher.finish()
+    }
+
+    /// Calculates the checksum of `data[range]`, the bytes of `data[skip]` are treated as zero,
+    /// eg: the checksum field itself.
+    pub fn checksum_range(&self, data: &[u8], range: Range<usize>, skip: Range<usize>) -> Option<u64> {
+        let data = data.get(range.clone())?;
+        let skip_start = skip.start.clamp(range.start, range.end) - range.start;
+        let skip_end = skip.end.clamp(range.start, range.end) - range.start;
+        let mut hasher = self.hasher();
+
+        if skip_start < skip_end {
+            hasher.update(&data[..skip_start]);
+
+            for _ in skip_start..skip_end {
+                hasher.update(&[0x00]);
+            }
+
+            hasher.update(&data[skip_end..]);
+        }
+        else {
+            hasher.update(data);
+        }
+
+        Some(hasher.finish())
+    }
+}
+
+
+/// This is a streaming hasher of [Checksum].
+#[derive(Debug, Clone)]
+pub struct ChecksumHasher {
+    checksum: Checksum,
+    state: u64,
+    odd: Option<u8>,
+}
+
+
+impl ChecksumHasher {
+    /// Updates the checksum with the bytes.
+    pub fn update(&mut self, data: &[u8]) {
+        match self.checksum {
+            Checksum::Internet => {
+                let mut data = data;
+
+                if let (Some(high), Some((low, rest))) = (self.odd, data.split_first()) {
+                    self.state += u16::from_be_bytes([high, *low]) as u64;
+                    self.odd = None;
+                    data = rest;
+                }
+
+                let chunks = data.chunks_exact(2);
+
+                if let Some(value) = chunks.remainder().first() {
+                    self.odd = Some(*value);
+                }
+
+                for value in chunks {
+                    self.state += u16::from_be_bytes([value[0], value[1]]) as u64;
+                }
+            },
+            Checksum::Adler32 => {
+                let (mut a, mut b) = (self.state & 0xffff, self.state >> 16);
+
+                for value in data {
+                    a = (a + *value as u64) % 65521;
+                    b = (b + a) % 65521;
+                }
+
+                self.state = (b << 16) | a;
+            },
+            Checksum::Xor => self.state = data.iter().fold(self.state, |acc, v| acc ^ *v as u64),
+            Checksum::Sum8 => self.state = data.iter().fold(self.state, |acc, v| (acc + *v as u64) & 0xff),
+            _ => {
+                if let Some(CrcParams(width, poly, _, reflected, _)) = self.checksum.crc_params() {
+                    let mask = u64::MAX >> (64 - width);
+                    let rpoly = poly.reverse_bits() >> (64 - width);
+
+                    for value in data {
+                        if reflected {
+                            self.state ^= *value as u64;
+
+                            for _ in 0..8 {
+                                self.state = if self.state & 1 == 1 { (self.state >> 1) ^ rpoly } else { self.state >> 1 };
+                            }
+                        }
+                        else {
+                            self.state ^= (*value as u64) << (width - 8);
+
+                            for _ in 0..8 {
+                                let top = self.state & (1 << (width - 1)) != 0;
+                                self.state = if top { (self.state << 1) ^ poly } else { self.state << 1 } & mask;
+                            }
+                        }
+                    }
+                }
+            },
+        }
+    }
+
+    /// Returns the checksum value.
+    pub fn finish(&self) -> u64 {
+        match self.checksum {
+            Checksum::Internet => {
+                let mut sum = self.state;
+
+                if let Some(high) = self.odd {
+                    sum += (high as u64) << 8;
+                }
+
+                while sum >> 16 != 0 {
+                    sum = (sum & 0xffff) + (sum >> 16);
+                }
+
+                !sum & 0xffff
+            },
+            Checksum::Adler32 | Checksum::Xor | Checksum::Sum8 => self.state,
+            _ => self.state ^ self.checksum.crc_params().map(|v| v.4).unwrap_or_default(),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checksum_check_values() {
+        let data = b"123456789";
+        assert_eq!(Checksum::Crc8.checksum(data), 0xf4);
+        assert_eq!(Checksum::Crc8Maxim.checksum(data), 0xa1);
+        assert_eq!(Checksum::Crc16Arc.checksum(data), 0xbb3d);
+        assert_eq!(Checksum::Crc16Modbus.checksum(data), 0x4b37);
+        assert_eq!(Checksum::Crc16Ccitt.checksum(data), 0x29b1);
+        assert_eq!(Checksum::Crc16Xmodem.checksum(data), 0x31c3);
+        assert_eq!(Checksum::Crc32.checksum(data), 0xcbf43926);
+        assert_eq!(Checksum::Crc32c.checksum(data), 0xe3069283);
+        assert_eq!(Checksum::Adler32.checksum(b"Wikipedia"), 0x11e60398);
+        assert_eq!(Checksum::Xor.checksum(&[0x01, 0x02, 0x04]), 0x07);
+        assert_eq!(Checksum::Sum8.checksum(&[0xff, 0x02]), 0x01);
+    }
+
+    #[test]
+    fn test_checksum_internet() {
+        // RFC 1071 example
+        assert_eq!(Checksum::Internet.checksum(&[0x00, 0x01, 0xf2, 0x03, 0xf4, 0xf5, 0xf6, 0xf7]), 0x220d);
+
+        // odd length and odd chunk boundaries
+        let mut hasher = Checksum::Internet.hasher();
+        hasher.update(&[0x00, 0x01, 0xf2]);
+        hasher.update(&[0x03, 0xf4]);
+        hasher.update(&[0xf5, 0xf6, 0xf7]);
+        assert_eq!(hasher.finish(), 0x220d);
+        assert_eq!(Checksum::Internet.checksum(&[0x01]), !0x0100 & 0xffff);
+    }
+
+    #[test]
+    fn test_checksum_range() {
+        let data = [0x00, 0x01, 0xaa, 0xbb, 0xf2, 0x03, 0xf4, 0xf5, 0xf6, 0xf7];
+        assert_eq!(Checksum::Internet.checksum_range(&data, 0..10, 2..4), Some(0x220d));
+        assert_eq!(Checksum::Crc32.checksum_range(b"x123456789", 1..10, 0..1), Some(0xcbf43926));
+        assert_eq!(Checksum::Crc32.checksum_range(b"123", 0..4, 0..0), None);
+    }
+
+    #[test]
+    fn test_checksum_parse() {
+        assert_eq!(Checksum::parse("crc16_modbus").unwrap(), Checksum::Crc16Modbus);
+        assert_eq!(Checksum::parse("Internet").unwrap(), Checksum::Internet);
+        assert_eq!(Checksum::parse("crc64").is_err(), true);
+        assert_eq!(Checksum::Crc16Modbus.size(), 2);
+        assert_eq!(Checksum::Crc32.size(), 4);
+    }
+}
@@ -0,0 +1,91 @@
+use core::any::Any;
+use core::marker::PhantomData;
+use crate::std::Vec;
+
+
+/// This is the user context declared by `#[jbytes(ctx="C")]`, it's implemented by the derive macros for each derived
+/// trait `K` (see [ByteArgs](crate::args::ByteArgs)), so the context of `decode_with_ctx`/`encode_with_ctx`
+/// and the contexts of the nested types are checked at compile time.
+///
+/// The types between the root and a type with `ctx` must declare the same `ctx`, as the context is passed through them.
+pub trait ByteCtx<K> {
+    type Ctx: Any;
+}
+
+
+/// This is the context of the types without `#[jbytes(ctx="C")]`.
+#[doc(hidden)]
+pub struct NoCtx;
+
+
+// The context is passed to the elements.
+impl<K, E: ByteCtx<K>> ByteCtx<K> for Option<E> {
+    type Ctx = E::Ctx;
+}
+
+
+impl<K, E: ByteCtx<K>> ByteCtx<K> for Vec<E> {
+    type Ctx = E::Ctx;
+}
+
+
+// The context of a field type is selected by autoref specialization, see `assert_ctx!`.
+#[doc(hidden)]
+pub struct CtxCheck<E: ?Sized, K, P>(PhantomData<fn(&E, K, P)>);
+
+
+impl<E: ?Sized, K, P> CtxCheck<E, K, P> {
+    #[inline]
+    pub fn new(_value: &E) -> Self {
+        Self(PhantomData)
+    }
+}
+
+
+#[doc(hidden)]
+pub struct CtxMarker<C, P>(PhantomData<fn(C, P)>);
+
+
+#[doc(hidden)]
+pub trait ViaByteCtx<P> {
+    type Ctx;
+
+    #[inline]
+    fn ctx_marker(&self) -> CtxMarker<Self::Ctx, P> {
+        CtxMarker(PhantomData)
+    }
+}
+
+
+#[doc(hidden)]
+pub trait ViaNoCtx<P> {
+    #[inline]
+    fn ctx_marker(&self) -> CtxMarker<P, P> {
+        CtxMarker(PhantomData)
+    }
+}
+
+
+impl<E: ByteCtx<K> + ?Sized, K, P> ViaByteCtx<P> for &CtxCheck<E, K, P> {
+    type Ctx = E::Ctx;
+}
+
+
+impl<E: ?Sized, K, P> ViaNoCtx<P> for CtxCheck<E, K, P> {}
+
+
+#[doc(hidden)]
+#[inline]
+pub fn check_ctx<C>(_marker: CtxMarker<C, C>) {}
+
+
+/// Checks the context of a field type against the context of the container at compile time, it's used by the derive macros.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! assert_ctx {
+    ($value:expr, $kind:ty, $ctx:ty) => {{
+        #[allow(unused_imports)]
+        use $crate::ctx::{ViaByteCtx as _, ViaNoCtx as _};
+        $crate::ctx::check_ctx((&&$crate::ctx::CtxCheck::<_, $kind, $ctx>::new($value)).ctx_marker());
+    }};
+}
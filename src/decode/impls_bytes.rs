@@ -2,10 +2,29 @@ use crate::{
     JResult, BufRead,
     BorrowByteDecode,
     ContainerAttrModifiers, FieldAttrModifiers,
-    get_byteorder,
+    get_byteorder, get_code_unit_size,
+    ErrorKind, make_error,
 };
 
 
+/// Reads bytes up to a NUL code unit and skips it, the code units are aligned to the current position.
+#[inline]
+fn take_cstr<T: BufRead>(input: &T, unit: usize) -> JResult<&'_ [u8]> {
+    if unit == 1 {
+        return input.find_subsequence([0x00]);
+    }
+
+    match input.remaining().chunks_exact(unit).position(|v| v.iter().all(|v| *v == 0x00)) {
+        Some(index) => {
+            let value = input.take_bytes(index * unit)?;
+            input.advance(unit);
+            Ok(value)
+        },
+        None => Err(make_error(input.get_position(), ErrorKind::SubSequence)),
+    }
+}
+
+
 #[inline]
 pub(super) fn find_subsequence<'da, 'db, T: BufRead>(input: &'da T, cattr: Option<&'db ContainerAttrModifiers>, fattr: Option<&'db FieldAttrModifiers>) -> JResult<&'da [u8]> {
     let value;
@@ -21,11 +40,18 @@ pub(super) fn find_subsequence<'da, 'db, T: BufRead>(input: &'da T, cattr: Optio
             _ = input.find_subsequence_needle(split, true)?;
         }
 
+        let unit = get_code_unit_size(fattr);
+        // length/byte_count/the default prefix counts code units instead of bytes.
+        let scale = if fr.code_units { unit } else { 1 };
+
         if let Some(length) = fr.length {
-            value = input.take_bytes(length)?;
+            value = input.take_bytes(length * scale)?;
         }
         else if let Some(byte_count) = fr.byte_count {
-            value = input.take_bytes(input.take_byteorder_uint(byte_count, get_byteorder(cattr, fattr))? as usize)?;
+            value = input.take_bytes(input.take_byteorder_uint(byte_count, get_byteorder(cattr, fattr))? as usize * scale)?;
+        }
+        else if fr.cstr {
+            value = take_cstr(input, unit)?;
         }
         else if fr.linend {
             value = input.find_subsequences(["\r\n", "\n", "\x00"])?;
@@ -37,7 +63,24 @@ pub(super) fn find_subsequence<'da, 'db, T: BufRead>(input: &'da T, cattr: Optio
             value = input.take_bytes(input.remaining_len())?;
         }
         else {
-            value = input.take_bytes(input.take_u8()? as usize)?;
+            value = input.take_bytes(input.take_u8()? as usize * scale)?;
+        }
+
+        // A fixed-width C string ends at the first NUL, the rest is padding.
+        if fr.cstr {
+            if let Some(index) = value.chunks_exact(unit).position(|v| v.iter().all(|v| *v == 0x00)) {
+                return Ok(&value[..index * unit]);
+            }
+        }
+
+        if let Some(pad) = fr.pad {
+            let mut value = value;
+
+            while value.len() >= unit && value[value.len() - unit..].iter().all(|v| *v == pad) {
+                value = &value[..value.len() - unit];
+            }
+
+            return Ok(value);
         }
     }
     else {
@@ -139,4 +182,50 @@ mod tests {
         assert_eq!(<&[u8]>::decode_inner(&bytes, None, Some(&fattr)).unwrap(), &[0x03, 0x04, 0x05]);
         assert_eq!(bytes.remaining_len(), 0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_decode_bytes_pad() {
+        // test pad example
+        let bytes = Bytes::new([0x01, 0x02, 0x20, 0x20, 0x03]);
+        let fattr = FieldAttrModifiers {
+            length: Some(4),
+            pad: Some(0x20),
+            ..Default::default()
+        };
+        assert_eq!(<&[u8]>::decode_inner(&bytes, None, Some(&fattr)).unwrap(), &[0x01, 0x02]);
+        assert_eq!(bytes.remaining_len(), 1);
+
+        // test pad of all bytes
+        let bytes = Bytes::new([0x00, 0x00]);
+        let fattr = FieldAttrModifiers {
+            length: Some(2),
+            pad: Some(0x00),
+            ..Default::default()
+        };
+        assert_eq!(<&[u8]>::decode_inner(&bytes, None, Some(&fattr)).unwrap(), &[] as &[u8]);
+        assert_eq!(bytes.remaining_len(), 0);
+
+        // test cstr example
+        let bytes = Bytes::new([0x01, 0x02, 0x00, 0x03]);
+        let fattr = FieldAttrModifiers {
+            cstr: true,
+            ..Default::default()
+        };
+        assert_eq!(<&[u8]>::decode_inner(&bytes, None, Some(&fattr)).unwrap(), &[0x01, 0x02]);
+        assert_eq!(bytes.remaining_len(), 1);
+
+        // test cstr error example
+        let bytes = Bytes::new([0x01, 0x02]);
+        assert_eq!(<&[u8]>::decode_inner(&bytes, None, Some(&fattr)).is_err(), true);
+
+        // test fixed-width cstr example
+        let bytes = Bytes::new([0x01, 0x02, 0x00, 0xff, 0x03]);
+        let fattr = FieldAttrModifiers {
+            length: Some(4),
+            cstr: true,
+            ..Default::default()
+        };
+        assert_eq!(<&[u8]>::decode_inner(&bytes, None, Some(&fattr)).unwrap(), &[0x01, 0x02]);
+        assert_eq!(bytes.remaining_len(), 1);
+    }
+}
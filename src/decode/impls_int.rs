@@ -1,4 +1,5 @@
 use core::mem;
+use crate::std::*;
 use crate::{
     JResult, BufRead,
     ByteDecode, BorrowByteDecode,
@@ -6,6 +7,103 @@ use crate::{
     get_byteorder,
     ErrorKind, make_error,
 };
+use super::impls_bytes::find_subsequence;
+
+
+/// Converts a sign and magnitude into the target integer type, returns `None` if it does not fit.
+#[inline]
+fn from_sign_magnitude<T: TryFrom<u128> + TryFrom<i128>>(negative: bool, magnitude: u128) -> Option<T> {
+    if !negative || magnitude == 0 {
+        return T::try_from(magnitude).ok();
+    }
+
+    let value = match magnitude.cmp(&(i128::MIN.unsigned_abs())) {
+        cmp::Ordering::Less => -(magnitude as i128),
+        cmp::Ordering::Equal => i128::MIN,
+        cmp::Ordering::Greater => return None,
+    };
+
+    T::try_from(value).ok()
+}
+
+
+/// Parses packed BCD digits, two digits per byte with the high nibble first.
+/// 
+/// The last nibble may be a sign nibble: `0xC`/`0xF` positive, `0xD` negative.
+#[inline]
+fn parse_bcd(data: &[u8], position: usize) -> JResult<(bool, u128)> {
+    let mut negative = false;
+    let mut magnitude: u128 = 0;
+    let nibble_count = data.len() * 2;
+
+    for (index, nibble) in data.iter().flat_map(|v| [v >> 4, v & 0x0f]).enumerate() {
+        match nibble {
+            0x00..=0x09 => {
+                magnitude = match magnitude.checked_mul(10).and_then(|v| v.checked_add(nibble.into())) {
+                    Some(v) => v,
+                    None => return Err(make_error(position, ErrorKind::InvalidValue(format!("{data:02x?}")))),
+                };
+            },
+            0x0c | 0x0f if index + 1 == nibble_count => {},
+            0x0d if index + 1 == nibble_count => negative = true,
+            _ => return Err(make_error(position, ErrorKind::InvalidDigit(data[index / 2]))),
+        }
+    }
+
+    Ok((negative, magnitude))
+}
+
+
+/// Parses ASCII digits of the given radix, leading and trailing spaces (or NUL bytes) are padding.
+/// 
+/// Decimal digits can be prefixed with a `+`/`-` sign.
+#[inline]
+fn parse_ascii(data: &[u8], radix: u32, position: usize) -> JResult<(bool, u128)> {
+    let is_padding = |v: &u8| *v == b' ' || *v == 0x00;
+    let start = data.iter().position(|v| !is_padding(v)).unwrap_or(data.len());
+    let end = data.iter().rposition(|v| !is_padding(v)).map_or(start, |v| v + 1);
+    let mut digits = &data[start..end];
+    let mut negative = false;
+
+    if radix == 10 {
+        if let Some((sign @ (b'+' | b'-'), value)) = digits.split_first() {
+            negative = *sign == b'-';
+            digits = value;
+        }
+    }
+
+    if digits.is_empty() {
+        return Err(make_error(position, ErrorKind::InvalidValue(String::from_utf8_lossy(data).to_string())));
+    }
+
+    let mut magnitude: u128 = 0;
+
+    for digit in digits {
+        let value = match (*digit as char).to_digit(radix) {
+            Some(v) => v,
+            None => return Err(make_error(position, ErrorKind::InvalidDigit(*digit))),
+        };
+
+        magnitude = match magnitude.checked_mul(radix.into()).and_then(|v| v.checked_add(value.into())) {
+            Some(v) => v,
+            None => return Err(make_error(position, ErrorKind::InvalidValue(String::from_utf8_lossy(data).to_string()))),
+        };
+    }
+
+    Ok((negative, magnitude))
+}
+
+
+/// Reads the ASCII digits, the width follows the string modifiers (`length`, `byte_count`, `linend`, `remaining`),
+/// otherwise `width` bytes are read.
+#[inline]
+fn take_ascii<'a, I: BufRead>(input: &'a I, cattr: Option<&ContainerAttrModifiers>, fattr: &FieldAttrModifiers, width: usize) -> JResult<&'a [u8]> {
+    if fattr.length.is_some() || fattr.byte_count.is_some() || fattr.linend || fattr.linend_value.is_some() || fattr.remaining {
+        return find_subsequence(input, cattr, Some(fattr));
+    }
+
+    input.take_bytes(width)
+}
 
 
 macro_rules! impls_int_decode {
@@ -19,7 +117,39 @@ macro_rules! impls_int_decode {
                 let mut value;
                 let byteorder = get_byteorder(cattr, fattr);
                 let length = if let Some(fr) = fattr { fr.length } else { None };
-                
+
+                if let Some(fr) = fattr {
+                    let parts = if fr.bcd {
+                        let data = input.take_bytes(length.unwrap_or(mem::size_of::<$type>()))?;
+                        Some(parse_bcd(data, input.get_position())?)
+                    }
+                    else if fr.ascii_dec {
+                        let width = $type::MAX.to_string().len() + (($type::MIN as i128) < 0) as usize;
+                        let data = take_ascii(input, cattr, fr, width)?;
+                        Some(parse_ascii(data, 10, input.get_position())?)
+                    }
+                    else if fr.ascii_hex {
+                        let data = take_ascii(input, cattr, fr, mem::size_of::<$type>() * 2)?;
+                        let (_, magnitude) = parse_ascii(data, 16, input.get_position())?;
+
+                        // Hex digits carry the two's complement bits of the integer type.
+                        if magnitude.checked_shr($type::BITS).unwrap_or(0) != 0 {
+                            return Err(make_error(input.get_position(), ErrorKind::InvalidValue(String::from_utf8_lossy(data).to_string())));
+                        }
+
+                        return Ok(magnitude as $type);
+                    }
+                    else {
+                        None
+                    };
+
+                    if let Some((negative, magnitude)) = parts {
+                        return match from_sign_magnitude(negative, magnitude) {
+                            Some(value) => Ok(value),
+                            None => Err(make_error(input.get_position(), ErrorKind::InvalidValue(format!("{}{magnitude}", if negative { "-" } else { "" })))),
+                        };
+                    }
+                }
 
                 if let Some(length) = length {
                     if mem::size_of::<$type>().checked_sub(length).is_none() {
@@ -84,9 +214,11 @@ impls_int_decode!();
 
 #[cfg(test)]
 mod tests {
+    use crate::std::*;
     use crate::{
         Bytes, BufRead, ByteDecode, ByteOrder,
         ContainerAttrModifiers, FieldAttrModifiers,
+        ErrorKind, make_error,
     };
 
     #[test]
@@ -157,4 +289,99 @@ mod tests {
         assert_eq!(u32::decode_inner(&bytes, None, Some(&fattr)).is_err(), true);
         assert_eq!(bytes.remaining_len(), 5);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_decode_int_bcd() {
+        let fattr = FieldAttrModifiers {
+            bcd: true,
+            ..Default::default()
+        };
+        let bytes = Bytes::new([0x12, 0x34]);
+        assert_eq!(u16::decode_inner(&bytes, None, Some(&fattr)).unwrap(), 1234);
+        assert_eq!(bytes.remaining_len(), 0);
+
+        // test length
+        let fattr = FieldAttrModifiers {
+            bcd: true,
+            length: Some(3),
+            ..Default::default()
+        };
+        let bytes = Bytes::new([0x00, 0x12, 0x34]);
+        assert_eq!(u32::decode_inner(&bytes, None, Some(&fattr)).unwrap(), 1234);
+        assert_eq!(bytes.remaining_len(), 0);
+
+        // test sign nibble
+        let bytes = Bytes::new([0x00, 0x12, 0x3d]);
+        assert_eq!(i32::decode_inner(&bytes, None, Some(&fattr)).unwrap(), -123);
+        let bytes = Bytes::new([0x00, 0x12, 0x3c]);
+        assert_eq!(i32::decode_inner(&bytes, None, Some(&fattr)).unwrap(), 123);
+        let bytes = Bytes::new([0x00, 0x12, 0x3d]);
+        assert_eq!(u32::decode_inner(&bytes, None, Some(&fattr)).is_err(), true);
+
+        // test invalid digit
+        let bytes = Bytes::new([0x00, 0x1a, 0x34]);
+        assert_eq!(u32::decode_inner(&bytes, None, Some(&fattr)), Err(make_error(3, ErrorKind::InvalidDigit(0x1a))));
+
+        // test overflow
+        let bytes = Bytes::new([0x02, 0x56]);
+        let fattr = FieldAttrModifiers {
+            bcd: true,
+            length: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(u8::decode_inner(&bytes, None, Some(&fattr)), Err(make_error(2, ErrorKind::InvalidValue("256".to_string()))));
+    }
+
+    #[test]
+    fn test_decode_int_ascii() {
+        let fattr = FieldAttrModifiers {
+            ascii_dec: true,
+            length: Some(6),
+            ..Default::default()
+        };
+        let bytes = Bytes::new(b"001234");
+        assert_eq!(u32::decode_inner(&bytes, None, Some(&fattr)).unwrap(), 1234);
+        assert_eq!(bytes.remaining_len(), 0);
+        let bytes = Bytes::new(b"  1234");
+        assert_eq!(u32::decode_inner(&bytes, None, Some(&fattr)).unwrap(), 1234);
+        let bytes = Bytes::new(b"-01234");
+        assert_eq!(i32::decode_inner(&bytes, None, Some(&fattr)).unwrap(), -1234);
+        let bytes = Bytes::new(b"-01234");
+        assert_eq!(u32::decode_inner(&bytes, None, Some(&fattr)).is_err(), true);
+        let bytes = Bytes::new(b"0012a4");
+        assert_eq!(u32::decode_inner(&bytes, None, Some(&fattr)), Err(make_error(6, ErrorKind::InvalidDigit(b'a'))));
+        let bytes = Bytes::new(b"      ");
+        assert_eq!(u32::decode_inner(&bytes, None, Some(&fattr)).is_err(), true);
+
+        // test default width
+        let fattr = FieldAttrModifiers {
+            ascii_dec: true,
+            ..Default::default()
+        };
+        let bytes = Bytes::new(b"00255");
+        assert_eq!(u16::decode_inner(&bytes, None, Some(&fattr)).unwrap(), 255);
+        assert_eq!(bytes.remaining_len(), 0);
+
+        // test linend
+        let fattr = FieldAttrModifiers {
+            ascii_dec: true,
+            linend_value: Some(b"\r\n"),
+            ..Default::default()
+        };
+        let bytes = Bytes::new(b"255\r\n");
+        assert_eq!(u8::decode_inner(&bytes, None, Some(&fattr)).unwrap(), 255);
+        assert_eq!(bytes.remaining_len(), 0);
+
+        // test hex
+        let fattr = FieldAttrModifiers {
+            ascii_hex: true,
+            ..Default::default()
+        };
+        let bytes = Bytes::new(b"1aF0");
+        assert_eq!(u16::decode_inner(&bytes, None, Some(&fattr)).unwrap(), 0x1af0);
+        let bytes = Bytes::new(b"FFFF");
+        assert_eq!(i16::decode_inner(&bytes, None, Some(&fattr)).unwrap(), -1);
+        let bytes = Bytes::new(b"1G");
+        assert_eq!(u8::decode_inner(&bytes, None, Some(&fattr)), Err(make_error(2, ErrorKind::InvalidDigit(b'G'))));
+    }
+}
@@ -1,4 +1,4 @@
-use core::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
 use crate::{
     JResult, BufRead,
     ByteDecode, BorrowByteDecode,
@@ -85,10 +85,94 @@ impl<'de> BorrowByteDecode<'de> for IpAddr {
 }
 
 
+impl ByteDecode for SocketAddrV4 {
+    #[inline]
+    fn decode_inner<T: BufRead>(input: &T, cattr: Option<&ContainerAttrModifiers>, fattr: Option<&FieldAttrModifiers>) -> JResult<Self>
+    where 
+        Self: Sized
+    {
+        let addr = ByteDecode::decode_inner(input, cattr, fattr)?;
+        let port = input.take_byteorder_u16(get_byteorder(cattr, fattr))?;
+
+        Ok(Self::new(addr, port))
+    }
+}
+
+
+impl<'de> BorrowByteDecode<'de> for SocketAddrV4 {
+    #[inline]
+    fn decode_inner<T: BufRead>(input: &'de T, cattr: Option<&ContainerAttrModifiers>, fattr: Option<&FieldAttrModifiers>) -> JResult<Self>
+    where 
+        Self: Sized
+    {
+        ByteDecode::decode_inner(input, cattr, fattr)
+    }
+}
+
+
+impl ByteDecode for SocketAddrV6 {
+    #[inline]
+    fn decode_inner<T: BufRead>(input: &T, cattr: Option<&ContainerAttrModifiers>, fattr: Option<&FieldAttrModifiers>) -> JResult<Self>
+    where 
+        Self: Sized
+    {
+        let addr = ByteDecode::decode_inner(input, cattr, fattr)?;
+        let port = input.take_byteorder_u16(get_byteorder(cattr, fattr))?;
+
+        // The flowinfo and scope_id are not on the wire.
+        Ok(Self::new(addr, port, 0, 0))
+    }
+}
+
+
+impl<'de> BorrowByteDecode<'de> for SocketAddrV6 {
+    #[inline]
+    fn decode_inner<T: BufRead>(input: &'de T, cattr: Option<&ContainerAttrModifiers>, fattr: Option<&FieldAttrModifiers>) -> JResult<Self>
+    where 
+        Self: Sized
+    {
+        ByteDecode::decode_inner(input, cattr, fattr)
+    }
+}
+
+
+impl ByteDecode for SocketAddr {
+    #[inline]
+    fn decode_inner<T: BufRead>(input: &T, cattr: Option<&ContainerAttrModifiers>, fattr: Option<&FieldAttrModifiers>) -> JResult<Self>
+    where 
+        Self: Sized
+    {
+        if let Some(fattr_var) = fattr {
+            if let Some(length) = fattr_var.length {
+                if length == 18 {    
+                    return Ok(Self::V6(ByteDecode::decode_inner(input, cattr, fattr)?));
+                }
+                else if length == 6 {
+                    return Ok(Self::V4(ByteDecode::decode_inner(input, cattr, fattr)?));
+                }    
+            }
+        }
+
+        Err(make_error(input.get_position(), ErrorKind::Fail))
+    }
+}
+
+
+impl<'de> BorrowByteDecode<'de> for SocketAddr {
+    #[inline]
+    fn decode_inner<T: BufRead>(input: &'de T, cattr: Option<&ContainerAttrModifiers>, fattr: Option<&FieldAttrModifiers>) -> JResult<Self>
+    where 
+        Self: Sized
+    {
+        ByteDecode::decode_inner(input, cattr, fattr)
+    }
+}
+
+
 #[cfg(test)]
 mod tests {
     use core::{
-        net::{IpAddr, Ipv4Addr, Ipv6Addr},
+        net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
         str::FromStr
     };
     #[allow(unused_imports)]
@@ -185,4 +269,42 @@ mod tests {
         assert_eq!(IpAddr::decode_inner(&bytes, None, None).is_err(), true);
         assert_eq!(bytes.remaining_len(), 4);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_decode_socketaddr() {
+        // test ipv4
+        let bytes = Bytes::new([192, 168, 1, 100, 0x1f, 0x90]);
+        let addr = SocketAddrV4::from_str("192.168.1.100:8080").unwrap();
+        assert_eq!(SocketAddrV4::decode(&bytes).unwrap(), addr);
+        assert_eq!(bytes.remaining_len(), 0);
+
+        // test ipv4 little-endian
+        let fattr = FieldAttrModifiers {
+            length: Some(6),
+            byteorder: Some(ByteOrder::Le),
+            ..Default::default()
+        };
+        let bytes = Bytes::new([100, 1, 168, 192, 0x90, 0x1f]);
+        assert_eq!(SocketAddr::decode_inner(&bytes, None, Some(&fattr)).unwrap(), SocketAddr::V4(addr));
+        assert_eq!(bytes.remaining_len(), 0);
+
+        // test ipv6
+        let bytes = Bytes::new([250, 128, 0, 0, 0, 0, 0, 0, 14, 116, 171, 255, 254, 147, 86, 240, 0x00, 0x35]);
+        let addr = SocketAddrV6::from_str("[fa80::e74:abff:fe93:56f0]:53").unwrap();
+        assert_eq!(SocketAddrV6::decode(&bytes).unwrap(), addr);
+        assert_eq!(bytes.remaining_len(), 0);
+
+        let fattr = FieldAttrModifiers {
+            length: Some(18),
+            ..Default::default()
+        };
+        let bytes = Bytes::new([250, 128, 0, 0, 0, 0, 0, 0, 14, 116, 171, 255, 254, 147, 86, 240, 0x00, 0x35]);
+        assert_eq!(SocketAddr::decode_inner(&bytes, None, Some(&fattr)).unwrap(), SocketAddr::V6(addr));
+        assert_eq!(bytes.remaining_len(), 0);
+
+        // test error
+        let bytes = Bytes::new([192, 168, 1, 100, 0x1f]);
+        assert_eq!(SocketAddrV4::decode(&bytes).is_err(), true);
+        assert_eq!(SocketAddr::decode(&bytes).is_err(), true);
+    }
+}
@@ -0,0 +1,75 @@
+use crate::std::*;
+use crate::{
+    types::IpPrefix,
+    JResult, BufRead,
+    ContainerAttrModifiers, FieldAttrModifiers,
+    ErrorKind, make_error,
+};
+
+
+impl crate::ByteDecode for IpPrefix {
+    #[inline]
+    fn decode_inner<T: BufRead>(input: &T, _cattr: Option<&ContainerAttrModifiers>, fattr: Option<&FieldAttrModifiers>) -> JResult<Self>
+    where 
+        Self: Sized
+    {
+        let is_ipv6 = fattr.and_then(|v| v.length) == Some(16);
+        let position = input.get_position();
+        let len = input.take_u8()?;
+        let data = input.take_bytes((len as usize).div_ceil(8))?;
+
+        match IpPrefix::from_octets(data, len, is_ipv6) {
+            Some(v) => Ok(v),
+            None => Err(make_error(position, ErrorKind::InvalidValue(format!("prefix length {len}")))),
+        }
+    }
+}
+
+
+impl<'de> crate::BorrowByteDecode<'de> for IpPrefix {
+    #[inline]
+    fn decode_inner<T: BufRead>(input: &'de T, cattr: Option<&ContainerAttrModifiers>, fattr: Option<&FieldAttrModifiers>) -> JResult<Self>
+    where 
+        Self: Sized
+    {
+        crate::ByteDecode::decode_inner(input, cattr, fattr)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use core::str::FromStr;
+    use crate::{BufRead, Bytes, ByteDecode};
+    use super::*;
+
+    #[test]
+    fn test_decode_ipprefix() {
+        // test ipv4
+        let bytes = Bytes::new([15, 10, 0, 0xff]);
+        assert_eq!(IpPrefix::decode(&bytes).unwrap(), IpPrefix::from_str("10.0.0.0/15").unwrap());
+        assert_eq!(bytes.remaining_len(), 1);
+
+        // test default route
+        let bytes = Bytes::new([0]);
+        assert_eq!(IpPrefix::decode(&bytes).unwrap(), IpPrefix::from_str("0.0.0.0/0").unwrap());
+        assert_eq!(bytes.remaining_len(), 0);
+
+        // test ipv6
+        let bytes = Bytes::new([32, 0x20, 0x01, 0x0d, 0xb8]);
+        let fattr = FieldAttrModifiers {
+            length: Some(16),
+            ..Default::default()
+        };
+        assert_eq!(IpPrefix::decode_inner(&bytes, None, Some(&fattr)).unwrap(), IpPrefix::from_str("2001:db8::/32").unwrap());
+        assert_eq!(bytes.remaining_len(), 0);
+
+        // test prefix length error
+        let bytes = Bytes::new([33, 10, 0, 0, 0, 0]);
+        assert_eq!(IpPrefix::decode(&bytes).is_err(), true);
+
+        // test insufficient bytes error
+        let bytes = Bytes::new([24, 10, 0]);
+        assert_eq!(IpPrefix::decode(&bytes).is_err(), true);
+    }
+}
@@ -0,0 +1,79 @@
+use core::cell::OnceCell;
+use crate::{
+    JResult, BufRead,
+    ByteDecode, BorrowByteDecode,
+    ContainerAttrModifiers, FieldAttrModifiers,
+    get_byteorder,
+    types::Lazy,
+};
+
+
+impl<'de, T: ByteDecode> BorrowByteDecode<'de> for Lazy<'de, T> {
+    #[inline]
+    fn decode_inner<I: BufRead>(input: &'de I, cattr: Option<&ContainerAttrModifiers>,
+                                    fattr: Option<&FieldAttrModifiers>) -> JResult<Self>
+    where
+        Self: Sized
+    {
+        let byteorder = get_byteorder(cattr, fattr);
+        let byteorder_subtree = fattr.and_then(|v| v.byteorder_from).or(cattr.and_then(|v| v.byteorder_subtree));
+        let position = input.get_position();
+        let mut prefix_len = 0;
+        let mut value = OnceCell::new();
+
+        match fattr {
+            Some(fr) if fr.length.is_some() => { input.take_bytes(fr.length.unwrap_or_default())?; },
+            Some(fr) if fr.byte_count.is_some() => {
+                prefix_len = fr.byte_count.unwrap_or_default();
+                let length = input.take_byteorder_uint(prefix_len, byteorder)?;
+                input.take_bytes(length as usize)?;
+            },
+            Some(fr) if fr.remaining => { input.take_bytes(input.remaining_len())?; },
+            // The end of the value is unknown without decoding it.
+            _ => value = OnceCell::from(T::decode_inner(input, cattr, fattr)?),
+        }
+
+        Ok(Self {
+            raw: &input.get_data()[position..input.get_position()],
+            prefix_len,
+            position,
+            byteorder,
+            byteorder_subtree,
+            value,
+            is_modified: false,
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Bytes, BorrowByteDecode, FieldAttrModifiers,
+        types::Lazy,
+    };
+
+    #[test]
+    fn test_decode_lazy() {
+        let input = Bytes::new(b"\x00\x01\x02");
+        let fattr = FieldAttrModifiers { length: Some(2), ..Default::default() };
+        let value: Lazy<u16> = BorrowByteDecode::decode_inner(&input, None, Some(&fattr)).unwrap();
+        assert_eq!(value.raw(), b"\x00\x01");
+        assert_eq!(value.is_materialized(), false);
+        assert_eq!(*value.get().unwrap(), 1);
+        assert_eq!(value.is_materialized(), true);
+
+        // The error position is in the input.
+        let input = Bytes::new(b"\x00\x01\x02");
+        let fattr = FieldAttrModifiers { byte_count: Some(1), ..Default::default() };
+        let value: Lazy<u16> = BorrowByteDecode::decode_inner(&input, None, Some(&fattr)).unwrap();
+        assert_eq!(value.raw(), b"");
+        assert_eq!(value.get().unwrap_err().position, 1);
+
+        // The value is decoded without the range modifiers.
+        let input = Bytes::new(b"\x00\x01\x02");
+        let value: Lazy<u16> = BorrowByteDecode::decode_inner(&input, None, None).unwrap();
+        assert_eq!(value.is_materialized(), true);
+        assert_eq!(*value.get().unwrap(), 1);
+    }
+}
@@ -4,10 +4,23 @@ use crate::{
     ByteDecode, BorrowByteDecode,
     ContainerAttrModifiers, FieldAttrModifiers,
     ErrorKind, make_error,
+    Encoding,
 };
 use super::impls_bytes::find_subsequence;
 
 
+/// Transcodes the bytes by the `encoding` modifier, defaults to UTF-8.
+#[inline]
+fn decode_string<I: BufRead>(input: &I, fattr: Option<&FieldAttrModifiers>, value: &[u8]) -> JResult<String> {
+    let encoding = if let Some(fr) = fattr { fr.encoding.unwrap_or_default() } else { Encoding::Utf8 };
+
+    match encoding.decode(value) {
+        Some(v) => Ok(v),
+        None => Err(make_error(input.get_position(), ErrorKind::Fail))
+    }
+}
+
+
 impl ByteDecode for String {
     #[inline]
     fn decode_inner<I: BufRead>(input: &I, cattr: Option<&ContainerAttrModifiers>,
@@ -15,12 +28,7 @@ impl ByteDecode for String {
     where 
         Self: Sized
     {
-        let value = find_subsequence(input, cattr, fattr)?;
-
-        match str::from_utf8(value) {
-            Ok(v) => Ok(v.to_string()),
-            Err(_e) => Err(make_error(input.get_position(), ErrorKind::Fail))
-        }
+        decode_string(input, fattr, find_subsequence(input, cattr, fattr)?)
     }
 }
 
@@ -31,12 +39,7 @@ impl<'de> BorrowByteDecode<'de> for String {
     where 
         Self: Sized
     {
-        let value = find_subsequence(input, cattr, fattr)?;
-
-        match str::from_utf8(value) {
-            Ok(v) => Ok(v.to_string()),
-            Err(_e) => Err(make_error(input.get_position(), ErrorKind::Fail))
-        }
+        decode_string(input, fattr, find_subsequence(input, cattr, fattr)?)
     }
 }
 
@@ -49,6 +52,13 @@ impl<'de> BorrowByteDecode<'de> for &'de str {
     {
         let value = find_subsequence(input, cattr, fattr)?;
 
+        // A borrowed string can't be transcoded.
+        if let Some(fr) = fattr {
+            if fr.encoding.is_some_and(|v| v != Encoding::Utf8) {
+                return Err(make_error(input.get_position(), ErrorKind::Fail));
+            }
+        }
+
         match str::from_utf8(value) {
             Ok(v) => Ok(v),
             Err(_e) => Err(make_error(input.get_position(), ErrorKind::Fail))
@@ -62,7 +72,7 @@ mod tests {
     use crate::std::*;
     use crate::{
         Bytes, BufRead, BorrowByteDecode,
-        FieldAttrModifiers,
+        FieldAttrModifiers, Encoding,
     };
 
     #[test]
@@ -162,4 +172,64 @@ mod tests {
         assert_eq!(<&str>::decode_inner(&bytes, None, Some(&fattr)).unwrap(), "abc");
         assert_eq!(bytes.remaining_len(), 0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_decode_string_encoding() {
+        // test utf-16 example
+        let bytes = Bytes::new(b"\x04a\x00b\x00");
+        let fattr = FieldAttrModifiers {
+            encoding: Some(Encoding::Utf16Le),
+            ..Default::default()
+        };
+        assert_eq!(String::decode_inner(&bytes, None, Some(&fattr)).unwrap(), "ab");
+        assert_eq!(bytes.remaining_len(), 0);
+
+        // test code units example
+        let bytes = Bytes::new(b"\x02\x00a\x00b");
+        let fattr = FieldAttrModifiers {
+            encoding: Some(Encoding::Utf16Be),
+            code_units: true,
+            ..Default::default()
+        };
+        assert_eq!(String::decode_inner(&bytes, None, Some(&fattr)).unwrap(), "ab");
+        assert_eq!(bytes.remaining_len(), 0);
+
+        // test utf-16 cstr example
+        let bytes = Bytes::new(b"a\x00\x00\x01\x00\x00b");
+        let fattr = FieldAttrModifiers {
+            encoding: Some(Encoding::Utf16Le),
+            cstr: true,
+            ..Default::default()
+        };
+        assert_eq!(String::decode_inner(&bytes, None, Some(&fattr)).unwrap(), "a\u{100}");
+        assert_eq!(bytes.remaining_len(), 1);
+
+        // test utf-16 pad example
+        let bytes = Bytes::new(b"a\x00\x00\x00");
+        let fattr = FieldAttrModifiers {
+            encoding: Some(Encoding::Utf16Le),
+            length: Some(2),
+            code_units: true,
+            pad: Some(0x00),
+            ..Default::default()
+        };
+        assert_eq!(String::decode_inner(&bytes, None, Some(&fattr)).unwrap(), "a");
+        assert_eq!(bytes.remaining_len(), 0);
+
+        // test latin1 example
+        let bytes = Bytes::new(b"\x04caf\xe9");
+        let fattr = FieldAttrModifiers {
+            encoding: Some(Encoding::Latin1),
+            ..Default::default()
+        };
+        assert_eq!(String::decode_inner(&bytes, None, Some(&fattr)).unwrap(), "café");
+
+        // test borrowed str error example
+        let bytes = Bytes::new(b"\x02a\x00");
+        let fattr = FieldAttrModifiers {
+            encoding: Some(Encoding::Utf16Le),
+            ..Default::default()
+        };
+        assert_eq!(<&str>::decode_inner(&bytes, None, Some(&fattr)).is_err(), true);
+    }
+}
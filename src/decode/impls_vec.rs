@@ -27,6 +27,15 @@ impl<T: ByteDecode> ByteDecode for Vec<T> {
             return Ok(value_list);
         }
 
+        // remaining: decodes elements until the input is exhausted.
+        if fattr.is_some_and(|fr| fr.remaining) {
+            while input.remaining_len() > 0 {
+                value_list.push(T::decode_inner(input, cattr, fattr)?);
+            }
+
+            return Ok(value_list);
+        }
+
         let (count, try_count) = get_count_and_try_count(input, cattr, fattr)?;
 
         if let Some(try_count) = try_count {
@@ -65,6 +74,15 @@ impl<'de, T: BorrowByteDecode<'de>> BorrowByteDecode<'de> for Vec<T> {
 
             return Ok(value_list);
         }   
+
+        // remaining: decodes elements until the input is exhausted.
+        if fattr.is_some_and(|fr| fr.remaining) {
+            while input.remaining_len() > 0 {
+                value_list.push(T::decode_inner(input, cattr, fattr)?);
+            }
+
+            return Ok(value_list);
+        }
         
         let (count, try_count) = get_count_and_try_count(input, cattr, fattr)?;
         
@@ -135,6 +153,19 @@ mod tests {
         assert_eq!(Vec::<u16>::decode_inner(&bytes, None, Some(&fattr)).is_err(), true);
         assert_eq!(bytes.remaining_len(), 0);
 
+        // test `remaining` example
+        let bytes = Bytes::new([0x00, 0x01, 0x00, 0x02]);
+        let fattr = FieldAttrModifiers {
+            remaining: true,
+            ..Default::default()
+        };
+        assert_eq!(Vec::<u16>::decode_inner(&bytes, None, Some(&fattr)).unwrap(), vec![0x0001, 0x0002]);
+        assert_eq!(bytes.remaining_len(), 0);
+
+        // test `remaining` error example
+        let bytes = Bytes::new([0x00, 0x01, 0x00]);
+        assert_eq!(Vec::<u16>::decode_inner(&bytes, None, Some(&fattr)).is_err(), true);
+
         // test `try_count` example
         let bytes = Bytes::new([0x00, 0x01, 0x00, 0x02]);
         let fattr = FieldAttrModifiers {
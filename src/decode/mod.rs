@@ -17,7 +17,9 @@ mod impls_option;
 mod impls_ipaddress;
 mod impls_macaddress;
 mod impls_netaddress;
+mod impls_ipprefix;
 mod impls_mark;
+mod impls_lazy;
 
 use crate::{
     JResult,
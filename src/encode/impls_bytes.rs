@@ -2,7 +2,8 @@ use crate::{
     JResult, BufWrite,
     BorrowByteEncode,
     ContainerAttrModifiers, FieldAttrModifiers,
-    get_byteorder,
+    get_byteorder, get_code_unit_size,
+    ErrorKind, make_error,
 };
 
 
@@ -11,8 +12,17 @@ pub(super) fn encode_inner<B: BufWrite>(buffer: &mut B, cattr: Option<&Container
                                             fattr: Option<&FieldAttrModifiers>, data: &[u8]) -> JResult<usize> {
     let mut r_nbytes = 0;
     let mut linend_value = None;
+    let mut pad_count = 0;
+    let mut pad_value = 0x00;
+    let mut nul_value: Option<&[u8]> = None;
 
     if let Some(fr) = fattr {
+        let unit = get_code_unit_size(fattr);
+        // length/byte_count/the default prefix counts code units instead of bytes.
+        let scale = if fr.code_units { unit } else { 1 };
+        let nul: &[u8] = if unit == 2 { b"\x00\x00" } else { b"\x00" };
+        let nul_len = if fr.cstr { unit } else { 0 };
+
         if let Some(key) = fr.key {
             r_nbytes += buffer.push(key)?;
         }
@@ -21,10 +31,22 @@ pub(super) fn encode_inner<B: BufWrite>(buffer: &mut B, cattr: Option<&Container
             r_nbytes += buffer.push(split)?;
         }
 
-        if let Some(_length) = fr.length {
+        if let Some(length) = fr.length {
+            if fr.cstr || fr.pad.is_some() {
+                // A fixed-width C string keeps at least one NUL code unit.
+                pad_count = match (length * scale).checked_sub(data.len() + nul_len) {
+                    Some(v) => v + nul_len,
+                    None => return Err(make_error(buffer.get_position(), ErrorKind::InvalidByteLength)),
+                };
+                pad_value = fr.pad.unwrap_or(0x00);
+                nul_value = if fr.cstr { Some(nul) } else { None };
+            }
         }
         else if let Some(byte_count) = fr.byte_count {
-            r_nbytes += buffer.push_byteorder_uint(data.len() as u64, byte_count, get_byteorder(cattr, fattr))?;
+            r_nbytes += buffer.push_byteorder_uint((data.len() / scale) as u64, byte_count, get_byteorder(cattr, fattr))?;
+        }
+        else if fr.cstr {
+            linend_value = Some(nul);
         }
         else if fr.linend {
             linend_value = Some(b"\r\n".as_ref());
@@ -36,7 +58,7 @@ pub(super) fn encode_inner<B: BufWrite>(buffer: &mut B, cattr: Option<&Container
 
         }
         else {
-            r_nbytes += buffer.push_u8(data.len() as u8)?;
+            r_nbytes += buffer.push_u8((data.len() / scale) as u8)?;
         }
     }
     else {
@@ -45,6 +67,16 @@ pub(super) fn encode_inner<B: BufWrite>(buffer: &mut B, cattr: Option<&Container
 
     r_nbytes += buffer.push(data)?;
 
+    // A fixed-width C string ends at the NUL code unit, which isn't replaced by the padding.
+    if let Some(nul) = nul_value {
+        r_nbytes += buffer.push(nul)?;
+        pad_count -= nul.len();
+    }
+
+    for _i in 0..pad_count {
+        r_nbytes += buffer.push_u8(pad_value)?;
+    }
+
     if let Some(linend_value) = linend_value {
         r_nbytes += buffer.push(linend_value)?;
     }
@@ -119,4 +151,50 @@ mod tests {
         assert_eq!(value.encode_inner(&mut buffer, None, Some(&fattr)).unwrap(), 9);
         assert_eq!(*buffer, vec![0xff, 0xfe, 0xfa, 0x01, 0x02, 0x03, 0x04, 0x05, 0xff]);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_encode_bytes_pad() {
+        // test pad example
+        let fattr = FieldAttrModifiers {
+            length: Some(4),
+            pad: Some(0x20),
+            ..Default::default()
+        };
+        let mut buffer = Buffer::new();
+        let value = b"\x01\x02".as_ref();
+        assert_eq!(value.encode_inner(&mut buffer, None, Some(&fattr)).unwrap(), 4);
+        assert_eq!(*buffer, vec![0x01, 0x02, 0x20, 0x20]);
+
+        // test pad error example
+        let mut buffer = Buffer::new();
+        let value = b"\x01\x02\x03\x04\x05".as_ref();
+        assert_eq!(value.encode_inner(&mut buffer, None, Some(&fattr)).is_err(), true);
+        assert_eq!(buffer.is_empty(), true);
+
+        // test cstr example
+        let fattr = FieldAttrModifiers {
+            cstr: true,
+            ..Default::default()
+        };
+        let mut buffer = Buffer::new();
+        let value = b"\x01\x02".as_ref();
+        assert_eq!(value.encode_inner(&mut buffer, None, Some(&fattr)).unwrap(), 3);
+        assert_eq!(*buffer, vec![0x01, 0x02, 0x00]);
+
+        // test fixed-width cstr example
+        let fattr = FieldAttrModifiers {
+            length: Some(4),
+            cstr: true,
+            ..Default::default()
+        };
+        let mut buffer = Buffer::new();
+        let value = b"\x01\x02".as_ref();
+        assert_eq!(value.encode_inner(&mut buffer, None, Some(&fattr)).unwrap(), 4);
+        assert_eq!(*buffer, vec![0x01, 0x02, 0x00, 0x00]);
+
+        // test fixed-width cstr error example, there is no room for the NUL byte.
+        let mut buffer = Buffer::new();
+        let value = b"\x01\x02\x03\x04".as_ref();
+        assert_eq!(value.encode_inner(&mut buffer, None, Some(&fattr)).is_err(), true);
+    }
+}
@@ -1,4 +1,5 @@
 use core::mem;
+use crate::std::*;
 use crate::{
     JResult, BufWrite,
     ByteEncode, BorrowByteEncode,
@@ -6,6 +7,54 @@ use crate::{
     get_byteorder,
     ErrorKind, make_error,
 };
+use super::impls_bytes::encode_inner;
+
+
+/// Writes packed BCD digits into `nbytes` bytes, left-padded with zero nibbles.
+/// 
+/// A negative value is written with a trailing `0xD` sign nibble.
+#[inline]
+fn push_bcd<B: BufWrite>(buffer: &mut B, negative: bool, magnitude: u128, nbytes: usize) -> JResult<usize> {
+    let mut nibbles: Vec<u8> = format!("{magnitude}").bytes().map(|v| v - b'0').collect();
+
+    if negative {
+        nibbles.push(0x0d);
+    }
+
+    let padding = match (nbytes * 2).checked_sub(nibbles.len()) {
+        Some(v) => v,
+        None => return Err(make_error(buffer.get_position(), ErrorKind::InvalidValue(format!("{}{magnitude}", if negative { "-" } else { "" })))),
+    };
+
+    let nibbles = [vec![0; padding], nibbles].concat();
+    let data = nibbles.chunks(2).map(|v| (v[0] << 4) | v[1]).collect::<Vec<u8>>();
+
+    buffer.push(data)
+}
+
+
+/// Writes ASCII digits, when the width follows the string modifiers (`length`, `byte_count`, `linend`, `remaining`),
+/// otherwise `width` bytes are written.
+/// 
+/// The digits are left-padded with `0` after the sign to fill a fixed width.
+#[inline]
+fn push_ascii<B: BufWrite>(buffer: &mut B, cattr: Option<&ContainerAttrModifiers>, fattr: &FieldAttrModifiers, sign: &str, digits: String, width: usize) -> JResult<usize> {
+    let is_delimited = fattr.byte_count.is_some() || fattr.linend || fattr.linend_value.is_some() || fattr.remaining;
+    let width = if let Some(length) = fattr.length { length } else if is_delimited { 0 } else { width };
+    let padding = width.saturating_sub(sign.len() + digits.len());
+
+    if width != 0 && sign.len() + digits.len() > width {
+        return Err(make_error(buffer.get_position(), ErrorKind::InvalidValue(format!("{sign}{digits}"))));
+    }
+
+    let value = format!("{sign}{}{digits}", "0".repeat(padding));
+
+    if fattr.length.is_some() || is_delimited {
+        return encode_inner(buffer, cattr, Some(fattr), value.as_bytes());
+    }
+
+    buffer.push(value)
+}
 
 
 macro_rules! impls_int_encode {
@@ -17,7 +66,26 @@ macro_rules! impls_int_encode {
                 let mut value = *self;
                 let byteorder = get_byteorder(cattr, fattr);
                 let length = if let Some(fr) = fattr { fr.length } else { None };
-                
+
+                if let Some(fr) = fattr {
+                    #[allow(unused_comparisons)]
+                    let negative = value < 0;
+                    let magnitude = if negative { (value as i128).unsigned_abs() } else { value as u128 };
+
+                    if fr.bcd {
+                        return push_bcd(buffer, negative, magnitude, length.unwrap_or(mem::size_of::<$type>()));
+                    }
+                    else if fr.ascii_dec {
+                        let width = $type::MAX.to_string().len() + (($type::MIN as i128) < 0) as usize;
+                        return push_ascii(buffer, cattr, fr, if negative { "-" } else { "" }, format!("{magnitude}"), width);
+                    }
+                    else if fr.ascii_hex {
+                        // Hex digits carry the two's complement bits of the integer type.
+                        let bits = (value as u128) & (u128::MAX >> (128 - $type::BITS));
+                        return push_ascii(buffer, cattr, fr, "", format!("{bits:X}"), mem::size_of::<$type>() * 2);
+                    }
+                }
+
                 if let Some(fr) = fattr {
                     if let Some(bits) = fr.bits {
                         let mut bits = bits as $type;
@@ -152,4 +220,83 @@ mod tests {
         assert_eq!(0x000001_u32.encode_inner(&mut buffer, None, Some(&fattr)).is_err(), true);
         assert_eq!(buffer.is_empty(), true);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_encode_int_bcd() {
+        let fattr = FieldAttrModifiers {
+            bcd: true,
+            ..Default::default()
+        };
+        let mut buffer = Buffer::new();
+        assert_eq!(1234_u16.encode_inner(&mut buffer, None, Some(&fattr)).unwrap(), 2);
+        assert_eq!(*buffer, vec![0x12, 0x34]);
+
+        let fattr = FieldAttrModifiers {
+            bcd: true,
+            length: Some(3),
+            ..Default::default()
+        };
+        let mut buffer = Buffer::new();
+        assert_eq!(1234_u32.encode_inner(&mut buffer, None, Some(&fattr)).unwrap(), 3);
+        assert_eq!(*buffer, vec![0x00, 0x12, 0x34]);
+
+        let mut buffer = Buffer::new();
+        assert_eq!((-123_i32).encode_inner(&mut buffer, None, Some(&fattr)).unwrap(), 3);
+        assert_eq!(*buffer, vec![0x00, 0x12, 0x3d]);
+
+        let mut buffer = Buffer::new();
+        assert_eq!(1234567_u32.encode_inner(&mut buffer, None, Some(&fattr)).is_err(), true);
+        assert_eq!(buffer.is_empty(), true);
+    }
+
+    #[test]
+    fn test_encode_int_ascii() {
+        let fattr = FieldAttrModifiers {
+            ascii_dec: true,
+            length: Some(6),
+            ..Default::default()
+        };
+        let mut buffer = Buffer::new();
+        assert_eq!(1234_u32.encode_inner(&mut buffer, None, Some(&fattr)).unwrap(), 6);
+        assert_eq!(*buffer, b"001234");
+
+        let mut buffer = Buffer::new();
+        assert_eq!((-1234_i32).encode_inner(&mut buffer, None, Some(&fattr)).unwrap(), 6);
+        assert_eq!(*buffer, b"-01234");
+
+        let mut buffer = Buffer::new();
+        assert_eq!(1234567_u32.encode_inner(&mut buffer, None, Some(&fattr)).is_err(), true);
+
+        // test default width
+        let fattr = FieldAttrModifiers {
+            ascii_dec: true,
+            ..Default::default()
+        };
+        let mut buffer = Buffer::new();
+        assert_eq!(255_u16.encode_inner(&mut buffer, None, Some(&fattr)).unwrap(), 5);
+        assert_eq!(*buffer, b"00255");
+
+        // test linend
+        let fattr = FieldAttrModifiers {
+            ascii_dec: true,
+            linend_value: Some(b"\r\n"),
+            ..Default::default()
+        };
+        let mut buffer = Buffer::new();
+        assert_eq!(255_u8.encode_inner(&mut buffer, None, Some(&fattr)).unwrap(), 5);
+        assert_eq!(*buffer, b"255\r\n");
+
+        // test hex
+        let fattr = FieldAttrModifiers {
+            ascii_hex: true,
+            ..Default::default()
+        };
+        let mut buffer = Buffer::new();
+        assert_eq!(0x1af0_u16.encode_inner(&mut buffer, None, Some(&fattr)).unwrap(), 4);
+        assert_eq!(*buffer, b"1AF0");
+
+        let mut buffer = Buffer::new();
+        assert_eq!((-1_i16).encode_inner(&mut buffer, None, Some(&fattr)).unwrap(), 4);
+        assert_eq!(*buffer, b"FFFF");
+    }
+}
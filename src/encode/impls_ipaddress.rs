@@ -1,4 +1,4 @@
-use core::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
 use crate::{
     JResult, BufWrite,
     ContainerAttrModifiers, FieldAttrModifiers,
@@ -66,11 +66,79 @@ impl crate::BorrowByteEncode for IpAddr {
 }
 
 
+impl crate::ByteEncode for SocketAddrV4 {
+    #[inline]
+    fn encode_inner<B: BufWrite>(&self, buffer: &mut B, cattr: Option<&ContainerAttrModifiers>,
+                                                                  fattr: Option<&FieldAttrModifiers>) -> JResult<usize> {
+        let mut r_nbytes = self.ip().encode_inner(buffer, cattr, fattr)?;
+        r_nbytes += buffer.push_byteorder_u16(self.port(), get_byteorder(cattr, fattr))?;
+        Ok(r_nbytes)
+    }
+}
+
+
+impl crate::BorrowByteEncode for SocketAddrV4 {
+    #[inline]
+    fn encode_inner<B: BufWrite>(&self, buffer: &mut B, cattr: Option<&ContainerAttrModifiers>,
+                                                                  fattr: Option<&FieldAttrModifiers>) -> JResult<usize> {
+        let mut r_nbytes = self.ip().encode_inner(buffer, cattr, fattr)?;
+        r_nbytes += buffer.push_byteorder_u16(self.port(), get_byteorder(cattr, fattr))?;
+        Ok(r_nbytes)
+    }
+}
+
+
+impl crate::ByteEncode for SocketAddrV6 {
+    #[inline]
+    fn encode_inner<B: BufWrite>(&self, buffer: &mut B, cattr: Option<&ContainerAttrModifiers>,
+                                                                  fattr: Option<&FieldAttrModifiers>) -> JResult<usize> {
+        let mut r_nbytes = self.ip().encode_inner(buffer, cattr, fattr)?;
+        r_nbytes += buffer.push_byteorder_u16(self.port(), get_byteorder(cattr, fattr))?;
+        Ok(r_nbytes)
+    }
+}
+
+
+impl crate::BorrowByteEncode for SocketAddrV6 {
+    #[inline]
+    fn encode_inner<B: BufWrite>(&self, buffer: &mut B, cattr: Option<&ContainerAttrModifiers>,
+                                                                  fattr: Option<&FieldAttrModifiers>) -> JResult<usize> {
+        let mut r_nbytes = self.ip().encode_inner(buffer, cattr, fattr)?;
+        r_nbytes += buffer.push_byteorder_u16(self.port(), get_byteorder(cattr, fattr))?;
+        Ok(r_nbytes)
+    }
+}
+
+
+impl crate::ByteEncode for SocketAddr {
+    #[inline]
+    fn encode_inner<B: BufWrite>(&self, buffer: &mut B, cattr: Option<&ContainerAttrModifiers>,
+                                                                  fattr: Option<&FieldAttrModifiers>) -> JResult<usize> {
+        match self {
+            Self::V4(addr) => addr.encode_inner(buffer, cattr, fattr),
+            Self::V6(addr) => addr.encode_inner(buffer, cattr, fattr),
+        }
+    }
+}
+
+
+impl crate::BorrowByteEncode for SocketAddr {
+    #[inline]
+    fn encode_inner<B: BufWrite>(&self, buffer: &mut B, cattr: Option<&ContainerAttrModifiers>,
+                                                                  fattr: Option<&FieldAttrModifiers>) -> JResult<usize> {
+        match self {
+            Self::V4(addr) => addr.encode_inner(buffer, cattr, fattr),
+            Self::V6(addr) => addr.encode_inner(buffer, cattr, fattr),
+        }
+    }
+}
+
+
 #[cfg(test)]
 mod tests {
     use crate::std::*;
     use core::{
-        net::{IpAddr, Ipv4Addr, Ipv6Addr},
+        net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4},
         str::FromStr
     };
     #[allow(unused_imports)]
@@ -142,4 +210,27 @@ mod tests {
         assert_eq!(addr.encode_inner(&mut buffer, None, Some(&fattr)).unwrap(), 16);
         assert_eq!(*buffer, vec![240, 86, 147, 254, 255, 171, 116, 14, 0, 0, 0, 0, 0, 0, 128, 250]);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_encode_socketaddr() {
+        let mut buffer = Buffer::new();
+        let addr = SocketAddrV4::from_str("192.168.1.100:8080").unwrap();
+        assert_eq!(addr.encode(&mut buffer).unwrap(), 6);
+        assert_eq!(*buffer, vec![192, 168, 1, 100, 0x1f, 0x90]);
+
+        // test little-endian
+        let fattr = FieldAttrModifiers {
+            byteorder: Some(ByteOrder::Le),
+            ..Default::default()
+        };
+        let mut buffer = Buffer::new();
+        assert_eq!(SocketAddr::V4(addr).encode_inner(&mut buffer, None, Some(&fattr)).unwrap(), 6);
+        assert_eq!(*buffer, vec![100, 1, 168, 192, 0x90, 0x1f]);
+
+        // test ipv6
+        let mut buffer = Buffer::new();
+        let addr = SocketAddr::from_str("[fa80::e74:abff:fe93:56f0]:53").unwrap();
+        assert_eq!(addr.encode(&mut buffer).unwrap(), 18);
+        assert_eq!(*buffer, vec![250, 128, 0, 0, 0, 0, 0, 0, 14, 116, 171, 255, 254, 147, 86, 240, 0x00, 0x35]);
+    }
+}
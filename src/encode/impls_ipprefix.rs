@@ -0,0 +1,52 @@
+use crate::{
+    types::IpPrefix,
+    JResult, BufWrite,
+    ContainerAttrModifiers, FieldAttrModifiers,
+};
+
+
+impl crate::ByteEncode for IpPrefix {
+    #[inline]
+    fn encode_inner<B: BufWrite>(&self, buffer: &mut B, _cattr: Option<&ContainerAttrModifiers>,
+                                                                  _fattr: Option<&FieldAttrModifiers>) -> JResult<usize> {
+        let mut r_nbytes = buffer.push_u8(self.len())?;
+        r_nbytes += buffer.push(self.octets())?;
+        Ok(r_nbytes)
+    }
+}
+
+
+impl crate::BorrowByteEncode for IpPrefix {
+    #[inline]
+    fn encode_inner<B: BufWrite>(&self, buffer: &mut B, cattr: Option<&ContainerAttrModifiers>,
+                                                                  fattr: Option<&FieldAttrModifiers>) -> JResult<usize> {
+        crate::ByteEncode::encode_inner(self, buffer, cattr, fattr)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use core::str::FromStr;
+    use crate::std::*;
+    use crate::{Buffer, ByteEncode};
+    use super::*;
+
+    #[test]
+    fn test_encode_ipprefix() {
+        let mut buffer = Buffer::new();
+        let prefix = IpPrefix::from_str("10.1.2.3/15").unwrap();
+        assert_eq!(prefix.encode(&mut buffer).unwrap(), 3);
+        assert_eq!(*buffer, vec![15, 10, 0]);
+
+        let mut buffer = Buffer::new();
+        let prefix = IpPrefix::from_str("0.0.0.0/0").unwrap();
+        assert_eq!(prefix.encode(&mut buffer).unwrap(), 1);
+        assert_eq!(*buffer, vec![0]);
+
+        let mut buffer = Buffer::new();
+        let prefix = IpPrefix::from_str("2001:db8::/32").unwrap();
+        assert_eq!(prefix.encode(&mut buffer).unwrap(), 5);
+        assert_eq!(*buffer, vec![32, 0x20, 0x01, 0x0d, 0xb8]);
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{
+    JResult, BufWrite, Buffer,
+    make_error, ErrorKind,
+    ByteEncode, BorrowByteEncode,
+    ContainerAttrModifiers, FieldAttrModifiers,
+    get_byteorder,
+    types::Lazy,
+};
+
+
+#[inline]
+fn encode_inner<B: BufWrite, T: ByteEncode>(lazy: &Lazy<'_, T>, buffer: &mut B, cattr: Option<&ContainerAttrModifiers>,
+                                                                  fattr: Option<&FieldAttrModifiers>) -> JResult<usize> {
+    // The untouched raw bytes are encoded verbatim.
+    let value = match lazy.value.get() {
+        Some(value) if lazy.is_modified => value,
+        _ => return buffer.push(lazy.raw),
+    };
+
+    // The value fills the byte range of the field.
+    let fattr_remaining = FieldAttrModifiers { remaining: true, ..Default::default() };
+
+    match fattr {
+        Some(fr) if fr.byte_count.is_some() => {
+            let mut value_buffer = Buffer::new();
+            value.encode_inner(&mut value_buffer, cattr, Some(&fattr_remaining))?;
+
+            let byte_count = fr.byte_count.unwrap_or_default();
+            let r_nbytes = buffer.push_byteorder_uint(value_buffer.len() as u64, byte_count, get_byteorder(cattr, fattr))?;
+
+            Ok(r_nbytes + buffer.push(&*value_buffer)?)
+        },
+        // The value is padded with zeros to the fixed length, which can't be exceeded.
+        Some(fr) if fr.length.is_some() => {
+            let length = fr.length.unwrap_or_default();
+            let mut value_buffer = Buffer::new();
+            value.encode_inner(&mut value_buffer, cattr, Some(&fattr_remaining))?;
+
+            if value_buffer.len() > length {
+                return Err(make_error(buffer.get_position(), ErrorKind::InvalidByteLength));
+            }
+
+            let mut r_nbytes = buffer.push(&*value_buffer)?;
+
+            for _i in value_buffer.len()..length {
+                r_nbytes += buffer.push_u8(0x00)?;
+            }
+
+            Ok(r_nbytes)
+        },
+        Some(fr) if fr.remaining => value.encode_inner(buffer, cattr, Some(&fattr_remaining)),
+        _ => value.encode_inner(buffer, cattr, fattr),
+    }
+}
+
+
+impl<'a, T: ByteEncode> ByteEncode for Lazy<'a, T> {
+    #[inline]
+    fn encode_inner<B: BufWrite>(&self, buffer: &mut B, cattr: Option<&ContainerAttrModifiers>,
+                                                                  fattr: Option<&FieldAttrModifiers>) -> JResult<usize> {
+        encode_inner(self, buffer, cattr, fattr)
+    }
+}
+
+
+impl<'a, T: ByteEncode> BorrowByteEncode for Lazy<'a, T> {
+    #[inline]
+    fn encode_inner<B: BufWrite>(&self, buffer: &mut B, cattr: Option<&ContainerAttrModifiers>,
+                                                                  fattr: Option<&FieldAttrModifiers>) -> JResult<usize> {
+        encode_inner(self, buffer, cattr, fattr)
+    }
+}
@@ -3,15 +3,34 @@ use crate::{
     JResult, BufWrite,
     ByteEncode, BorrowByteEncode,
     ContainerAttrModifiers, FieldAttrModifiers,
+    ErrorKind, make_error,
+    Encoding,
 };
 use super::impls_bytes::encode_inner;
 
 
+/// Transcodes the string by the `encoding` modifier, defaults to UTF-8.
+#[inline]
+fn encode_string<B: BufWrite>(buffer: &mut B, cattr: Option<&ContainerAttrModifiers>,
+                                  fattr: Option<&FieldAttrModifiers>, value: &str) -> JResult<usize> {
+    if let Some(fr) = fattr {
+        if let Some(encoding) = fr.encoding.filter(|v| *v != Encoding::Utf8) {
+            return match encoding.encode(value) {
+                Some(data) => encode_inner(buffer, cattr, fattr, &data),
+                None => Err(make_error(buffer.get_position(), ErrorKind::Fail)),
+            };
+        }
+    }
+
+    encode_inner(buffer, cattr, fattr, value.as_bytes())
+}
+
+
 impl ByteEncode for String {
     #[inline]
     fn encode_inner<B: BufWrite>(&self, buffer: &mut B, cattr: Option<&ContainerAttrModifiers>,
                                                                   fattr: Option<&FieldAttrModifiers>) -> JResult<usize> {
-        encode_inner(buffer, cattr, fattr, self.as_bytes())
+        encode_string(buffer, cattr, fattr, self)
     }
 }
 
@@ -20,7 +39,7 @@ impl BorrowByteEncode for String {
     #[inline]
     fn encode_inner<B: BufWrite>(&self, buffer: &mut B, cattr: Option<&ContainerAttrModifiers>,
                                                                   fattr: Option<&FieldAttrModifiers>) -> JResult<usize> {
-        encode_inner(buffer, cattr, fattr, self.as_bytes())
+        encode_string(buffer, cattr, fattr, self)
     }
 }
 
@@ -29,6 +48,68 @@ impl<'de> BorrowByteEncode for &'de str {
     #[inline]
     fn encode_inner<B: BufWrite>(&self, buffer: &mut B, cattr: Option<&ContainerAttrModifiers>,
                                                                   fattr: Option<&FieldAttrModifiers>) -> JResult<usize> {
-        encode_inner(buffer, cattr, fattr, self.as_bytes())
+        encode_string(buffer, cattr, fattr, self)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use crate::std::*;
+    use crate::{
+        Buffer, ByteEncode, FieldAttrModifiers, Encoding,
+    };
+
+    #[test]
+    fn test_encode_string_encoding() {
+        // test utf-16 example
+        let fattr = FieldAttrModifiers {
+            encoding: Some(Encoding::Utf16Le),
+            ..Default::default()
+        };
+        let mut buffer = Buffer::new();
+        assert_eq!("ab".to_string().encode_inner(&mut buffer, None, Some(&fattr)).unwrap(), 5);
+        assert_eq!(*buffer, b"\x04a\x00b\x00");
+
+        // test code units example
+        let fattr = FieldAttrModifiers {
+            encoding: Some(Encoding::Utf16Be),
+            byte_count: Some(2),
+            code_units: true,
+            ..Default::default()
+        };
+        let mut buffer = Buffer::new();
+        assert_eq!("ab".to_string().encode_inner(&mut buffer, None, Some(&fattr)).unwrap(), 6);
+        assert_eq!(*buffer, b"\x00\x02\x00a\x00b");
+
+        // test utf-16 cstr example
+        let fattr = FieldAttrModifiers {
+            encoding: Some(Encoding::Utf16Le),
+            cstr: true,
+            ..Default::default()
+        };
+        let mut buffer = Buffer::new();
+        assert_eq!("a".to_string().encode_inner(&mut buffer, None, Some(&fattr)).unwrap(), 4);
+        assert_eq!(*buffer, b"a\x00\x00\x00");
+
+        // test utf-16 fixed-width example
+        let fattr = FieldAttrModifiers {
+            encoding: Some(Encoding::Utf16Le),
+            length: Some(3),
+            code_units: true,
+            pad: Some(0x00),
+            ..Default::default()
+        };
+        let mut buffer = Buffer::new();
+        assert_eq!("a".to_string().encode_inner(&mut buffer, None, Some(&fattr)).unwrap(), 6);
+        assert_eq!(*buffer, b"a\x00\x00\x00\x00\x00");
+
+        // test latin1 error example
+        let fattr = FieldAttrModifiers {
+            encoding: Some(Encoding::Latin1),
+            ..Default::default()
+        };
+        let mut buffer = Buffer::new();
+        assert_eq!("€".to_string().encode_inner(&mut buffer, None, Some(&fattr)).is_err(), true);
     }
 }
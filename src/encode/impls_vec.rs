@@ -102,6 +102,15 @@ mod tests {
         assert_eq!(vec![0x0001_u16, 0x0002].encode_inner(&mut buffer, None, Some(&fattr)).unwrap(), 4);
         assert_eq!(*buffer, vec![0x00, 0x01, 0x00, 0x02]);
 
+        // test `remaining` example
+        let mut buffer = Buffer::new();
+        let fattr = FieldAttrModifiers {
+            remaining: true,
+            ..Default::default()
+        };
+        assert_eq!(vec![0x0001_u16, 0x0002].encode_inner(&mut buffer, None, Some(&fattr)).unwrap(), 4);
+        assert_eq!(*buffer, vec![0x00, 0x01, 0x00, 0x02]);
+
         // test `byte_count_outside` example
         let mut buffer = Buffer::new();
         let fattr = FieldAttrModifiers {
@@ -15,7 +15,9 @@ mod impls_option;
 mod impls_ipaddress;
 mod impls_macaddress;
 mod impls_netaddress;
+mod impls_ipprefix;
 mod impls_mark;
+mod impls_lazy;
 
 // use crate::std::*;
 use crate::{
@@ -97,6 +99,7 @@ fn push_count_and_try_count<B: BufWrite>(buffer: &mut B, cattr: Option<&Containe
         }
         else if fr.count.is_some() { }
         else if fr.try_count.is_some() { }
+        else if fr.remaining { }
         else {
             r_nbytes += buffer.push_u8(value as u8)?;
         }
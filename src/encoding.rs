@@ -0,0 +1,172 @@
+use crate::std::*;
+use crate::errors::ThisError;
+#[cfg(feature = "encoding_rs")]
+pub use encoding_rs;
+
+
+/// Returns the legacy code page of the derive macros, eg: `legacy_encoding!(GBK, "gbk")`.
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "encoding_rs")]
+macro_rules! legacy_encoding {
+    ($name:ident, $label:literal) => {
+        $crate::Encoding::Legacy($crate::encoding::encoding_rs::$name)
+    };
+}
+
+
+/// The legacy code pages need the `encoding_rs` feature.
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "encoding_rs"))]
+macro_rules! legacy_encoding {
+    ($name:ident, $label:literal) => {
+        compile_error!(concat!("the encoding `", $label, "` requires the `encoding_rs` feature of `jbytes`"))
+    };
+}
+
+
+#[derive(Debug, ThisError)]
+pub enum EncodingParseError {
+    #[error("invalid encoding: `{0}`")]
+    InvalidEncoding(String),
+}
+
+
+/// This is a text encoding type of string fields.
+///
+/// # Example
+///
+/// ```
+/// use jbytes::Encoding;
+/// assert_eq!(Encoding::Utf16Le.encode("ab").unwrap(), b"a\x00b\x00");
+/// assert_eq!(Encoding::Utf16Le.decode(b"a\x00b\x00").unwrap(), "ab");
+/// assert_eq!(Encoding::Latin1.decode(b"caf\xe9").unwrap(), "café");
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum Encoding {
+    #[default]
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    /// ISO-8859-1, each byte is a unicode code point of `U+0000..=U+00FF`.
+    Latin1,
+    /// Legacy code pages, eg: GBK/Shift_JIS/windows-1252.
+    #[cfg(feature = "encoding_rs")]
+    Legacy(&'static encoding_rs::Encoding),
+}
+
+
+impl Encoding {
+    /// Parses the encoding label, eg: `utf8`/`utf16le`/`utf16be`/`latin1`, the other labels need the `encoding_rs` feature.
+    pub fn parse(label: &str) -> Result<Self, EncodingParseError> {
+        match label.to_ascii_lowercase().as_str() {
+            "utf8" | "utf-8" => Ok(Self::Utf8),
+            "utf16le" | "utf-16le" => Ok(Self::Utf16Le),
+            "utf16be" | "utf-16be" => Ok(Self::Utf16Be),
+            "latin1" | "iso-8859-1" => Ok(Self::Latin1),
+            #[cfg(feature = "encoding_rs")]
+            _ => match encoding_rs::Encoding::for_label(label.as_bytes()) {
+                Some(encoding) => Ok(Self::Legacy(encoding)),
+                None => Err(EncodingParseError::InvalidEncoding(label.to_string())),
+            },
+            #[cfg(not(feature = "encoding_rs"))]
+            _ => Err(EncodingParseError::InvalidEncoding(label.to_string())),
+        }
+    }
+
+    /// Returns the number of bytes of a code unit, 2 for UTF-16, otherwise 1.
+    #[inline]
+    pub fn code_unit_size(&self) -> usize {
+        match self {
+            Self::Utf16Le | Self::Utf16Be => 2,
+            _ => 1,
+        }
+    }
+
+    /// Decodes the bytes to a string, returns `None` if the bytes are malformed.
+    pub fn decode(&self, data: &[u8]) -> Option<String> {
+        match self {
+            Self::Utf8 => str::from_utf8(data).ok().map(|v| v.to_string()),
+            Self::Utf16Le | Self::Utf16Be => {
+                let chunks = data.chunks_exact(2);
+
+                if !chunks.remainder().is_empty() {
+                    return None;
+                }
+
+                let units = chunks.map(|v| {
+                    if *self == Self::Utf16Le { u16::from_le_bytes([v[0], v[1]]) } else { u16::from_be_bytes([v[0], v[1]]) }
+                });
+
+                char::decode_utf16(units).collect::<Result<String, _>>().ok()
+            },
+            Self::Latin1 => Some(data.iter().map(|v| *v as char).collect()),
+            #[cfg(feature = "encoding_rs")]
+            Self::Legacy(encoding) => encoding.decode_without_bom_handling_and_without_replacement(data).map(|v| v.to_string()),
+        }
+    }
+
+    /// Encodes the string to bytes, returns `None` if a character is unmappable.
+    pub fn encode(&self, value: &str) -> Option<Vec<u8>> {
+        match self {
+            Self::Utf8 => Some(value.as_bytes().to_vec()),
+            Self::Utf16Le => Some(value.encode_utf16().flat_map(|v| v.to_le_bytes()).collect()),
+            Self::Utf16Be => Some(value.encode_utf16().flat_map(|v| v.to_be_bytes()).collect()),
+            Self::Latin1 => value.chars().map(|v| u8::try_from(v as u32).ok()).collect(),
+            #[cfg(feature = "encoding_rs")]
+            Self::Legacy(encoding) => {
+                let (data, _encoding, has_errors) = encoding.encode(value);
+
+                if has_errors {
+                    return None;
+                }
+
+                Some(data.to_vec())
+            },
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encoding_parse() {
+        assert_eq!(Encoding::parse("utf8").unwrap(), Encoding::Utf8);
+        assert_eq!(Encoding::parse("UTF-16LE").unwrap(), Encoding::Utf16Le);
+        assert_eq!(Encoding::parse("utf16be").unwrap(), Encoding::Utf16Be);
+        assert_eq!(Encoding::parse("latin1").unwrap(), Encoding::Latin1);
+        assert_eq!(Encoding::parse("xxx").is_err(), true);
+    }
+
+    #[test]
+    fn test_encoding_utf16() {
+        assert_eq!(Encoding::Utf16Be.encode("a€").unwrap(), b"\x00a\x20\xac");
+        assert_eq!(Encoding::Utf16Be.decode(b"\x00a\x20\xac").unwrap(), "a€");
+        assert_eq!(Encoding::Utf16Le.encode("😀").unwrap(), b"\x3d\xd8\x00\xde");
+        assert_eq!(Encoding::Utf16Le.decode(b"\x3d\xd8\x00\xde").unwrap(), "😀");
+
+        // odd length and unpaired surrogate
+        assert_eq!(Encoding::Utf16Le.decode(b"a\x00b"), None);
+        assert_eq!(Encoding::Utf16Le.decode(b"\x3d\xd8"), None);
+    }
+
+    #[test]
+    fn test_encoding_latin1() {
+        assert_eq!(Encoding::Latin1.encode("café").unwrap(), b"caf\xe9");
+        assert_eq!(Encoding::Latin1.encode("€"), None);
+    }
+
+    #[cfg(feature = "encoding_rs")]
+    #[test]
+    fn test_encoding_legacy() {
+        let encoding = Encoding::parse("gbk").unwrap();
+        assert_eq!(encoding, Encoding::Legacy(encoding_rs::GBK));
+        assert_eq!(encoding.encode("中文").unwrap(), b"\xd6\xd0\xce\xc4");
+        assert_eq!(encoding.decode(b"\xd6\xd0\xce\xc4").unwrap(), "中文");
+        assert_eq!(encoding.encode("😀"), None);
+    }
+}
@@ -55,6 +55,16 @@ pub enum ErrorKind {
     PushFail,
     #[error("invalid value ({0})")]
     InvalidValue(String),
+    #[error("invalid digit ({0:#04x})")]
+    InvalidDigit(u8),
+    #[error("invalid magic ({0:02x?})")]
+    InvalidMagic(Vec<u8>),
+    #[error("invalid checksum (expected: {0:#x}, found: {1:#x})")]
+    InvalidChecksum(u64, u64),
+    #[error("missing context")]
+    MissingContext,
+    #[error("missing argument ({0})")]
+    MissingArgument(String),
 }
 
 
@@ -0,0 +1,682 @@
+use crate::std::*;
+use crate::schema::{Schema, SchemaBody, FieldSchema, VariantSchema, Modifier};
+use super::{
+    Export, Unsupported, Job, Modifiers,
+    to_snake_case, parse_int, parse_bytes, get_int_type, strip_type, translate_expr,
+    get_endian, get_variant_id, get_variant_keys, get_branch_modifier,
+};
+
+
+/// Exports the schema of a `#[derive(Describe)]` type to a Kaitai Struct (.ksy) document.
+///
+/// The nested derived types are exported to `types`, an enum with a `branch` field is a `switch-on` of the
+/// field expression, and an enum without it reads its discriminant into a `branch` field first.
+/// The modifiers which can't be represented are returned by `Export::unsupported` and added to the `doc` of
+/// the field, eg: `with`/`key`/`seek`/a multi-byte `linend`.
+///
+/// # Example
+///
+/// ```
+/// use jbytes_derive::{ByteDecode, ByteEncode, Describe};
+/// use jbytes::prelude::*;
+///
+///
+/// #[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, Describe)]
+/// #[jbytes(byteorder="LE")]
+/// pub struct KsyExample {
+///     pub length: u16,
+///     #[jbytes(length="length")]
+///     pub value: String,
+/// }
+///
+///
+/// let export = jbytes::export::to_ksy(&KsyExample::SCHEMA);
+/// assert_eq!(export.document, "\
+/// meta:
+///   id: ksy_example
+///   endian: le
+/// seq:
+///   - id: length
+///     type: u2
+///   - id: value
+///     type: str
+///     size: length
+///     encoding: UTF-8
+/// ");
+/// assert_eq!(export.unsupported.is_empty(), true);
+/// ```
+pub fn to_ksy(schema: &'static Schema) -> Export {
+    let mut exporter = KsyExporter::default();
+    let id = to_snake_case(schema.name);
+
+    exporter.root_endian = match schema.get("byteorder").and_then(|v| get_endian(v.value)) {
+        Some(Some(endian)) => endian,
+        _ => "be",
+    };
+
+    exporter.done.push(Job::Type(schema));
+    let (_, seq) = exporter.type_body(schema);
+    let mut lines = vec!["meta:".to_string(), format!("  id: {id}"), format!("  endian: {}", exporter.root_endian)];
+    write_seq(&mut lines, &seq, 0);
+
+    let mut types = vec![];
+
+    while !exporter.pending.is_empty() {
+        let job = exporter.pending.remove(0);
+
+        match job {
+            Job::Type(schema) => {
+                let (endian, seq) = exporter.type_body(schema);
+                types.push((to_snake_case(schema.name), endian, seq));
+            },
+            Job::Variants(schema) => {
+                for variant in schema.variants() {
+                    let (endian, seq) = exporter.variant_body(schema, variant);
+                    types.push((get_variant_id(schema, variant), endian, seq));
+                }
+            },
+        }
+    }
+
+    if !types.is_empty() {
+        lines.push("types:".to_string());
+    }
+
+    for (id, endian, seq) in types {
+        // The unit variant doesn't read anything.
+        if seq.is_empty() {
+            lines.push(format!("  {id}: {{}}"));
+            continue;
+        }
+
+        lines.push(format!("  {id}:"));
+
+        if let Some(endian) = endian {
+            lines.push("    meta:".to_string());
+            lines.push(format!("      endian: {endian}"));
+        }
+
+        write_seq(&mut lines, &seq, 4);
+    }
+
+    let mut document = lines.join("\n");
+    document.push('\n');
+
+    Export { document, unsupported: exporter.unsupported }
+}
+
+
+#[derive(Default)]
+struct KsyExporter {
+    unsupported: Vec<Unsupported>,
+    pending: Vec<Job>,
+    done: Vec<Job>,
+    /// The endian of the document, which is inherited by `byteorder="inherit"`.
+    root_endian: &'static str,
+    /// The effective endian of the current type.
+    endian: &'static str,
+}
+
+
+/// Quotes a YAML scalar if it isn't plain, eg: `unsupported: with` => `'unsupported: with'`.
+fn to_yaml(value: &str) -> String {
+    let is_plain = !value.is_empty()
+        && !value.contains(": ")
+        && !value.contains(" #")
+        && !value.ends_with(':')
+        && !value.starts_with(|v: char| "!&*-?[]{}|>'\"%@`#, ".contains(v));
+
+    match is_plain {
+        true => value.to_string(),
+        false => format!("'{}'", value.replace('\'', "''")),
+    }
+}
+
+
+/// Returns the `contents` of the magic bytes, eg: `b"JB"` => `[0x4a, 0x42]`.
+fn to_contents(value: &str) -> String {
+    let bytes = parse_bytes(value).unwrap_or_else(|| value.as_bytes().to_vec());
+    let bytes = bytes.iter().map(|v| format!("{v:#04x}")).collect::<Vec<String>>().join(", ");
+
+    format!("[{bytes}]")
+}
+
+
+/// Returns the integer type of Kaitai Struct, the other sizes are byte-aligned bit fields, eg: `b24le`.
+fn to_int_type(size: usize, signed: bool, endian: &str, is_explicit: bool) -> Option<String> {
+    let suffix = if is_explicit && size > 1 { endian } else { "" };
+
+    match size {
+        1 | 2 | 4 | 8 => Some(format!("{}{size}{suffix}", if signed { 's' } else { 'u' })),
+        3 | 5..=7 => Some(format!("b{}{endian}", size * 8)),
+        _ => None,
+    }
+}
+
+
+/// Returns the Kaitai Struct label of an `encoding` modifier, eg: `utf16le` => `UTF-16LE`.
+fn to_encoding(value: Option<&str>) -> String {
+    match value.map(|v| v.to_ascii_lowercase()).as_deref() {
+        None | Some("utf8" | "utf-8") => "UTF-8".to_string(),
+        Some("utf16le" | "utf-16le") => "UTF-16LE".to_string(),
+        Some("utf16be" | "utf-16be") => "UTF-16BE".to_string(),
+        Some("latin1") => "ISO-8859-1".to_string(),
+        Some(label) => label.to_ascii_uppercase(),
+    }
+}
+
+
+/// Writes the `seq` entries, the first line of an entry is prefixed with `- `.
+fn write_seq(lines: &mut Vec<String>, seq: &[Vec<String>], indent: usize) {
+    if seq.is_empty() {
+        return;
+    }
+
+    let indent = " ".repeat(indent);
+    lines.push(format!("{indent}seq:"));
+
+    for entry in seq {
+        for (index, line) in entry.iter().enumerate() {
+            lines.push(format!("{indent}{}{line}", if index == 0 { "  - " } else { "    " }));
+        }
+    }
+}
+
+
+impl KsyExporter {
+    fn report(&mut self, path: &str, modifier: &'static Modifier) {
+        let value = Unsupported { path: path.to_string(), key: modifier.key, value: modifier.value };
+
+        if !self.unsupported.contains(&value) {
+            self.unsupported.push(value);
+        }
+    }
+
+    fn report_type(&mut self, path: &str, rtype: &'static str) {
+        let value = Unsupported { path: path.to_string(), key: "type", value: Some(rtype) };
+
+        if !self.unsupported.contains(&value) {
+            self.unsupported.push(value);
+        }
+    }
+
+    fn report_unused(&mut self, path: &str, modifiers: &Modifiers) {
+        for modifier in modifiers.unused() {
+            self.report(path, modifier);
+        }
+    }
+
+    fn push_job(&mut self, job: Job) {
+        if !self.done.contains(&job) {
+            self.pending.push(job);
+            self.done.push(job);
+        }
+    }
+
+    /// Returns the own endian of a container and sets the effective endian, `None` is inherited.
+    fn set_endian(&mut self, path: &str, modifiers: &mut Modifiers) -> Option<&'static str> {
+        let endian = match modifiers.take("byteorder") {
+            Some(modifier) => get_endian(modifier.value).unwrap_or_else(|| {
+                self.report(path, modifier);
+                None
+            }),
+            None => Some("be"),
+        };
+
+        self.endian = endian.unwrap_or(self.root_endian);
+        endian
+    }
+
+    /// Returns the own endian and the `seq` of a struct, or an enum which reads its discriminant.
+    fn type_body(&mut self, schema: &'static Schema) -> (Option<&'static str>, Vec<Vec<String>>) {
+        let path = to_snake_case(schema.name);
+        let mut modifiers = Modifiers::new(schema.modifiers);
+        let endian = self.set_endian(&path, &mut modifiers);
+        let mut seq = vec![];
+
+        match schema.body {
+            SchemaBody::Struct(fields) => {
+                if let Some(magic) = modifiers.value("magic") {
+                    seq.push(vec!["id: magic".to_string(), format!("contents: {}", to_contents(magic))]);
+                }
+
+                self.fields_seq(&path, fields, &mut seq);
+            },
+            SchemaBody::Enum(_) => {
+                let size = modifiers.value("byte_count").and_then(parse_int).unwrap_or(1) as usize;
+                modifiers.take("branch_type");
+
+                if let Some(modifier) = modifiers.take("byte_count_disable") {
+                    self.report(&path, modifier);
+                }
+
+                match get_branch_modifier(schema) {
+                    Some(modifier) => {
+                        self.report(&path, modifier);
+                        seq.push(vec!["id: body".to_string(), "size-eos: true".to_string()]);
+                    },
+                    None => {
+                        let rtype = to_int_type(size, false, self.endian, true).unwrap_or_else(|| "u1".to_string());
+                        seq.push(vec!["id: branch".to_string(), format!("type: {rtype}")]);
+
+                        let mut entry = vec!["id: body".to_string()];
+                        entry.extend(self.switch_lines(schema, "branch"));
+                        seq.push(entry);
+                    },
+                }
+            },
+        }
+
+        self.report_unused(&path, &modifiers);
+
+        (endian, seq)
+    }
+
+    /// Returns the own endian and the `seq` of an enum variant, the container modifiers are the enum's.
+    fn variant_body(&mut self, schema: &'static Schema, variant: &'static VariantSchema) -> (Option<&'static str>, Vec<Vec<String>>) {
+        let path = get_variant_id(schema, variant);
+        let mut modifiers = Modifiers::new(schema.modifiers);
+        let endian = self.set_endian(&to_snake_case(schema.name), &mut modifiers);
+        let mut seq = vec![];
+
+        // The discriminant is read by the enum or the parent field.
+        for key in ["byte_count", "byte_count_disable", "branch_type"] {
+            modifiers.take(key);
+        }
+
+        self.report_unused(&to_snake_case(schema.name), &modifiers);
+
+        let mut variant_modifiers = Modifiers::new(variant.modifiers);
+
+        if get_variant_keys(variant).is_some() {
+            for key in ["branch_value", "branch_range", "branch_default"] {
+                variant_modifiers.take(key);
+            }
+        }
+
+        self.fields_seq(&path, variant.fields, &mut seq);
+        self.report_unused(&path, &variant_modifiers);
+
+        (endian, seq)
+    }
+
+    /// Returns the `type` lines of a `switch-on` of the variant types.
+    fn switch_lines(&mut self, schema: &'static Schema, on: &str) -> Vec<String> {
+        let mut lines = vec!["type:".to_string(), format!("  switch-on: {}", to_yaml(on)), "  cases:".to_string()];
+
+        for variant in schema.variants() {
+            for key in get_variant_keys(variant).unwrap_or_default() {
+                lines.push(format!("    {}: {}", to_yaml(&key), get_variant_id(schema, variant)));
+            }
+        }
+
+        self.push_job(Job::Variants(schema));
+
+        lines
+    }
+
+    fn fields_seq(&mut self, path: &str, fields: &'static [FieldSchema], seq: &mut Vec<Vec<String>>) {
+        let mut index = 0;
+
+        while index < fields.len() {
+            let count = self.bits_seq(path, &fields[index..], seq);
+
+            if count > 0 {
+                index += count;
+                continue;
+            }
+
+            self.field_seq(path, &fields[index], seq);
+            index += 1;
+        }
+    }
+
+    /// Returns the number of the fields of a bits group, eg: `bits_start=0xf0, untake` and `bits=0x0f`,
+    /// which are exported to the bit fields with the padding, or 0 if it isn't a valid group.
+    fn bits_seq(&mut self, path: &str, fields: &'static [FieldSchema], seq: &mut Vec<Vec<String>>) -> usize {
+        let is_bits = |v: &FieldSchema| v.get("bits").or(v.get("bits_start")).is_some();
+
+        let count = match fields.iter().position(|v| !is_bits(v) || v.get("untake").is_none()) {
+            Some(index) if is_bits(&fields[index]) => index + 1,
+            _ => return 0,
+        };
+
+        let group = &fields[..count];
+        let size = match get_int_type(strip_type(group[0].rtype).as_str()) {
+            Some((size, _)) if group.iter().all(|v| v.rtype == group[0].rtype) => size,
+            _ => return 0,
+        };
+
+        // The bits of a multi-byte little-endian integer aren't contiguous in the stream.
+        if size > 1 && (self.endian == "le" || group.iter().any(|v| v.get("byteorder").is_some())) {
+            return 0;
+        }
+
+        let mut masks = vec![];
+
+        for field in group {
+            let mask = match field.get("bits").or(field.get("bits_start")).and_then(|v| parse_int(v.value.unwrap_or_default())) {
+                Some(mask) if mask != 0 => mask,
+                _ => return 0,
+            };
+            let (start, end) = (mask.trailing_zeros() as usize, 128 - mask.leading_zeros() as usize);
+
+            // The mask must be contiguous and in the width of the integer.
+            if (mask >> start).count_ones() as usize != end - start || end > size * 8 {
+                return 0;
+            }
+
+            masks.push((end, start, field));
+        }
+
+        masks.sort_by_key(|v| core::cmp::Reverse(v.0));
+
+        if masks.windows(2).any(|v| v[1].0 > v[0].1) {
+            return 0;
+        }
+
+        let mut position = size * 8;
+
+        for (end, start, field) in masks {
+            if position > end {
+                seq.push(vec![format!("type: b{}", position - end)]);
+            }
+
+            let id = to_snake_case(field.name);
+            let mut modifiers = Modifiers::new(field.modifiers);
+
+            for key in ["bits", "bits_start", "untake"] {
+                modifiers.take(key);
+            }
+
+            let mut entry = vec![format!("id: {id}"), format!("type: b{}", end - start)];
+            self.finish_entry(&format!("{path}.{id}"), &mut modifiers, &mut entry, &mut []);
+            seq.push(entry);
+            position = start;
+        }
+
+        if position > 0 {
+            seq.push(vec![format!("type: b{position}")]);
+        }
+
+        count
+    }
+
+    fn field_seq(&mut self, path: &str, field: &'static FieldSchema, seq: &mut Vec<Vec<String>>) {
+        let id = to_snake_case(field.name);
+        let path = format!("{path}.{id}");
+        let mut modifiers = Modifiers::new(field.modifiers);
+
+        if modifiers.has("skip") || modifiers.has("skip_decode") {
+            return;
+        }
+
+        // The discriminant field is assigned by the enum without reading.
+        if let Some(modifier) = modifiers.take("discriminant") {
+            self.report(&path, modifier);
+            return;
+        }
+
+        let mut prefix = vec![];
+
+        if let Some(offset) = modifiers.value("offset") {
+            prefix.push(vec![format!("size: {}", to_yaml(&translate_expr(offset)))]);
+        }
+
+        let mut entry = vec![format!("id: {id}")];
+
+        if let Some(magic) = modifiers.value("magic") {
+            entry.push(format!("contents: {}", to_contents(magic)));
+        }
+        else {
+            let endian = match modifiers.take("byteorder") {
+                Some(modifier) => get_endian(modifier.value).unwrap_or_else(|| {
+                    self.report(&path, modifier);
+                    None
+                }),
+                None => None,
+            };
+
+            match self.type_lines(&path, &id, field, strip_type(field.rtype).as_str(), &mut modifiers, endian, &mut prefix) {
+                Some(lines) => entry.extend(lines),
+                // The zero-sized field, eg: `()`/`PhantomData<T>`.
+                None => {
+                    self.report_unused(&path, &modifiers);
+                    return;
+                },
+            }
+        }
+
+        if let Some(value) = modifiers.value("check_value") {
+            entry.push(format!("valid: {}", to_yaml(&translate_expr(value))));
+        }
+
+        self.finish_entry(&path, &mut modifiers, &mut entry, &mut prefix);
+        seq.extend(prefix);
+        seq.push(entry);
+    }
+
+    /// Adds the `if` and the `doc` of the unsupported modifiers to the entries of a field.
+    fn finish_entry(&mut self, path: &str, modifiers: &mut Modifiers, entry: &mut Vec<String>, prefix: &mut [Vec<String>]) {
+        if let Some(value) = modifiers.value("if_expr") {
+            let line = format!("if: {}", to_yaml(&translate_expr(value)));
+            prefix.iter_mut().for_each(|v| v.push(line.clone()));
+            entry.push(line);
+        }
+
+        self.report_unused(path, modifiers);
+
+        let keys = self.unsupported.iter()
+            .filter(|v| v.path == path)
+            .map(|v| v.key)
+            .collect::<Vec<&str>>();
+
+        if !keys.is_empty() {
+            entry.push(format!("doc: {}", to_yaml(&format!("unsupported: {}", keys.join(", ")))));
+        }
+    }
+
+    /// Returns the prefix entry of a length/count, eg: `{id}_len`.
+    fn prefix_entry(&self, id: &str, size: usize, endian: Option<&str>) -> Vec<String> {
+        let rtype = to_int_type(size, false, endian.unwrap_or(self.endian), endian.is_some()).unwrap_or_else(|| "u1".to_string());
+        vec![format!("id: {id}"), format!("type: {rtype}")]
+    }
+
+    /// Returns the `size` lines of the bytes/string, eg: `size: length`/`terminator: 10`/`size-eos: true`.
+    fn size_lines(&mut self, path: &str, id: &str, modifiers: &mut Modifiers, endian: Option<&'static str>, prefix: &mut Vec<Vec<String>>) -> Vec<String> {
+        let mut lines = vec![];
+
+        if let Some(length) = modifiers.value("length") {
+            lines.push(format!("size: {}", to_yaml(&translate_expr(length))));
+        }
+        else if let Some(size) = modifiers.value("byte_count").and_then(parse_int) {
+            prefix.push(self.prefix_entry(&format!("{id}_len"), size as usize, endian));
+            lines.push(format!("size: {id}_len"));
+        }
+        else if let Some(modifier) = modifiers.get("linend") {
+            match modifier.value.and_then(parse_bytes).or_else(|| modifier.value.map(|v| v.as_bytes().to_vec())) {
+                Some(value) if value.len() == 1 => {
+                    modifiers.take("linend");
+                    lines.push(format!("terminator: {}", value[0]));
+                },
+                // A multi-byte or the default `\r\n`/`\n`/`\0` terminators.
+                _ => {
+                    modifiers.take("linend");
+                    self.report(path, modifier);
+                    lines.push("size-eos: true".to_string());
+                },
+            }
+        }
+        else if modifiers.has("remaining") {
+            lines.push("size-eos: true".to_string());
+        }
+        else {
+            prefix.push(self.prefix_entry(&format!("{id}_len"), 1, endian));
+            lines.push(format!("size: {id}_len"));
+        }
+
+        if let Some(pad) = modifiers.value("pad").and_then(|v| parse_int(v).or_else(|| parse_bytes(v)?.first().map(|v| *v as u128))) {
+            lines.push(format!("pad-right: {pad}"));
+        }
+
+        lines
+    }
+
+    /// Returns the `type` lines of a field type, or `None` for the zero-sized types.
+    #[allow(clippy::too_many_arguments)]
+    fn type_lines(&mut self, path: &str, id: &str, field: &'static FieldSchema, rtype: &str, modifiers: &mut Modifiers,
+                  endian: Option<&'static str>, prefix: &mut Vec<Vec<String>>) -> Option<Vec<String>>
+    {
+        let mut lines = vec![];
+        let is_explicit = endian.is_some();
+        let endian_value = endian.unwrap_or(self.endian);
+
+        if let Some(inner) = rtype.strip_prefix("Vec<").and_then(|v| v.strip_suffix('>')) {
+            let mut repeat = vec![];
+
+            if modifiers.has("remaining") {
+                repeat.push("repeat: eos".to_string());
+            }
+            else if let Some(count) = modifiers.value("count") {
+                repeat.push("repeat: expr".to_string());
+                repeat.push(format!("repeat-expr: {}", to_yaml(&translate_expr(count))));
+            }
+            else if modifiers.get("try_count").is_some() {
+                repeat.push("repeat: eos".to_string());
+            }
+            else {
+                let size = modifiers.value("byte_count_outside").and_then(parse_int).unwrap_or(1) as usize;
+                prefix.push(self.prefix_entry(&format!("{id}_count"), size, endian));
+                repeat.push("repeat: expr".to_string());
+                repeat.push(format!("repeat-expr: {id}_count"));
+            }
+
+            // The bytes are exported as a byte array instead of the repeated `u1`.
+            if inner == "u8" {
+                return Some(match repeat.last() {
+                    Some(line) if line.starts_with("repeat-expr: ") => vec![line.replacen("repeat-expr", "size", 1)],
+                    _ => vec!["size-eos: true".to_string()],
+                });
+            }
+
+            let mut element_prefix = vec![];
+            lines = self.type_lines(path, id, field, inner, modifiers, endian, &mut element_prefix)?;
+
+            // The element has its own length prefix.
+            if !element_prefix.is_empty() {
+                self.report_type(path, field.rtype);
+            }
+
+            lines.extend(repeat);
+        }
+        else if let Some((inner, length)) = rtype.strip_prefix('[').and_then(|v| v.strip_suffix(']')).and_then(|v| v.split_once(';')) {
+            let length = translate_expr(length);
+
+            if inner.trim() == "u8" {
+                return Some(vec![format!("size: {}", to_yaml(&length))]);
+            }
+
+            lines = self.type_lines(path, id, field, inner.trim(), modifiers, endian, prefix)?;
+            lines.push("repeat: expr".to_string());
+            lines.push(format!("repeat-expr: {}", to_yaml(&length)));
+        }
+        else if let Some(inner) = rtype.strip_prefix("Option<").and_then(|v| v.strip_suffix('>')) {
+            lines = self.type_lines(path, id, field, inner, modifiers, endian, prefix)?;
+
+            // The value is `None` if it can't be decoded, eg: at the end of the input.
+            if modifiers.get("if_expr").is_none() {
+                lines.push("if: not _io.eof".to_string());
+            }
+        }
+        else if let Some(inner) = ["Box<", "Lazy<"].iter().find_map(|v| rtype.strip_prefix(v)).and_then(|v| v.strip_suffix('>')) {
+            return self.type_lines(path, id, field, inner, modifiers, endian, prefix);
+        }
+        else if let Some((size, signed)) = get_int_type(rtype) {
+            // The integer of `length` bytes is unsigned, the expression `length` is unsupported.
+            let (size, signed) = match modifiers.get("length").and_then(|v| parse_int(v.value.unwrap_or_default())) {
+                Some(length) => {
+                    modifiers.take("length");
+                    (length as usize, false)
+                },
+                None => (size, signed),
+            };
+
+            match to_int_type(size, signed, endian_value, is_explicit) {
+                Some(rtype) => lines.push(format!("type: {rtype}")),
+                None => {
+                    self.report_type(path, field.rtype);
+                    lines.push(format!("size: {size}"));
+                },
+            }
+        }
+        else if rtype == "f32" || rtype == "f64" {
+            let suffix = if is_explicit { endian_value } else { "" };
+            lines.push(format!("type: f{}{suffix}", if rtype == "f32" { 4 } else { 8 }));
+        }
+        else if rtype == "bool" {
+            lines.push("type: u1".to_string());
+        }
+        else if rtype == "String" || rtype == "str" {
+            let is_cstr = modifiers.has("cstr");
+            lines.push(format!("type: {}", if is_cstr { "strz" } else { "str" }));
+
+            if !is_cstr || modifiers.get("length").is_some() {
+                let size = self.size_lines(path, id, modifiers, endian, prefix);
+                lines.extend(size);
+            }
+
+            lines.push(format!("encoding: {}", to_encoding(modifiers.value("encoding"))));
+        }
+        else if rtype == "[u8]" {
+            let is_cstr = modifiers.has("cstr");
+
+            if !is_cstr || modifiers.get("length").is_some() {
+                let size = self.size_lines(path, id, modifiers, endian, prefix);
+                lines.extend(size);
+            }
+
+            if is_cstr {
+                lines.push("terminator: 0".to_string());
+            }
+        }
+        else if let Some(size) = [("MacAddress", 6), ("Ipv4Addr", 4), ("Ipv6Addr", 16)].iter().find(|v| v.0 == rtype).map(|v| v.1) {
+            lines.push(format!("size: {size}"));
+        }
+        else if rtype == "()" || rtype.starts_with("PhantomData") {
+            return None;
+        }
+        else if let Some(schema) = field.schema.filter(|v| rtype.split('<').next().unwrap_or_default().rsplit("::").next() == Some(v.name)) {
+            if is_explicit {
+                // The byte order of the nested type is its own or inherited.
+                if let Some(modifier) = modifiers.get("byteorder") {
+                    self.report(path, modifier);
+                }
+            }
+
+            match schema.body {
+                SchemaBody::Enum(_) => match (modifiers.value("branch"), get_branch_modifier(schema)) {
+                    (_, Some(modifier)) => {
+                        self.report(path, modifier);
+                        lines.push("size-eos: true".to_string());
+                    },
+                    (Some(branch), None) => lines.extend(self.switch_lines(schema, &translate_expr(branch))),
+                    (None, None) => {
+                        self.push_job(Job::Type(schema));
+                        lines.push(format!("type: {}", to_snake_case(schema.name)));
+                    },
+                },
+                SchemaBody::Struct(_) => {
+                    self.push_job(Job::Type(schema));
+                    lines.push(format!("type: {}", to_snake_case(schema.name)));
+                },
+            }
+        }
+        else {
+            // The type parameters and the types which don't implement `Describe` are raw bytes.
+            self.report_type(path, field.rtype);
+            lines.push("size-eos: true".to_string());
+        }
+
+        Some(lines)
+    }
+}
@@ -0,0 +1,877 @@
+use crate::std::*;
+use crate::schema::{Schema, SchemaBody, FieldSchema, VariantSchema, Modifier};
+use super::{
+    Export, Unsupported, Job, Modifiers,
+    to_snake_case, parse_int, parse_bytes, get_int_type, strip_type, translate_expr,
+    get_endian, get_variant_id, get_branch_modifier,
+};
+
+
+/// The Lua keywords which are kept in the translated expressions.
+const LUA_KEYWORDS: &[&str] = &["and", "or", "not", "true", "false", "nil"];
+
+
+/// Exports the schema of a `#[derive(Describe)]` type to a Wireshark Lua dissector.
+///
+/// The fields are `ProtoField`s of the protocol which is named by the type, eg: `HttpHeader` => `http_header`,
+/// the integers are read by `tvb` in the byte order of the field/container, the nested structs and
+/// the enum branches are subtrees. The decoded values are kept in a table, so the `length`/`count`/
+/// `branch`/`if_expr` expressions are evaluated at runtime.
+/// The fields which can't be expressed are added as raw bytes, they are returned by `Export::unsupported`.
+///
+/// # Example
+///
+/// ```
+/// use jbytes_derive::{ByteDecode, ByteEncode, Describe};
+/// use jbytes::prelude::*;
+///
+///
+/// #[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, Describe)]
+/// #[jbytes(byteorder="LE")]
+/// pub struct LuaExample {
+///     pub length: u16,
+///     #[jbytes(length="length")]
+///     pub value: String,
+/// }
+///
+///
+/// let export = jbytes::export::to_lua(&LuaExample::SCHEMA);
+/// assert_eq!(export.document.contains("fields.lua_example_length = ProtoField.uint16(\"lua_example.length\", \"length\", base.DEC)"), true);
+/// assert_eq!(export.document.contains("v.length = tvb(offset, 2):le_uint()"), true);
+/// assert_eq!(export.unsupported.is_empty(), true);
+/// ```
+pub fn to_lua(schema: &'static Schema) -> Export {
+    let mut exporter = LuaExporter { proto: to_snake_case(schema.name), ..Default::default() };
+
+    exporter.root_endian = match schema.get("byteorder").and_then(|v| get_endian(v.value)) {
+        Some(Some(endian)) => endian,
+        _ => "be",
+    };
+
+    exporter.pending.push(Job::Type(schema));
+    exporter.done.push(Job::Type(schema));
+
+    let mut functions = vec![];
+
+    while !exporter.pending.is_empty() {
+        let job = exporter.pending.remove(0);
+
+        match job {
+            Job::Type(schema) | Job::Variants(schema) => functions.push((to_snake_case(schema.name), exporter.type_function(schema))),
+        }
+    }
+
+    let proto = &exporter.proto;
+    let mut lines = vec![
+        format!("-- This dissector is generated by jbytes from `{}`.", schema.name),
+        String::new(),
+        format!("local {proto} = Proto({proto:?}, {:?})", schema.name),
+        format!("local fields = {proto}.fields"),
+        String::new(),
+    ];
+
+    lines.extend(exporter.fields.iter().cloned());
+
+    if functions.iter().any(|v| v.1.iter().any(|v| v.contains("find_needle("))) {
+        lines.extend([
+            "",
+            "-- Returns the length of the bytes before `needle`, or the remaining length if it isn't found.",
+            "local function find_needle(tvb, offset, needle)",
+            "    local index = string.find(tvb:raw(offset), needle, 1, true)",
+            "",
+            "    if index == nil then",
+            "        return tvb:len() - offset",
+            "    end",
+            "",
+            "    return index - 1",
+            "end",
+        ].iter().map(|v| v.to_string()));
+    }
+
+    lines.push(String::new());
+    lines.push(format!("local {}", functions.iter().map(|v| format!("dissect_{}", v.0)).collect::<Vec<String>>().join(", ")));
+
+    for (_, function) in functions {
+        lines.push(String::new());
+        lines.extend(function);
+    }
+
+    lines.extend([
+        String::new(),
+        format!("function {proto}.dissector(tvb, pinfo, tree)"),
+        format!("    pinfo.cols.protocol = {:?}", schema.name),
+        format!("    local subtree = tree:add({proto}, tvb())"),
+        format!("    dissect_{proto}(tvb, subtree, 0)"),
+        "end".to_string(),
+        String::new(),
+        format!("-- Registers the dissector, eg: DissectorTable.get(\"udp.port\"):add(9000, {proto})"),
+    ]);
+
+    let mut document = lines.join("\n");
+    document.push('\n');
+
+    Export { document, unsupported: exporter.unsupported }
+}
+
+
+/// This is a context of the generated code of a field.
+struct Context<'a> {
+    /// The type which defines the field, eg: `http_header`.
+    type_id: &'a str,
+    id: &'a str,
+    name: &'a str,
+    /// The Lua variable of the decoded value, eg: `v.length`.
+    target: String,
+    /// The Lua variable of the tree item.
+    tree: &'a str,
+}
+
+
+impl Context<'_> {
+    fn path(&self) -> String {
+        format!("{}.{}", self.type_id, self.id)
+    }
+}
+
+
+#[derive(Default)]
+struct LuaExporter {
+    unsupported: Vec<Unsupported>,
+    pending: Vec<Job>,
+    done: Vec<Job>,
+    /// The protocol name and the prefix of the field abbreviations.
+    proto: String,
+    /// The `ProtoField` definitions.
+    fields: Vec<String>,
+    /// The field names of the current type, which are `v.<name>` in the expressions.
+    names: Vec<String>,
+    /// The endian of the document, which is inherited by `byteorder="inherit"`.
+    root_endian: &'static str,
+    /// The effective endian of the current type.
+    endian: &'static str,
+}
+
+
+/// Indents the lines of a block.
+fn indent(lines: Vec<String>) -> Vec<String> {
+    lines.into_iter().map(|v| if v.is_empty() { v } else { format!("    {v}") }).collect()
+}
+
+
+/// Returns a Lua string literal of the bytes, eg: `b"\r\n"` => `"\13\10"`.
+fn to_lua_string(value: &[u8]) -> String {
+    let mut result = String::from("\"");
+
+    for v in value {
+        match v {
+            b'"' | b'\\' => { result.push('\\'); result.push(*v as char); },
+            0x20..=0x7e => result.push(*v as char),
+            _ => result.push_str(&format!("\\{v}")),
+        }
+    }
+
+    result.push('"');
+    result
+}
+
+
+/// Returns the Wireshark encoding of an `encoding` modifier, eg: `utf16le` => `ENC_UTF_16 + ENC_LITTLE_ENDIAN`.
+fn to_encoding(value: Option<&str>) -> Option<&'static str> {
+    let value = match value.map(|v| v.to_ascii_lowercase()).as_deref() {
+        None | Some("utf8" | "utf-8") => "ENC_UTF_8",
+        Some("utf16le" | "utf-16le") => "ENC_UTF_16 + ENC_LITTLE_ENDIAN",
+        Some("utf16be" | "utf-16be") => "ENC_UTF_16 + ENC_BIG_ENDIAN",
+        Some("latin1") => "ENC_ISO_8859_1",
+        _ => return None,
+    };
+
+    Some(value)
+}
+
+
+/// Returns the Lua condition of a variant, eg: `branch_range="6..=8"` => `branch >= 6 and branch <= 8`,
+/// or `None` if it's the default variant.
+fn get_variant_condition(variant: &VariantSchema) -> Option<Result<String, &'static Modifier>> {
+    if variant.get("branch_default").is_some() {
+        return None;
+    }
+
+    if let Some(modifier) = variant.get("branch_range") {
+        let range = modifier.value.and_then(|v| v.split_once("..")).and_then(|(start, end)| {
+            match end.strip_prefix('=') {
+                Some(end) => Some(format!("branch >= {} and branch <= {}", parse_int(start)?, parse_int(end)?)),
+                None => Some(format!("branch >= {} and branch < {}", parse_int(start)?, parse_int(end)?)),
+            }
+        });
+
+        return Some(range.ok_or(modifier));
+    }
+
+    match (variant.branch, variant.get("branch_bits")) {
+        (Some(branch), _) => Some(Ok(format!("branch == {branch}"))),
+        (None, Some(modifier)) => Some(Err(modifier)),
+        (None, None) => Some(Err(&Modifier { key: "branch", value: None })),
+    }
+}
+
+
+impl LuaExporter {
+    fn report(&mut self, path: &str, modifier: &'static Modifier) {
+        let value = Unsupported { path: path.to_string(), key: modifier.key, value: modifier.value };
+
+        if !self.unsupported.contains(&value) {
+            self.unsupported.push(value);
+        }
+    }
+
+    fn report_type(&mut self, path: &str, rtype: &'static str) {
+        let value = Unsupported { path: path.to_string(), key: "type", value: Some(rtype) };
+
+        if !self.unsupported.contains(&value) {
+            self.unsupported.push(value);
+        }
+    }
+
+    fn push_job(&mut self, job: Job) {
+        if !self.done.contains(&job) {
+            self.pending.push(job);
+            self.done.push(job);
+        }
+    }
+
+    /// Adds a `ProtoField` definition and returns its reference, eg: `fields.http_header_key`.
+    fn add_field(&self, defs: &mut Vec<String>, type_id: &str, id: &str, name: &str, kind: &str, args: &str) -> String {
+        let key = format!("{type_id}_{id}");
+        let abbr = match type_id == self.proto {
+            true => format!("{}.{id}", self.proto),
+            false => format!("{}.{type_id}.{id}", self.proto),
+        };
+
+        defs.push(format!("fields.{key} = ProtoField.{kind}({abbr:?}, {name:?}{args})"));
+        format!("fields.{key}")
+    }
+
+    /// Translates a field expression to Lua, the fields are `v.<name>`, eg: `length - 4` => `v.length - 4`,
+    /// or `None` if it references an unknown name or calls a function.
+    fn to_lua_expr(&self, expr: &str) -> Option<String> {
+        let expr = translate_expr(expr).replace("!=", "~=");
+        let chars = expr.chars().collect::<Vec<char>>();
+        let mut result = String::new();
+        let mut index = 0;
+
+        while index < chars.len() {
+            let c = chars[index];
+
+            if !c.is_ascii_alphanumeric() && c != '_' {
+                result.push(c);
+                index += 1;
+                continue;
+            }
+
+            let start = index;
+
+            while index < chars.len() && (chars[index].is_ascii_alphanumeric() || chars[index] == '_') {
+                index += 1;
+            }
+
+            let word = chars[start..index].iter().collect::<String>();
+
+            if c.is_ascii_digit() {
+                result.push_str(&parse_int(&word)?.to_string());
+            }
+            else if chars.get(index) == Some(&'(') {
+                return None;
+            }
+            else if start > 0 && chars[start - 1] == '.' || LUA_KEYWORDS.contains(&word.as_str()) {
+                result.push_str(&word);
+            }
+            else if self.names.contains(&word) {
+                result.push_str(&format!("v.{word}"));
+            }
+            else {
+                return None;
+            }
+        }
+
+        Some(result)
+    }
+
+    /// Returns the own endian of a container and sets the effective endian.
+    fn set_endian(&mut self, path: &str, modifiers: &mut Modifiers) {
+        let endian = match modifiers.take("byteorder") {
+            Some(modifier) => get_endian(modifier.value).unwrap_or_else(|| {
+                self.report(path, modifier);
+                None
+            }),
+            None => Some("be"),
+        };
+
+        self.endian = endian.unwrap_or(self.root_endian);
+    }
+
+    /// Returns the dissect function of a type, it returns the next offset and the table of the decoded values.
+    fn type_function(&mut self, schema: &'static Schema) -> Vec<String> {
+        let type_id = to_snake_case(schema.name);
+        let mut modifiers = Modifiers::new(schema.modifiers);
+        let mut defs = vec![];
+        let mut code = vec![];
+        self.set_endian(&type_id, &mut modifiers);
+
+        let args = match schema.body {
+            SchemaBody::Struct(fields) => {
+                if let Some(magic) = modifiers.value("magic") {
+                    let magic = parse_bytes(magic).unwrap_or_else(|| magic.as_bytes().to_vec());
+                    let field = self.add_field(&mut defs, &type_id, "magic", "magic", "bytes", "");
+                    code.push(format!("tree:add({field}, tvb(offset, {}))", magic.len()));
+                    code.push(format!("offset = offset + {}", magic.len()));
+                }
+
+                code.extend(self.fields_code(&type_id, fields, "tree", &mut defs));
+                "tvb, tree, offset"
+            },
+            SchemaBody::Enum(variants) => {
+                let size = modifiers.value("byte_count").and_then(parse_int).unwrap_or(1) as usize;
+                modifiers.take("branch_type");
+                modifiers.take("byte_count_disable");
+
+                // The discriminant is read by the enum without the `branch` of the parent field.
+                let (read, add) = self.int_methods(size, false, self.endian);
+                let field = self.add_field(&mut defs, &type_id, "branch", "branch", &format!("uint{}", size * 8), ", base.DEC");
+                code.extend([
+                    "if branch == nil then".to_string(),
+                    format!("    tree:{add}({field}, tvb(offset, {size}))"),
+                    format!("    branch = tvb(offset, {size}):{read}()"),
+                    format!("    offset = offset + {size}"),
+                    "end".to_string(),
+                    String::new(),
+                ]);
+
+                code.extend(self.variants_code(schema, variants, &mut defs));
+                "tvb, tree, offset, branch"
+            },
+        };
+
+        for modifier in modifiers.unused() {
+            self.report(&type_id, modifier);
+        }
+
+        self.fields.extend(defs);
+
+        let mut lines = vec![format!("function dissect_{type_id}({args})"), "    local v = {}".to_string(), String::new()];
+
+        if !code.is_empty() {
+            lines.extend(indent(code));
+            lines.push(String::new());
+        }
+
+        lines.push("    return offset, v".to_string());
+        lines.push("end".to_string());
+        lines
+    }
+
+    /// Returns the `if`/`elseif` branches of the variants, the default variant is the `else` branch.
+    fn variants_code(&mut self, schema: &'static Schema, variants: &'static [VariantSchema], defs: &mut Vec<String>) -> Vec<String> {
+        let type_id = to_snake_case(schema.name);
+        let mut branches = vec![];
+        let mut default = None;
+
+        for variant in variants {
+            let variant_id = get_variant_id(schema, variant);
+            let mut modifiers = Modifiers::new(variant.modifiers);
+
+            let condition = match get_variant_condition(variant) {
+                Some(Ok(condition)) => Some(condition),
+                Some(Err(modifier)) => {
+                    self.report(&variant_id, modifier);
+                    modifiers.take(modifier.key);
+                    continue;
+                },
+                None => None,
+            };
+
+            for key in ["branch_value", "branch_range", "branch_default"] {
+                modifiers.take(key);
+            }
+
+            for modifier in modifiers.unused() {
+                self.report(&variant_id, modifier);
+            }
+
+            let field = self.add_field(defs, &type_id, &to_snake_case(variant.name), variant.name, "none", "");
+
+            let mut code = match variant.fields.is_empty() {
+                true => vec![format!("tree:add({field}, tvb(offset, 0))")],
+                false => {
+                    let mut code = vec![
+                        format!("local branch_tree = tree:add({field}, tvb(offset))"),
+                        "local start = offset".to_string(),
+                        String::new(),
+                    ];
+                    code.extend(self.fields_code(&variant_id, variant.fields, "branch_tree", defs));
+                    code.push(String::new());
+                    code.push("branch_tree:set_len(offset - start)".to_string());
+                    code
+                },
+            };
+
+            code = indent(code);
+
+            match condition {
+                Some(condition) => branches.push((condition, code)),
+                None => default = Some(code),
+            }
+        }
+
+        let mut lines = vec![];
+
+        for (index, (condition, code)) in branches.into_iter().enumerate() {
+            lines.push(format!("{} {condition} then", if index == 0 { "if" } else { "elseif" }));
+            lines.extend(code);
+        }
+
+        if let Some(code) = default {
+            match lines.is_empty() {
+                true => lines.push("do".to_string()),
+                false => lines.push("else".to_string()),
+            }
+
+            lines.extend(code);
+        }
+
+        if !lines.is_empty() {
+            lines.push("end".to_string());
+        }
+
+        lines
+    }
+
+    fn fields_code(&mut self, type_id: &str, fields: &'static [FieldSchema], tree: &str, defs: &mut Vec<String>) -> Vec<String> {
+        let names = core::mem::take(&mut self.names);
+        let mut code = vec![];
+
+        for field in fields {
+            let id = to_snake_case(field.name);
+            let name = field.name.trim_start_matches("r#");
+            let context = Context { type_id, id: &id, name, target: format!("v.{id}"), tree };
+
+            code.extend(self.field_code(&context, field, defs));
+            self.names.push(id);
+        }
+
+        self.names = names;
+        code
+    }
+
+    /// Returns the code of a field, it's raw bytes if any modifier or the type can't be expressed.
+    fn field_code(&mut self, context: &Context, field: &'static FieldSchema, defs: &mut Vec<String>) -> Vec<String> {
+        let path = context.path();
+        let mut modifiers = Modifiers::new(field.modifiers);
+
+        if modifiers.has("skip") || modifiers.has("skip_decode") {
+            return vec![];
+        }
+
+        // The discriminant field is assigned by the enum without reading.
+        if modifiers.has("discriminant") {
+            return vec![format!("{} = branch", context.target)];
+        }
+
+        let start = self.unsupported.len();
+        let mut is_expressed = true;
+        let mut field_defs = vec![];
+        let mut code = vec![];
+
+        if let Some(offset) = modifiers.get("offset") {
+            match offset.value.and_then(|v| self.to_lua_expr(v)) {
+                Some(offset) => {
+                    modifiers.take("offset");
+                    code.push(format!("offset = offset + {offset}"));
+                },
+                None => self.report(&path, offset),
+            }
+        }
+
+        if let Some(magic) = modifiers.value("magic") {
+            let magic = parse_bytes(magic).unwrap_or_else(|| magic.as_bytes().to_vec());
+            let field = self.add_field(&mut field_defs, context.type_id, context.id, context.name, "bytes", "");
+            code.push(format!("{}:add({field}, tvb(offset, {}))", context.tree, magic.len()));
+            code.push(format!("offset = offset + {}", magic.len()));
+        }
+        else {
+            let endian = match modifiers.take("byteorder") {
+                Some(modifier) => get_endian(modifier.value).unwrap_or_else(|| {
+                    self.report(&path, modifier);
+                    None
+                }),
+                None => None,
+            };
+
+            match self.value_code(context, field, strip_type(field.rtype).as_str(), &mut modifiers, endian, &mut field_defs) {
+                Some(value) => code.extend(value),
+                None => is_expressed = false,
+            }
+        }
+
+        // The value isn't verified by the dissector.
+        modifiers.take("check_value");
+
+        if modifiers.has("untake") {
+            code.insert(0, "local offset = offset".to_string());
+            code = [vec!["do".to_string()], indent(code), vec!["end".to_string()]].concat();
+        }
+
+        if let Some(modifier) = modifiers.get("if_expr") {
+            match modifier.value.and_then(|v| self.to_lua_expr(v)) {
+                Some(condition) => {
+                    modifiers.take("if_expr");
+                    code = [vec![format!("if {condition} then")], indent(code), vec!["end".to_string()]].concat();
+                },
+                None => self.report(&path, modifier),
+            }
+        }
+
+        for modifier in modifiers.unused() {
+            self.report(&path, modifier);
+        }
+
+        // The type isn't expressed without an unsupported modifier, eg: a type parameter.
+        if !is_expressed && self.unsupported.len() == start {
+            self.report_type(&path, field.rtype);
+        }
+
+        if self.unsupported.len() == start {
+            defs.extend(field_defs);
+            return code;
+        }
+
+        // The raw bytes of the fixed-size types, or the remaining bytes.
+        let raw = self.add_field(defs, context.type_id, context.id, context.name, "bytes", "");
+
+        match get_fixed_size(strip_type(field.rtype).as_str(), &Modifiers::new(field.modifiers)) {
+            Some(size) => vec![
+                format!("{}:add({raw}, tvb(offset, {size}))", context.tree),
+                format!("offset = offset + {size}"),
+            ],
+            None => vec![
+                format!("{}:add({raw}, tvb(offset))", context.tree),
+                "offset = tvb:len()".to_string(),
+            ],
+        }
+    }
+
+    /// Returns the read and the add methods of an integer, eg: `le_uint`/`add_le`.
+    fn int_methods(&self, size: usize, signed: bool, endian: &str) -> (String, &'static str) {
+        let read = format!("{}{}{}", if endian == "le" { "le_" } else { "" }, if signed { "int" } else { "uint" }, if size > 4 { "64" } else { "" });
+        (read, if endian == "le" { "add_le" } else { "add" })
+    }
+
+    /// Returns the code which reads the bytes/string of a length, the length is the local `length`.
+    fn size_code(&mut self, context: &Context, modifiers: &mut Modifiers, endian: Option<&'static str>, defs: &mut Vec<String>) -> Option<(Vec<String>, usize)> {
+        let mut code = vec![];
+        let mut skip = 0;
+
+        if let Some(length) = modifiers.get("length") {
+            code.push(format!("local length = {}", self.to_lua_expr(length.value?)?));
+            modifiers.take("length");
+        }
+        else if let Some(size) = modifiers.value("byte_count").and_then(parse_int) {
+            let size = size as usize;
+            let (read, add) = self.int_methods(size, false, endian.unwrap_or(self.endian));
+            let field = self.add_field(defs, context.type_id, &format!("{}_len", context.id), &format!("{}_len", context.name), &format!("uint{}", size * 8), ", base.DEC");
+
+            code.push(format!("{}:{add}({field}, tvb(offset, {size}))", context.tree));
+            code.push(format!("local length = tvb(offset, {size}):{read}()"));
+            code.push(format!("offset = offset + {size}"));
+        }
+        else if modifiers.get("cstr").is_some() {
+            code.push("local length = tvb(offset):strsize()".to_string());
+        }
+        else if let Some(modifier) = modifiers.get("linend") {
+            let needle = modifier.value.and_then(|v| parse_bytes(v).or_else(|| Some(v.as_bytes().to_vec())))?;
+            modifiers.take("linend");
+            code.push(format!("local length = find_needle(tvb, offset, {})", to_lua_string(&needle)));
+            skip = needle.len();
+        }
+        else if modifiers.has("remaining") {
+            code.push("local length = tvb:len() - offset".to_string());
+        }
+        else {
+            let field = self.add_field(defs, context.type_id, &format!("{}_len", context.id), &format!("{}_len", context.name), "uint8", ", base.DEC");
+
+            code.push(format!("{}:add({field}, tvb(offset, 1))", context.tree));
+            code.push("local length = tvb(offset, 1):uint()".to_string());
+            code.push("offset = offset + 1".to_string());
+        }
+
+        Some((code, skip))
+    }
+
+    /// Returns the code which reads a value at `offset`, adds it to the tree and moves `offset`,
+    /// or `None` if the type can't be expressed.
+    #[allow(clippy::too_many_arguments)]
+    fn value_code(&mut self, context: &Context, field: &'static FieldSchema, rtype: &str, modifiers: &mut Modifiers,
+                  endian: Option<&'static str>, defs: &mut Vec<String>) -> Option<Vec<String>>
+    {
+        let (tree, target) = (context.tree, &context.target);
+        let endian_value = endian.unwrap_or(self.endian);
+        let mut code = vec![];
+
+        if let Some(inner) = rtype.strip_prefix("Vec<").and_then(|v| v.strip_suffix('>')) {
+            let header = if modifiers.has("remaining") {
+                "while offset < tvb:len() do".to_string()
+            }
+            else if let Some(count) = modifiers.get("count") {
+                let count = self.to_lua_expr(count.value?)?;
+                modifiers.take("count");
+                code.push(format!("local count = {count}"));
+                "for _ = 1, count do".to_string()
+            }
+            else if modifiers.get("try_count").is_some() {
+                return None;
+            }
+            else {
+                let size = modifiers.value("byte_count_outside").and_then(parse_int).unwrap_or(1) as usize;
+                let (read, add) = self.int_methods(size, false, endian_value);
+                let field = self.add_field(defs, context.type_id, &format!("{}_count", context.id), &format!("{}_count", context.name), &format!("uint{}", size * 8), ", base.DEC");
+
+                code.push(format!("{tree}:{add}({field}, tvb(offset, {size}))"));
+                code.push(format!("local count = tvb(offset, {size}):{read}()"));
+                code.push(format!("offset = offset + {size}"));
+                "for _ = 1, count do".to_string()
+            };
+
+            // The bytes are added as a byte array instead of the repeated `uint8`.
+            if inner == "u8" {
+                let field = self.add_field(defs, context.type_id, context.id, context.name, "bytes", "");
+
+                if header.starts_with("while") {
+                    code.push("local count = tvb:len() - offset".to_string());
+                }
+
+                code.push(format!("{target} = tvb(offset, count):bytes()"));
+                code.push(format!("{tree}:add({field}, tvb(offset, count))"));
+                code.push("offset = offset + count".to_string());
+            }
+            else {
+                let element = Context { target: format!("{target}[#{target} + 1]"), ..*context };
+                let element_code = self.value_code(&element, field, inner, modifiers, endian, defs)?;
+
+                code.push(format!("{target} = {{}}"));
+                code.push(header);
+                code.extend(indent(element_code));
+                code.push("end".to_string());
+            }
+
+            return Some([vec!["do".to_string()], indent(code), vec!["end".to_string()]].concat());
+        }
+
+        if let Some((inner, length)) = rtype.strip_prefix('[').and_then(|v| v.strip_suffix(']')).and_then(|v| v.split_once(';')) {
+            let length = parse_int(length)?;
+
+            if inner.trim() == "u8" {
+                let field = self.add_field(defs, context.type_id, context.id, context.name, "bytes", "");
+                code.push(format!("{tree}:add({field}, tvb(offset, {length}))"));
+                code.push(format!("{target} = tvb(offset, {length}):bytes()"));
+                code.push(format!("offset = offset + {length}"));
+                return Some(code);
+            }
+
+            let element = Context { target: format!("{target}[#{target} + 1]"), ..*context };
+            let element_code = self.value_code(&element, field, inner.trim(), modifiers, endian, defs)?;
+
+            code.push(format!("{target} = {{}}"));
+            code.push(format!("for _ = 1, {length} do"));
+            code.extend(indent(element_code));
+            code.push("end".to_string());
+            return Some(code);
+        }
+
+        if let Some(inner) = rtype.strip_prefix("Option<").and_then(|v| v.strip_suffix('>')) {
+            let code = self.value_code(context, field, inner, modifiers, endian, defs)?;
+
+            // The value is `None` if it can't be decoded, eg: at the end of the input.
+            return match modifiers.get("if_expr") {
+                Some(_) => Some(code),
+                None => Some([vec!["if offset < tvb:len() then".to_string()], indent(code), vec!["end".to_string()]].concat()),
+            };
+        }
+
+        if let Some(inner) = ["Box<", "Lazy<"].iter().find_map(|v| rtype.strip_prefix(v)).and_then(|v| v.strip_suffix('>')) {
+            return self.value_code(context, field, inner, modifiers, endian, defs);
+        }
+
+        if let Some((size, signed)) = get_int_type(rtype) {
+            // The integer of `length` bytes is unsigned.
+            let (size, signed) = match modifiers.get("length") {
+                Some(length) => {
+                    let length = parse_int(length.value?)? as usize;
+                    modifiers.take("length");
+                    (length, false)
+                },
+                None => (size, signed),
+            };
+
+            if !matches!(size, 1..=4 | 8) {
+                return None;
+            }
+
+            let kind = format!("{}int{}", if signed { "" } else { "u" }, if size == 3 { 24 } else { size * 8 });
+            let (read, add) = self.int_methods(size, signed, endian_value);
+            let range = format!("tvb(offset, {size})");
+
+            match modifiers.get("bits").or(modifiers.get("bits_start")) {
+                Some(bits) => {
+                    let mask = parse_int(bits.value?)?;
+
+                    // The `bit` library of Wireshark is 32 bits.
+                    if size > 4 || mask == 0 {
+                        return None;
+                    }
+
+                    modifiers.take("bits");
+                    modifiers.take("bits_start");
+
+                    let field = self.add_field(defs, context.type_id, context.id, context.name, &kind, &format!(", base.DEC, nil, {mask:#x}"));
+
+                    code.push(match mask.trailing_zeros() {
+                        0 => format!("{target} = bit.band({range}:{read}(), {mask:#x})"),
+                        shift => format!("{target} = bit.rshift(bit.band({range}:{read}(), {mask:#x}), {shift})"),
+                    });
+                    code.push(format!("{tree}:{add}({field}, {range})"));
+                },
+                None => {
+                    let field = self.add_field(defs, context.type_id, context.id, context.name, &kind, ", base.DEC");
+                    code.push(format!("{target} = {range}:{read}()"));
+                    code.push(format!("{tree}:{add}({field}, {range})"));
+                },
+            }
+
+            code.push(format!("offset = offset + {size}"));
+            return Some(code);
+        }
+
+        if let Some((kind, size)) = [("f32", ("float", 4)), ("f64", ("double", 8)), ("bool", ("bool", 1)),
+            ("MacAddress", ("ether", 6)), ("Ipv4Addr", ("ipv4", 4)), ("Ipv6Addr", ("ipv6", 16))].iter().find(|v| v.0 == rtype).map(|v| v.1)
+        {
+            let (prefix, add) = if endian_value == "le" && size > 1 { ("le_", "add_le") } else { ("", "add") };
+            let range = format!("tvb(offset, {size})");
+
+            let value = match kind {
+                "float" | "double" => format!("{range}:{prefix}float()"),
+                "bool" => format!("{range}:uint() ~= 0"),
+                "ipv4" => format!("{range}:{prefix}ipv4()"),
+                // The little-endian MAC/IPv6 addresses can't be read by `tvb`.
+                _ if !prefix.is_empty() => return None,
+                _ => format!("{range}:{kind}()"),
+            };
+            let field = self.add_field(defs, context.type_id, context.id, context.name, kind, "");
+
+            code.push(format!("{target} = {value}"));
+
+            match kind {
+                "bool" => code.push(format!("{tree}:add({field}, {range}, {target})")),
+                _ => code.push(format!("{tree}:{add}({field}, {range})")),
+            }
+
+            code.push(format!("offset = offset + {size}"));
+            return Some(code);
+        }
+
+        if rtype == "String" || rtype == "str" || rtype == "[u8]" {
+            let is_cstr = modifiers.has("cstr");
+            let is_string = rtype != "[u8]";
+            let encoding = to_encoding(modifiers.get("encoding").and_then(|v| v.value))?;
+            modifiers.take("encoding");
+
+            // The padding is displayed with the value.
+            modifiers.take("pad");
+
+            let kind = match (is_string, is_cstr) {
+                (true, true) => "stringz",
+                (true, false) => "string",
+                (false, _) => "bytes",
+            };
+            let (size_code, skip) = self.size_code(context, modifiers, endian, defs)?;
+            let field = self.add_field(defs, context.type_id, context.id, context.name, kind, "");
+            let range = "tvb(offset, length)";
+
+            code.extend(size_code);
+            code.push(match (is_string, is_cstr) {
+                (true, true) => format!("{target} = {range}:stringz({encoding})"),
+                (true, false) => format!("{target} = {range}:string({encoding})"),
+                (false, _) => format!("{target} = {range}:bytes()"),
+            });
+            code.push(match is_string {
+                true => format!("{tree}:add({field}, {range}, {target})"),
+                false => format!("{tree}:add({field}, {range})"),
+            });
+            code.push(match skip {
+                0 => "offset = offset + length".to_string(),
+                skip => format!("offset = offset + length + {skip}"),
+            });
+
+            return Some([vec!["do".to_string()], indent(code), vec!["end".to_string()]].concat());
+        }
+
+        if rtype == "()" || rtype.starts_with("PhantomData") {
+            return Some(vec![]);
+        }
+
+        let schema = field.schema.filter(|v| rtype.split('<').next().unwrap_or_default().rsplit("::").next() == Some(v.name))?;
+
+        // The byte order of the nested type is its own or inherited.
+        if endian.is_some() {
+            return None;
+        }
+
+        let branch = match schema.body {
+            SchemaBody::Enum(_) => {
+                if let Some(modifier) = get_branch_modifier(schema) {
+                    self.report(&context.path(), modifier);
+                    return None;
+                }
+
+                match modifiers.get("branch") {
+                    Some(branch) => {
+                        let branch = self.to_lua_expr(branch.value?)?;
+                        modifiers.take("branch");
+                        format!(", {branch}")
+                    },
+                    None => String::new(),
+                }
+            },
+            SchemaBody::Struct(_) => String::new(),
+        };
+
+        self.push_job(Job::Type(schema));
+
+        let field = self.add_field(defs, context.type_id, context.id, context.name, "none", "");
+
+        Some(vec![
+            "do".to_string(),
+            format!("    local subtree = {tree}:add({field}, tvb(offset))"),
+            "    local start = offset".to_string(),
+            format!("    offset, {target} = dissect_{}(tvb, subtree, offset{branch})", to_snake_case(schema.name)),
+            "    subtree:set_len(offset - start)".to_string(),
+            "end".to_string(),
+        ])
+    }
+}
+
+
+/// Returns the size of the fixed-size types, eg: `u16`/`[u8; 4]`/`length=3`.
+fn get_fixed_size(rtype: &str, modifiers: &Modifiers) -> Option<usize> {
+    if let Some(length) = modifiers.get("length").and_then(|v| parse_int(v.value?)) {
+        return Some(length as usize);
+    }
+
+    if let Some((size, _)) = get_int_type(rtype) {
+        return Some(size);
+    }
+
+    if let Some((inner, length)) = rtype.strip_prefix('[').and_then(|v| v.strip_suffix(']')).and_then(|v| v.split_once(';')) {
+        return Some(get_fixed_size(inner.trim(), &Modifiers::new(&[]))? * parse_int(length)? as usize);
+    }
+
+    [("f32", 4), ("f64", 8), ("bool", 1), ("MacAddress", 6), ("Ipv4Addr", 4), ("Ipv6Addr", 16)].iter().find(|v| v.0 == rtype).map(|v| v.1)
+}
@@ -0,0 +1,284 @@
+//! This is a module of the exporters, which convert the `Schema` of a `#[derive(Describe)]` type to
+//! the definitions of the other tools, eg: Kaitai Struct/Wireshark.
+use crate::std::*;
+use crate::schema::{Schema, VariantSchema, Modifier};
+
+pub mod ksy;
+pub mod lua;
+
+pub use ksy::to_ksy;
+pub use lua::to_lua;
+
+
+/// The modifiers which don't change the decoded layout, eg: encode only.
+pub(crate) const IGNORED_KEYS: &[&str] = &[
+    "length_of", "count_of", "skip_encode", "value_encode", "full", "default_value", "range", "ctx", "bound",
+];
+
+
+/// The container modifiers of the non-integer branches, which can't be matched by an integer switch.
+pub(crate) const BRANCH_KEYS: &[&str] = &["branch_starts_with", "branch_starts_with_untake", "branch_take_bytes"];
+
+
+/// The maximum number of the `branch_range` values which are expanded to the switch cases.
+pub(crate) const MAX_RANGE_CASES: u128 = 16;
+
+
+/// This is a modifier/type of a field which can't be represented by the exporter,
+/// eg: `with` => `Unsupported { path: "example.body", key: "with", value: Some("custom") }`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unsupported {
+    /// The path of the field in its type, eg: `http_header.key`.
+    pub path: String,
+    /// The modifier key, or `type` for the unsupported Rust types.
+    pub key: &'static str,
+    pub value: Option<&'static str>,
+}
+
+
+/// This is an exported document with the modifiers which can't be represented.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Export {
+    pub document: String,
+    pub unsupported: Vec<Unsupported>,
+}
+
+
+/// Converts a type name to snake case, eg: `HttpRequest` => `http_request`, `r#type` => `type`, `0` => `v0`.
+pub(crate) fn to_snake_case(name: &str) -> String {
+    let mut result = String::new();
+    let mut is_lower = false;
+
+    for c in name.trim_start_matches("r#").chars() {
+        if c.is_ascii_uppercase() && is_lower {
+            result.push('_');
+        }
+
+        is_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        result.push(c.to_ascii_lowercase());
+    }
+
+    // The tuple fields are named by their index.
+    if result.starts_with(|v: char| v.is_ascii_digit()) {
+        result.insert(0, 'v');
+    }
+
+    result
+}
+
+
+/// Parses an integer literal, eg: `12`/`0x0f`/`0b1010`/`1_000`/`3usize`.
+pub(crate) fn parse_int(value: &str) -> Option<u128> {
+    const SUFFIXES: &[&str] = &["usize", "isize", "u128", "i128", "u64", "i64", "u32", "i32", "u16", "i16", "u8", "i8"];
+
+    let value = value.trim().replace('_', "");
+    let value = SUFFIXES.iter().find_map(|v| value.strip_suffix(v)).unwrap_or(&value);
+
+    match (value.strip_prefix("0x"), value.strip_prefix("0b")) {
+        (Some(value), _) => u128::from_str_radix(value, 16).ok(),
+        (_, Some(value)) => u128::from_str_radix(value, 2).ok(),
+        _ => value.parse().ok(),
+    }
+}
+
+
+/// Translates a field expression to the expression language of the exporters,
+/// eg: `r#type as usize == 1 && !flag` => `type == 1 and not flag`.
+pub(crate) fn translate_expr(expr: &str) -> String {
+    let expr = expr.replace("r#", "").replace("&&", " and ").replace("||", " or ");
+    let mut result = String::new();
+    let mut words = expr.split_whitespace().peekable();
+
+    while let Some(word) = words.next() {
+        // The casts are implicit, eg: `length as usize`, the punctuation after the type is kept, eg: `(length as usize)`.
+        if word == "as" && words.peek().is_some() {
+            let rtype = words.next().unwrap_or_default();
+            result.push_str(rtype.trim_start_matches(|c: char| c.is_ascii_alphanumeric() || c == '_' || c == ':'));
+            continue;
+        }
+
+        if !result.is_empty() {
+            result.push(' ');
+        }
+
+        match word.strip_prefix('!').filter(|v| !v.starts_with('=')) {
+            Some(value) => { result.push_str("not "); result.push_str(value); },
+            None => result.push_str(word),
+        }
+    }
+
+    result
+}
+
+
+/// Parses a byte string literal, eg: `b"\x89PNG\r\n"`.
+pub(crate) fn parse_bytes(value: &str) -> Option<Vec<u8>> {
+    let value = value.trim().strip_prefix("b\"")?.strip_suffix('"')?;
+    let mut result = vec![];
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.extend(c.to_string().as_bytes());
+            continue;
+        }
+
+        match chars.next()? {
+            'x' => result.push(u8::from_str_radix(&chars.by_ref().take(2).collect::<String>(), 16).ok()?),
+            'r' => result.push(b'\r'),
+            'n' => result.push(b'\n'),
+            't' => result.push(b'\t'),
+            '0' => result.push(0),
+            c => result.extend(c.to_string().as_bytes()),
+        }
+    }
+
+    Some(result)
+}
+
+
+/// Returns the size and the signedness of an integer type, eg: `u16` => (2, false).
+pub(crate) fn get_int_type(rtype: &str) -> Option<(usize, bool)> {
+    let value = match rtype {
+        "u8" => (1, false), "u16" => (2, false), "u32" => (4, false), "u64" => (8, false), "u128" => (16, false),
+        "i8" => (1, true), "i16" => (2, true), "i32" => (4, true), "i64" => (8, true), "i128" => (16, true),
+        "usize" => (core::mem::size_of::<usize>(), false),
+        "isize" => (core::mem::size_of::<isize>(), true),
+        _ => return None,
+    };
+
+    Some(value)
+}
+
+
+/// Strips the reference and the lifetime of a type, eg: `&'a str` => `str`, `Lazy<'a, Header>` => `Lazy<Header>`.
+pub(crate) fn strip_type(rtype: &str) -> String {
+    let mut result = String::new();
+    let mut chars = rtype.trim().trim_start_matches('&').chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\'' {
+            while chars.next_if(|v| v.is_alphanumeric() || *v == '_').is_some() {}
+            while chars.next_if(|v| *v == ',' || *v == ' ').is_some() {}
+            continue;
+        }
+
+        result.push(c);
+    }
+
+    result.trim().to_string()
+}
+
+
+/// This is a deferred type of the exported document, it's generated after the current type,
+/// so the unsupported modifiers of a field don't include the modifiers of its nested type.
+#[derive(Clone, Copy)]
+pub(crate) enum Job {
+    Type(&'static Schema),
+    /// The variants of an enum, which are the cases of a switch.
+    Variants(&'static Schema),
+}
+
+
+impl PartialEq for Job {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Job::Type(a), Job::Type(b)) | (Job::Variants(a), Job::Variants(b)) => a.name == b.name,
+            _ => false,
+        }
+    }
+}
+
+
+/// This is a tracker of the modifiers which are represented, the rest are reported as unsupported.
+pub(crate) struct Modifiers {
+    modifiers: &'static [Modifier],
+    used: Vec<bool>,
+}
+
+
+impl Modifiers {
+    pub(crate) fn new(modifiers: &'static [Modifier]) -> Self {
+        Self { modifiers, used: vec![false; modifiers.len()] }
+    }
+
+    /// Returns the modifier without marking it as represented.
+    pub(crate) fn get(&self, key: &str) -> Option<&'static Modifier> {
+        self.modifiers.iter().find(|v| v.key == key)
+    }
+
+    /// Returns the modifier and marks it as represented.
+    pub(crate) fn take(&mut self, key: &str) -> Option<&'static Modifier> {
+        let index = self.modifiers.iter().position(|v| v.key == key)?;
+        self.used[index] = true;
+        Some(&self.modifiers[index])
+    }
+
+    pub(crate) fn value(&mut self, key: &str) -> Option<&'static str> {
+        self.take(key).and_then(|v| v.value)
+    }
+
+    pub(crate) fn has(&mut self, key: &str) -> bool {
+        self.take(key).is_some()
+    }
+
+    pub(crate) fn unused(&self) -> Vec<&'static Modifier> {
+        self.modifiers.iter()
+            .zip(&self.used)
+            .filter(|(modifier, used)| !**used && !IGNORED_KEYS.contains(&modifier.key))
+            .map(|(modifier, _)| modifier)
+            .collect()
+    }
+}
+
+
+/// Returns the endian of a `byteorder` value, `Some(None)` is inherited, `None` is a variable.
+pub(crate) fn get_endian(value: Option<&str>) -> Option<Option<&'static str>> {
+    match value? {
+        v if v.eq_ignore_ascii_case("BE") => Some(Some("be")),
+        v if v.eq_ignore_ascii_case("LE") => Some(Some("le")),
+        "inherit" => Some(None),
+        _ => None,
+    }
+}
+
+
+#[inline]
+pub(crate) fn get_variant_id(schema: &Schema, variant: &VariantSchema) -> String {
+    format!("{}_{}", to_snake_case(schema.name), to_snake_case(variant.name))
+}
+
+
+/// Returns the switch keys of a variant, eg: `branch_range="6..=8"` => `6`/`7`/`8`.
+pub(crate) fn get_variant_keys(variant: &VariantSchema) -> Option<Vec<String>> {
+    if variant.get("branch_default").is_some() {
+        return Some(vec!["_".to_string()]);
+    }
+
+    if let Some(value) = variant.get("branch_range").and_then(|v| v.value) {
+        let (start, end) = value.split_once("..")?;
+        let (end, is_inclusive) = match end.strip_prefix('=') {
+            Some(end) => (end, true),
+            None => (end, false),
+        };
+        let (start, end) = (parse_int(start)?, parse_int(end)? + is_inclusive as u128);
+
+        if end.saturating_sub(start) > MAX_RANGE_CASES {
+            return None;
+        }
+
+        return Some((start..end).map(|v| v.to_string()).collect());
+    }
+
+    variant.branch.map(|v| vec![v.to_string()])
+}
+
+
+/// Returns the container modifier which matches the branches by bytes instead of an integer.
+pub(crate) fn get_branch_modifier(schema: &'static Schema) -> Option<&'static Modifier> {
+    if let Some(modifier) = BRANCH_KEYS.iter().find_map(|key| schema.get(key)) {
+        return Some(modifier);
+    }
+
+    schema.get("branch_type").filter(|v| get_int_type(v.value.unwrap_or_default()).is_none())
+}
@@ -0,0 +1,104 @@
+use core::{
+    mem,
+    marker::PhantomData,
+    net::{Ipv4Addr, Ipv6Addr},
+};
+use crate::types::MacAddress;
+
+
+/// This is a trait of the types with a fixed wire size, which is known at compile time.
+///
+/// The derive `#[derive(FixedSize)]` sums the sizes of fields, it's rejected if a field has a variable size,
+/// eg: `length`/`count` variables, `String` without a literal `length`.
+///
+/// # Example
+///
+/// ```
+/// use jbytes_derive::{ByteDecode, ByteEncode, FixedSize};
+/// use jbytes::prelude::*;
+///
+///
+/// #[derive(Debug, PartialEq, Eq, ByteDecode, ByteEncode, FixedSize)]
+/// pub struct HeaderExample {
+///     pub cmd: u8,
+///     #[jbytes(length=3)]
+///     pub address: u32,
+///     pub mac: MacAddress,
+///     pub values: [u16; 2],
+/// }
+///
+///
+/// assert_eq!(HeaderExample::SIZE, 14);
+/// assert_eq!(<(u8, [u32; 2])>::SIZE, 9);
+///
+/// // The buffers are sized statically, and the short frames are rejected before decoding.
+/// let buffer = [0; HeaderExample::SIZE];
+/// let parse = |data: &[u8]| -> JResult<HeaderExample> {
+///     if data.len() < HeaderExample::SIZE {
+///         return Err(make_error(data.len(), ErrorKind::InvalidByteLength));
+///     }
+///
+///     jbytes::decode(data)
+/// };
+/// assert_eq!(parse(&buffer).is_ok(), true);
+/// assert_eq!(parse(&buffer[..10]).unwrap_err().code, ErrorKind::InvalidByteLength);
+/// ```
+pub trait FixedSize {
+    /// The number of bytes of the encoded value.
+    const SIZE: usize;
+}
+
+
+macro_rules! impls_fixed_size {
+    ($($type:ty => $size:expr),+ $(,)?) => {
+        $(
+            impl FixedSize for $type {
+                const SIZE: usize = $size;
+            }
+        )+
+    };
+}
+
+
+impls_fixed_size!(
+    u8 => 1, u16 => 2, u32 => 4, u64 => 8, u128 => 16, usize => mem::size_of::<usize>(),
+    i8 => 1, i16 => 2, i32 => 4, i64 => 8, i128 => 16, isize => mem::size_of::<isize>(),
+    f32 => 4, f64 => 8,
+    bool => 1,
+    MacAddress => 6,
+    Ipv4Addr => 4,
+    Ipv6Addr => 16,
+);
+
+
+impl<T> FixedSize for PhantomData<T> {
+    const SIZE: usize = 0;
+}
+
+
+impl<T: FixedSize, const N: usize> FixedSize for [T; N] {
+    const SIZE: usize = T::SIZE * N;
+}
+
+
+macro_rules! impls_fixed_size_tuple {
+    ($($t:ident),+) => {
+        impl<$($t: FixedSize,)+> FixedSize for ($($t,)+) {
+            const SIZE: usize = 0 $(+ $t::SIZE)+;
+        }
+    };
+    () => {
+        impls_fixed_size_tuple!(T1);
+        impls_fixed_size_tuple!(T1, T2);
+        impls_fixed_size_tuple!(T1, T2, T3);
+        impls_fixed_size_tuple!(T1, T2, T3, T4);
+        impls_fixed_size_tuple!(T1, T2, T3, T4, T5);
+        impls_fixed_size_tuple!(T1, T2, T3, T4, T5, T6);
+        impls_fixed_size_tuple!(T1, T2, T3, T4, T5, T6, T7);
+        impls_fixed_size_tuple!(T1, T2, T3, T4, T5, T6, T7, T8);
+        impls_fixed_size_tuple!(T1, T2, T3, T4, T5, T6, T7, T8, T9);
+    };
+}
+
+
+impls_fixed_size_tuple!();
@@ -6,6 +6,7 @@
 //!
 //! #[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
 //! pub struct SimpleExample {
+//!     #[jbytes(length_of="value")]
 //!     pub length: u16,
 //!     #[jbytes(length="length")]
 //!     pub value: String,
@@ -37,6 +38,9 @@
 //!     assert_eq!(*jbytes::encode(value).unwrap(), input);
 //! }
 //! ```
+//!
+//! The attributes are validated at compile time, eg: meaningless combinations, unsupported types and unknown fields,
+//! the expected compile errors are checked in `tests/ui`.
 #![allow(clippy::needless_borrow)]
 
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -50,7 +54,7 @@ extern crate alloc;
 extern crate jbytes_derive;
 
 #[cfg(feature = "jbytes_derive")]
-pub use jbytes_derive::{ByteDecode, ByteEncode, BorrowByteDecode, BorrowByteEncode};
+pub use jbytes_derive::{ByteDecode, ByteEncode, BorrowByteDecode, BorrowByteEncode, ByteView, ByteViewMut, FixedSize, ByteGenerate, Describe};
 
 
 #[cfg(feature = "jdefault_derive")]
@@ -72,9 +76,19 @@ mod impls;
 
 pub mod modifiers;
 pub mod byteorder;
+pub mod encoding;
 pub mod decode;
 pub mod encode;
 pub mod types;
+pub mod checksum;
+pub mod branch;
+pub mod args;
+pub mod ctx;
+pub mod view;
+pub mod fixed_size;
+pub mod testing;
+pub mod schema;
+pub mod export;
 
 pub use buffer::Buffer;
 pub use bytes::{Bytes, ToBytes};
@@ -82,10 +96,15 @@ pub use buf_mut_traits::{BufReadMut, BufWriteMut};
 pub use buf_traits::{BufRead, BufWrite};
 pub use errors::{JResult, ErrorKind, make_error};
 
-pub use modifiers::{ContainerAttrModifiers, FieldAttrModifiers,  get_byteorder};
+pub use modifiers::{ContainerAttrModifiers, FieldAttrModifiers,  get_byteorder, get_code_unit_size, get_ctx};
 pub use byteorder::ByteOrder;
+pub use encoding::Encoding;
 pub use decode::{ByteDecode, BorrowByteDecode};
 pub use encode::{ByteEncode, BorrowByteEncode};
+pub use checksum::{Checksum, ChecksumHasher};
+pub use branch::BranchValue;
+pub use fixed_size::FixedSize;
+pub use schema::Describe;
 
 pub mod prelude {
     pub use crate::buffer::Buffer;
@@ -94,15 +113,20 @@ pub mod prelude {
     pub use crate::buf_traits::{BufRead, BufWrite};
     pub use crate::errors::{JResult, ErrorKind, make_error};
     
-    pub use crate::modifiers::{ContainerAttrModifiers, FieldAttrModifiers,  get_byteorder};
+    pub use crate::modifiers::{ContainerAttrModifiers, FieldAttrModifiers,  get_byteorder, get_code_unit_size, get_ctx};
     pub use crate::byteorder::ByteOrder;
+    pub use crate::encoding::Encoding;
     pub use crate::decode::{ByteDecode, BorrowByteDecode};
     pub use crate::encode::{ByteEncode, BorrowByteEncode};
+    pub use crate::checksum::{Checksum, ChecksumHasher};
+    pub use crate::branch::BranchValue;
+    pub use crate::fixed_size::FixedSize;
+    pub use crate::schema::Describe;
 
-    pub use crate::types::{MacAddress, NetAddress, HexString, HexBytes};
+    pub use crate::types::{MacAddress, NetAddress, IpPrefix, HexString, HexBytes};
 
     #[cfg(feature = "jbytes_derive")]
-    pub use jbytes_derive::{ByteDecode, ByteEncode, BorrowByteDecode, BorrowByteEncode};    
+    pub use jbytes_derive::{ByteDecode, ByteEncode, BorrowByteDecode, BorrowByteEncode, ByteView, ByteViewMut, FixedSize, ByteGenerate, Describe};
 
     #[cfg(feature = "jdefault_derive")]
     pub use jdefault_derive::Jdefault;
@@ -156,6 +180,52 @@ pub fn decode<I: AsRef<[u8]>, T: ByteDecode>(input: I) -> JResult<T> {
 }
 
 
+/// This is a decode function of byte stream with a user context, the context is reachable as `ctx` in
+/// the modifier expressions of the types with `#[jbytes(ctx="C")]`.
+///
+/// The context type is checked against `#[jbytes(ctx="C")]` of `T` and the nested types at compile time, see [ctx::ByteCtx].
+/// It's passed to the nested types as `&dyn Any`, so it must be `'static`. The context is taken by shared reference,
+/// as it's shared by all nested types, the mutable state can be kept in `Cell`/`RefCell` fields of the context.
+///
+/// # Example
+///
+/// ```
+/// use jbytes_derive::{ByteEncode, ByteDecode};
+///
+///
+/// pub struct Config {
+///     pub version: u8,
+/// }
+///
+///
+/// #[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+/// #[jbytes(ctx="Config")]
+/// pub struct CtxExample {
+///     pub length: u8,
+///     #[jbytes(length="length", if_expr="ctx.version > 1")]
+///     pub value: String,
+/// }
+///
+///
+/// fn main() {
+///     let ctx = Config { version: 2 };
+///     let value = jbytes::decode_with_ctx::<CtxExample, Config>(b"\x03\x31\x32\x33", &ctx).unwrap();
+///     assert_eq!(value, CtxExample { length: 3, value: "123".to_string() });
+///     assert_eq!(*jbytes::encode_with_ctx(value, &ctx).unwrap(), b"\x03\x31\x32\x33");
+///
+///     let ctx = Config { version: 1 };
+///     let value: CtxExample = jbytes::decode_with_ctx(b"\x00", &ctx).unwrap();
+///     assert_eq!(value, CtxExample { length: 0, value: "".to_string() });
+/// }
+/// ```
+#[inline]
+pub fn decode_with_ctx<T: ByteDecode + ctx::ByteCtx<args::ByteDecode, Ctx = C>, C: core::any::Any>(input: impl AsRef<[u8]>, ctx: &C) -> JResult<T> {
+    let cattr = ContainerAttrModifiers { ctx: Some(ctx), ..Default::default() };
+
+    T::decode_inner(&Bytes::new(input), Some(&cattr), None)
+}
+
+
 /// This is a decode function of byte stream.
 /// 
 /// # Example
@@ -261,6 +331,18 @@ pub fn encode<T: ByteEncode>(t: T) -> JResult<Buffer> {
 }
 
 
+/// This is a encode function of byte stream with a user context, see [decode_with_ctx].
+#[inline]
+pub fn encode_with_ctx<T: ByteEncode + ctx::ByteCtx<args::ByteEncode, Ctx = C>, C: core::any::Any>(t: T, ctx: &C) -> JResult<Buffer> {
+    let mut buf = Buffer::new();
+    let cattr = ContainerAttrModifiers { ctx: Some(ctx), ..Default::default() };
+
+    t.encode_inner(&mut buf, Some(&cattr), None)?;
+
+    Ok(buf)
+}
+
+
 /// This is a encode function of byte stream.
 /// 
 /// # Example
@@ -1,5 +1,6 @@
+use core::any::Any;
 use crate::std::*;
-use crate::ByteOrder;
+use crate::{ByteOrder, Encoding, BranchValue};
 
 
 #[derive(Debug, Clone)]
@@ -12,13 +13,17 @@ pub enum ModifierValue {
 
 
 #[derive(Debug, Default, Clone)]
-pub struct ContainerAttrModifiers {
+pub struct ContainerAttrModifiers<'a> {
     pub byteorder: Option<ByteOrder>,
+    // The byte order of the whole subtree, eg: `#[jbytes(byteorder_from="expr")]`
+    pub byteorder_subtree: Option<ByteOrder>,
     pub byte_count: Option<usize>,
     pub expr: Option<String>,
 
     // cache variable
     pub variable_name: RefCell<BTreeMap<String, usize>>,
+    // The user context of `decode_with_ctx`/`encode_with_ctx`, eg: `#[jbytes(ctx="MyCtx")]`
+    pub ctx: Option<&'a dyn Any>,
 }
 
 
@@ -30,6 +35,8 @@ pub struct FieldAttrModifiers<'a> {
     pub length: Option<usize>,
     // byte stream byteorder
     pub byteorder: Option<ByteOrder>,
+    // The byte order of the field and its subtree
+    pub byteorder_from: Option<ByteOrder>,
     // Unmoved byte stream
     pub untake: bool,
     pub linend: bool,
@@ -38,6 +45,19 @@ pub struct FieldAttrModifiers<'a> {
     pub bits_start: bool,
     pub byte_count: Option<usize>,
     pub remaining: bool,
+    // fixed-width string padding byte
+    pub pad: Option<u8>,
+    // NUL-terminated string
+    pub cstr: bool,
+    // string text encoding
+    pub encoding: Option<Encoding>,
+    // string length/byte_count counts code units
+    pub code_units: bool,
+
+    // integer text/decimal encodings
+    pub bcd: bool,
+    pub ascii_dec: bool,
+    pub ascii_hex: bool,
 
     // list/vec/..
     pub count: Option<usize>,
@@ -50,6 +70,10 @@ pub struct FieldAttrModifiers<'a> {
 
     // branch
     pub branch: Option<usize>,
+    // typed branch, eg: `&str`/`[u8]`/C-like enums/tuples
+    pub branch_value: Option<BranchValue<'a>>,
+    // The arguments of the nested type, eg: `#[jbytes(args(version="header.version"))]`, see `ByteArgs`.
+    pub args: Option<&'a dyn Any>,
     // pub branch_byte: Option<u8>,
     // pub branch_byteorder: Option<ByteOrder>,
     // pub branch_func: Option<String>,
@@ -61,10 +85,42 @@ pub struct FieldAttrModifiers<'a> {
 }
 
 
+impl<'a> ContainerAttrModifiers<'a> {
+    /// Returns the container modifiers of a nested struct/enum, the user context and the byte order of
+    /// `byteorder_from` are passed to the whole subtree, and the effective byte order of the parent is used
+    /// if `inherit` is true, eg: `#[jbytes(byteorder="inherit")]`.
+    pub fn nested(cattr: Option<&ContainerAttrModifiers<'a>>, fattr: Option<&FieldAttrModifiers>,
+        byteorder: Option<ByteOrder>, byte_count: Option<usize>, inherit: bool) -> Option<Self>
+    {
+        let ctx = cattr.and_then(|v| v.ctx);
+        let byteorder_subtree = fattr.and_then(|v| v.byteorder_from).or(cattr.and_then(|v| v.byteorder_subtree));
+        let byteorder = match byteorder {
+            Some(byteorder) => Some(byteorder),
+            None if inherit => fattr.and_then(|v| v.byteorder.or(v.byteorder_from)).or(cattr.and_then(|v| v.byteorder)).or(byteorder_subtree),
+            // The explicit byte order of the field overrides the subtree byte order.
+            None => byteorder_subtree.map(|v| fattr.and_then(|v| v.byteorder).unwrap_or(v)),
+        };
+
+        if byteorder.is_none() && byteorder_subtree.is_none() && byte_count.is_none() && ctx.is_none() {
+            return None;
+        }
+
+        Some(Self { byteorder, byteorder_subtree, byte_count, ctx, ..Default::default() })
+    }
+}
+
+
+/// Returns the user context of `decode_with_ctx`/`encode_with_ctx`, or None if the type mismatches.
+#[inline]
+pub fn get_ctx<'a, C: Any>(cattr: Option<&ContainerAttrModifiers<'a>>) -> Option<&'a C> {
+    cattr.and_then(|v| v.ctx).and_then(|v| v.downcast_ref::<C>())
+}
+
+
 #[inline]
 pub fn get_byteorder(cattr: Option<&ContainerAttrModifiers>, fattr: Option<&FieldAttrModifiers>) -> ByteOrder {
     if let Some(value) = fattr {
-        if let Some(byteorder) = value.byteorder {
+        if let Some(byteorder) = value.byteorder.or(value.byteorder_from) {
             return byteorder;
         }
     }
@@ -77,3 +133,16 @@ pub fn get_byteorder(cattr: Option<&ContainerAttrModifiers>, fattr: Option<&Fiel
 
     ByteOrder::Be
 }
+
+
+/// Returns the number of bytes of a text code unit, eg: 2 for UTF-16, otherwise 1.
+#[inline]
+pub fn get_code_unit_size(fattr: Option<&FieldAttrModifiers>) -> usize {
+    if let Some(value) = fattr {
+        if let Some(encoding) = value.encoding {
+            return encoding.code_unit_size();
+        }
+    }
+
+    1
+}
@@ -0,0 +1,193 @@
+use core::marker::PhantomData;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+
+/// This is a trait of the types which describe their layout at runtime, eg: field names, types, modifiers and branches.
+///
+/// The derive `#[derive(Describe)]` generates a static `Schema` tree which mirrors the `#[jbytes(..)]` attributes,
+/// the nested derived types are linked by `FieldSchema::schema`, eg: `Header`/`Vec<Header>`/`Option<Header>`.
+/// The tree is serialised to JSON with the `serde` feature.
+///
+/// # Example
+///
+/// ```
+/// use jbytes_derive::{ByteDecode, ByteEncode, Describe};
+/// use jbytes::prelude::*;
+/// use jbytes::schema::SchemaBody;
+///
+///
+/// #[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, Describe)]
+/// #[jbytes(byteorder="LE")]
+/// pub struct DescribeExample {
+///     pub length: u16,
+///     #[jbytes(length="length")]
+///     pub value: String,
+///     pub cmd: u8,
+///     #[jbytes(branch="cmd")]
+///     pub body: DescribeExampleBody,
+/// }
+///
+///
+/// #[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, Describe)]
+/// pub enum DescribeExampleBody {
+///     #[jbytes(branch_value=1)]
+///     Read {
+///         address: u8,
+///     },
+///     #[jbytes(branch_default)]
+///     Unknown,
+/// }
+///
+///
+/// let schema = &DescribeExample::SCHEMA;
+/// assert_eq!(schema.name, "DescribeExample");
+/// assert_eq!(schema.get("byteorder").unwrap().value, Some("LE"));
+///
+/// let fields = schema.fields();
+/// assert_eq!((fields[1].name, fields[1].rtype), ("value", "String"));
+/// assert_eq!(fields[1].get("length").unwrap().value, Some("length"));
+///
+/// // The nested derived types are linked.
+/// let body = fields[3].schema.unwrap();
+/// assert_eq!(body.name, "DescribeExampleBody");
+///
+/// if let SchemaBody::Enum(variants) = &body.body {
+///     assert_eq!((variants[0].name, variants[0].branch), ("Read", Some(1)));
+///     assert_eq!((variants[1].name, variants[1].branch), ("Unknown", None));
+/// }
+/// ```
+pub trait Describe {
+    /// The layout of the type.
+    const SCHEMA: Schema;
+}
+
+
+/// This is a modifier of `#[jbytes(..)]` with the source value,
+/// eg: `length="length - 4"` => `length`/`length - 4`, `untake` => `untake`/`None`.
+///
+/// The keys are canonical, eg: `byte_size` => `byte_count`, `end_with` => `linend`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Modifier {
+    pub key: &'static str,
+    pub value: Option<&'static str>,
+}
+
+
+/// This is a layout of the struct/enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct Schema {
+    pub name: &'static str,
+    /// The container modifiers, eg: `byteorder`/`byte_count`/`magic`/`branch_type`.
+    pub modifiers: &'static [Modifier],
+    pub body: SchemaBody,
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum SchemaBody {
+    Struct(&'static [FieldSchema]),
+    Enum(&'static [VariantSchema]),
+}
+
+
+/// This is a branch of the enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct VariantSchema {
+    pub name: &'static str,
+    /// The integer branch value which is encoded, eg: `branch_value=3`/the index of the variant,
+    /// it's `None` for `branch_default`/`branch_bits` and the non-integer branches, see the modifiers.
+    pub branch: Option<usize>,
+    /// The variant modifiers, eg: `branch_value`/`branch_range`/`branch_bits`/`branch_default`.
+    pub modifiers: &'static [Modifier],
+    pub fields: &'static [FieldSchema],
+}
+
+
+/// This is a field of the struct/enum variant, the tuple fields are named by their index, eg: `0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct FieldSchema {
+    pub name: &'static str,
+    /// The Rust type of the field, eg: `u16`/`Vec<Header>`/`[u8; 4]`.
+    pub rtype: &'static str,
+    pub modifiers: &'static [Modifier],
+    /// The schema of the nested derived type, eg: `Header` of `Header`/`Vec<Header>`/`[Header; 2]`/`Option<Header>`,
+    /// or `None` if the type doesn't implement `Describe`, eg: `u8`/`SocketAddr`/`Arc<Header>`.
+    pub schema: Option<&'static Schema>,
+}
+
+
+#[inline]
+fn get_modifier<'a>(modifiers: &'a [Modifier], key: &str) -> Option<&'a Modifier> {
+    modifiers.iter().find(|v| v.key == key)
+}
+
+
+impl Schema {
+    /// Returns the container modifier by its canonical key.
+    #[inline]
+    pub fn get(&self, key: &str) -> Option<&'static Modifier> {
+        get_modifier(self.modifiers, key)
+    }
+
+    /// Returns the fields of the struct, or empty for the enum.
+    #[inline]
+    pub fn fields(&self) -> &'static [FieldSchema] {
+        match self.body {
+            SchemaBody::Struct(fields) => fields,
+            SchemaBody::Enum(_) => &[],
+        }
+    }
+
+    /// Returns the variants of the enum, or empty for the struct.
+    #[inline]
+    pub fn variants(&self) -> &'static [VariantSchema] {
+        match self.body {
+            SchemaBody::Struct(_) => &[],
+            SchemaBody::Enum(variants) => variants,
+        }
+    }
+}
+
+
+impl VariantSchema {
+    /// Returns the variant modifier by its canonical key.
+    #[inline]
+    pub fn get(&self, key: &str) -> Option<&'static Modifier> {
+        get_modifier(self.modifiers, key)
+    }
+}
+
+
+impl FieldSchema {
+    /// Returns the field modifier by its canonical key.
+    #[inline]
+    pub fn get(&self, key: &str) -> Option<&'static Modifier> {
+        get_modifier(self.modifiers, key)
+    }
+}
+
+
+// The schema of a field type is selected by the inherent associated const, which shadows the trait one
+// when the type implements `Describe`, so the other types (eg: `SocketAddr`/`Arc<T>`) aren't linked.
+#[doc(hidden)]
+pub struct DescribeCheck<T: ?Sized>(PhantomData<T>);
+
+
+impl<T: Describe + ?Sized> DescribeCheck<T> {
+    pub const SCHEMA: Option<&'static Schema> = Some(&T::SCHEMA);
+}
+
+
+#[doc(hidden)]
+pub trait NoDescribe {
+    const SCHEMA: Option<&'static Schema> = None;
+}
+
+
+impl<T: ?Sized> NoDescribe for DescribeCheck<T> {}
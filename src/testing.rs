@@ -0,0 +1,358 @@
+use core::{
+    fmt::Debug,
+    mem,
+    marker::PhantomData,
+    net::{Ipv4Addr, Ipv6Addr},
+    ops::Range,
+};
+use crate::std::*;
+use crate::{
+    Buffer, Bytes, BufRead,
+    ByteDecode, ByteEncode,
+    FieldAttrModifiers,
+};
+use crate::types::MacAddress;
+
+
+/// The number of elements/characters of the values without a `length`/`count` modifier.
+pub const MAX_GENERATE_LENGTH: u64 = 16;
+
+
+/// This is a small seeded random number generator (SplitMix64) of `ByteGenerate`,
+/// the same seed generates the same values, so a failed case can be reproduced.
+///
+/// # Example
+///
+/// ```
+/// use jbytes::testing::Rng;
+///
+/// let mut rng = Rng::new(1);
+/// let value = rng.next_u64();
+/// assert_eq!(Rng::new(1).next_u64(), value);
+/// assert_eq!((0..100).all(|_| (3..7).contains(&rng.gen_range(3..7))), true);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+
+impl Rng {
+    #[inline]
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    #[inline]
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+
+        let mut value = self.state;
+        value = (value ^ (value >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94d049bb133111eb);
+        value ^ (value >> 31)
+    }
+
+    /// Returns a random value of the range, or the start of an empty range.
+    #[inline]
+    pub fn gen_range(&mut self, range: Range<u64>) -> u64 {
+        match range.end.checked_sub(range.start) {
+            Some(0) | None => range.start,
+            Some(size) => range.start + self.next_u64() % size,
+        }
+    }
+
+    #[inline]
+    pub fn gen_bool(&mut self) -> bool {
+        self.next_u64() & 0x01 == 0x01
+    }
+
+    #[inline]
+    fn gen_u128(&mut self) -> u128 {
+        ((self.next_u64() as u128) << 64) | self.next_u64() as u128
+    }
+
+    /// Returns an ASCII alphanumeric string, which doesn't contain the `linend`/`pad`/`cstr` bytes.
+    fn gen_string(&mut self, length: usize) -> String {
+        const CHARS: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+        (0..length).map(|_| CHARS[self.gen_range(0..CHARS.len() as u64) as usize] as char).collect()
+    }
+}
+
+
+/// Returns the number of elements/bytes of a value, `length`/`count` are respected.
+#[inline]
+fn get_generate_length(rng: &mut Rng, length: Option<usize>) -> usize {
+    match length {
+        Some(length) => length,
+        None => rng.gen_range(0..MAX_GENERATE_LENGTH + 1) as usize,
+    }
+}
+
+
+/// This is a trait of the random values for the property tests of encode/decode,
+/// the values respect the field modifiers, eg: the `length` of a string, the `count` of a list.
+///
+/// The derive `#[derive(ByteGenerate)]` generates the fields in order, the fields referenced by
+/// the `length`/`count` expressions are small integers, the fields referenced by `branch` are the branch values of the enum,
+/// the `check_value` fields are the expected values, the `magic`/`skip` fields are the default values.
+///
+/// # Example
+///
+/// ```
+/// use jbytes_derive::{ByteDecode, ByteEncode, ByteGenerate};
+/// use jbytes::prelude::*;
+/// use jbytes::testing::{Rng, assert_roundtrip};
+///
+///
+/// #[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, ByteGenerate)]
+/// pub struct GenerateExample {
+///     #[jbytes(check_value="0x01")]
+///     pub version: u8,
+///     pub length: u16,
+///     #[jbytes(length="length")]
+///     pub value: String,
+///     pub cmd: u8,
+///     #[jbytes(branch="cmd")]
+///     pub body: GenerateExampleBody,
+/// }
+///
+///
+/// #[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, ByteGenerate)]
+/// pub enum GenerateExampleBody {
+///     #[jbytes(branch_value=1)]
+///     Read {
+///         address: u8,
+///     },
+///     #[jbytes(branch_value=2)]
+///     Write {
+///         address: u8,
+///         count: u8,
+///         #[jbytes(count="count")]
+///         values: Vec<u16>,
+///     },
+/// }
+///
+///
+/// let mut rng = Rng::new(0);
+///
+/// for _ in 0..100 {
+///     let value: GenerateExample = jbytes::testing::generate(&mut rng);
+///     assert_eq!(value.version, 0x01);
+///     assert_eq!(value.value.len(), value.length as usize);
+///     assert_roundtrip(&value);
+/// }
+/// ```
+pub trait ByteGenerate: Sized {
+    fn generate_inner(rng: &mut Rng, fattr: Option<&FieldAttrModifiers>) -> Self;
+
+    /// Returns a random branch value of the enum variants, which is assigned to the field referenced by `branch`.
+    #[inline]
+    fn generate_branch(_rng: &mut Rng) -> Option<usize> {
+        None
+    }
+}
+
+
+/// This is a generate function of the random values.
+#[inline]
+pub fn generate<T: ByteGenerate>(rng: &mut Rng) -> T {
+    T::generate_inner(rng, None)
+}
+
+
+/// This is a roundtrip assertion of the property tests, which checks that encode/decode are mutually inverse:
+/// the value is decoded from its bytes without the remaining bytes, and the decoded value is encoded to the same bytes.
+///
+/// # Example
+///
+/// ```
+/// use jbytes::testing::{Rng, assert_roundtrip};
+///
+/// let mut rng = Rng::new(0);
+///
+/// for _ in 0..100 {
+///     assert_roundtrip(&jbytes::testing::generate::<(u8, Vec<u16>, String)>(&mut rng));
+/// }
+/// ```
+pub fn assert_roundtrip<T: ByteEncode + ByteDecode + PartialEq + Debug>(value: &T) {
+    let mut buffer = Buffer::new();
+
+    if let Err(e) = value.encode(&mut buffer) {
+        panic!("failed to encode {value:?}: {e:?}");
+    }
+
+    let input = Bytes::new(&buffer[..]);
+    let value_new = match T::decode(&input) {
+        Ok(value_new) => value_new,
+        Err(e) => panic!("failed to decode {value:?} from {:02x?}: {e:?}", &buffer[..]),
+    };
+
+    assert_eq!(&value_new, value, "the decoded value isn't equal to the encoded value, bytes: {:02x?}", &buffer[..]);
+    assert_eq!(input.remaining_len(), 0, "the bytes aren't decoded completely: {:02x?}", &buffer[..]);
+
+    let mut buffer_new = Buffer::new();
+
+    if let Err(e) = value_new.encode(&mut buffer_new) {
+        panic!("failed to encode {value_new:?}: {e:?}");
+    }
+
+    assert_eq!(&buffer_new[..], &buffer[..], "the decoded value isn't encoded to the same bytes");
+}
+
+
+macro_rules! impls_int_generate {
+    ($($type:ident),+) => {
+        $(
+            impl ByteGenerate for $type {
+                fn generate_inner(rng: &mut Rng, fattr: Option<&FieldAttrModifiers>) -> Self {
+                    let mut value = rng.gen_u128();
+
+                    if let Some(fr) = fattr {
+                        // The textual integers are small to fit the width of the digits.
+                        if fr.bcd || fr.ascii_dec || fr.ascii_hex {
+                            return rng.gen_range(0..if fr.length == Some(1) { 10 } else { 100 }) as $type;
+                        }
+
+                        if let Some(bits) = fr.bits {
+                            value &= bits.checked_shr(bits.trailing_zeros()).unwrap_or(0) as u128;
+                        }
+
+                        // The integer of `length` bytes is decoded without the sign extension.
+                        if let Some(length) = fr.length {
+                            if length < mem::size_of::<$type>() {
+                                value &= (1 << (length * 8)) - 1;
+                            }
+                        }
+                    }
+
+                    value as $type
+                }
+            }
+        )+
+    };
+}
+
+
+impls_int_generate!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+
+impl ByteGenerate for bool {
+    #[inline]
+    fn generate_inner(rng: &mut Rng, _fattr: Option<&FieldAttrModifiers>) -> Self {
+        rng.gen_bool()
+    }
+}
+
+
+// The floats are finite, `NaN` isn't equal to itself.
+impl ByteGenerate for f32 {
+    #[inline]
+    fn generate_inner(rng: &mut Rng, _fattr: Option<&FieldAttrModifiers>) -> Self {
+        rng.next_u64() as i32 as f32 / 16.0
+    }
+}
+
+
+impl ByteGenerate for f64 {
+    #[inline]
+    fn generate_inner(rng: &mut Rng, _fattr: Option<&FieldAttrModifiers>) -> Self {
+        rng.next_u64() as i64 as f64 / 16.0
+    }
+}
+
+
+impl ByteGenerate for String {
+    #[inline]
+    fn generate_inner(rng: &mut Rng, fattr: Option<&FieldAttrModifiers>) -> Self {
+        let length = get_generate_length(rng, fattr.and_then(|fr| fr.length));
+        rng.gen_string(length)
+    }
+}
+
+
+impl<T: ByteGenerate> ByteGenerate for Vec<T> {
+    #[inline]
+    fn generate_inner(rng: &mut Rng, fattr: Option<&FieldAttrModifiers>) -> Self {
+        let count = get_generate_length(rng, fattr.and_then(|fr| fr.count));
+        (0..count).map(|_| T::generate_inner(rng, fattr)).collect()
+    }
+}
+
+
+impl<T: ByteGenerate, const N: usize> ByteGenerate for [T; N] {
+    #[inline]
+    fn generate_inner(rng: &mut Rng, fattr: Option<&FieldAttrModifiers>) -> Self {
+        core::array::from_fn(|_| T::generate_inner(rng, fattr))
+    }
+}
+
+
+impl<T: ByteGenerate> ByteGenerate for Option<T> {
+    #[inline]
+    fn generate_inner(rng: &mut Rng, fattr: Option<&FieldAttrModifiers>) -> Self {
+        match fattr.and_then(|fr| fr.length) {
+            Some(0) => None,
+            _ => Some(T::generate_inner(rng, fattr)),
+        }
+    }
+}
+
+
+impl<T> ByteGenerate for PhantomData<T> {
+    #[inline]
+    fn generate_inner(_rng: &mut Rng, _fattr: Option<&FieldAttrModifiers>) -> Self {
+        PhantomData
+    }
+}
+
+
+impl ByteGenerate for MacAddress {
+    #[inline]
+    fn generate_inner(rng: &mut Rng, _fattr: Option<&FieldAttrModifiers>) -> Self {
+        MacAddress::from_bits(rng.next_u64() & 0xffff_ffff_ffff)
+    }
+}
+
+
+impl ByteGenerate for Ipv4Addr {
+    #[inline]
+    fn generate_inner(rng: &mut Rng, _fattr: Option<&FieldAttrModifiers>) -> Self {
+        Ipv4Addr::from(rng.next_u64() as u32)
+    }
+}
+
+
+impl ByteGenerate for Ipv6Addr {
+    #[inline]
+    fn generate_inner(rng: &mut Rng, _fattr: Option<&FieldAttrModifiers>) -> Self {
+        Ipv6Addr::from(rng.gen_u128())
+    }
+}
+
+
+macro_rules! impls_tuple_generate {
+    ($($t:ident),+) => {
+        impl<$($t: ByteGenerate,)+> ByteGenerate for ($($t,)+) {
+            #[inline]
+            fn generate_inner(rng: &mut Rng, fattr: Option<&FieldAttrModifiers>) -> Self {
+                ($($t::generate_inner(rng, fattr),)+)
+            }
+        }
+    };
+    () => {
+        impls_tuple_generate!(T1);
+        impls_tuple_generate!(T1, T2);
+        impls_tuple_generate!(T1, T2, T3);
+        impls_tuple_generate!(T1, T2, T3, T4);
+        impls_tuple_generate!(T1, T2, T3, T4, T5);
+        impls_tuple_generate!(T1, T2, T3, T4, T5, T6);
+        impls_tuple_generate!(T1, T2, T3, T4, T5, T6, T7);
+        impls_tuple_generate!(T1, T2, T3, T4, T5, T6, T7, T8);
+        impls_tuple_generate!(T1, T2, T3, T4, T5, T6, T7, T8, T9);
+    };
+}
+
+
+impls_tuple_generate!();
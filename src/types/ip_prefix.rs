@@ -0,0 +1,239 @@
+use core::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    str::FromStr,
+    fmt,
+};
+use crate::std::*;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Serializer, Deserialize, Deserializer, de::Error as DeError};
+use crate::errors::ThisError;
+
+
+#[derive(Debug, ThisError)]
+pub enum IpPrefixParseError {
+    #[error("invalid prefix: `{0}`")]
+    InvalidPrefix(String),
+}
+
+
+/// This is a CIDR prefix type, eg: `10.0.0.0/8`.
+///
+/// It uses the compact BGP/OSPF-style encoding: a prefix length byte, followed by only
+/// `ceil(len / 8)` address bytes. The family is IPv6 when `length=16`, otherwise IPv4.
+///
+/// # Example
+///
+/// ```
+/// use std::str::FromStr;
+/// use jbytes::{prelude::*, types::IpPrefix};
+///
+/// let prefix = IpPrefix::from_str("10.1.0.0/16").unwrap();
+/// assert_eq!(*jbytes::encode(prefix).unwrap(), [16, 10, 1]);
+/// assert_eq!(jbytes::decode::<_, IpPrefix>(&[16, 10, 1]).unwrap(), prefix);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct IpPrefix {
+    addr: IpAddr,
+    len: u8,
+}
+
+
+impl IpPrefix {
+    /// Creates a prefix, returns [None] if the prefix length exceeds the address bits.
+    #[inline]
+    pub fn new(addr: IpAddr, len: u8) -> Option<Self> {
+        if len as usize > Self::max_len(&addr) {
+            return None;
+        }
+
+        Some(Self { addr, len })
+    }
+
+    #[inline]
+    fn max_len(addr: &IpAddr) -> usize {
+        match addr {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        }
+    }
+
+    /// Returns the address of the prefix.
+    #[inline]
+    pub fn addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    /// Returns the prefix length in bits.
+    #[inline]
+    pub fn len(&self) -> u8 {
+        self.len
+    }
+
+    /// Returns [true] if this is a default route prefix, eg: `0.0.0.0/0`.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns [true] if this is a ipv4 prefix.
+    #[inline]
+    pub fn is_ipv4(&self) -> bool {
+        self.addr.is_ipv4()
+    }
+
+    /// Returns [true] if this is a ipv6 prefix.
+    #[inline]
+    pub fn is_ipv6(&self) -> bool {
+        self.addr.is_ipv6()
+    }
+
+    /// Returns the address with the host bits cleared.
+    pub fn network(&self) -> IpAddr {
+        match self.addr {
+            IpAddr::V4(addr) => {
+                let mask = u32::MAX.checked_shl(32 - self.len as u32).unwrap_or(0);
+                IpAddr::V4(Ipv4Addr::from(u32::from(addr) & mask))
+            },
+            IpAddr::V6(addr) => {
+                let mask = u128::MAX.checked_shl(128 - self.len as u32).unwrap_or(0);
+                IpAddr::V6(Ipv6Addr::from(u128::from(addr) & mask))
+            },
+        }
+    }
+
+    /// Returns the number of address bytes on the wire, `ceil(len / 8)`.
+    #[inline]
+    pub fn byte_len(&self) -> usize {
+        (self.len as usize).div_ceil(8)
+    }
+
+    /// Returns the address bytes on the wire, the host bits of the last byte are cleared.
+    pub fn octets(&self) -> Vec<u8> {
+        let data = match self.network() {
+            IpAddr::V4(addr) => addr.octets().to_vec(),
+            IpAddr::V6(addr) => addr.octets().to_vec(),
+        };
+
+        data[..self.byte_len()].to_vec()
+    }
+
+    /// Creates a prefix from the truncated address bytes, the missing bytes are zero.
+    pub fn from_octets(data: &[u8], len: u8, is_ipv6: bool) -> Option<Self> {
+        let addr = if is_ipv6 {
+            let mut octets = [0; 16];
+            octets.get_mut(..data.len())?.copy_from_slice(data);
+            IpAddr::V6(Ipv6Addr::from(octets))
+        }
+        else {
+            let mut octets = [0; 4];
+            octets.get_mut(..data.len())?.copy_from_slice(data);
+            IpAddr::V4(Ipv4Addr::from(octets))
+        };
+
+        Self::new(addr, len)
+    }
+}
+
+
+impl Default for IpPrefix {
+    fn default() -> Self {
+        Self { addr: IpAddr::V4(Ipv4Addr::UNSPECIFIED), len: 0 }
+    }
+}
+
+
+impl FromStr for IpPrefix {
+    type Err = IpPrefixParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((addr, len)) = s.split_once('/') {
+            if let (Ok(addr), Ok(len)) = (IpAddr::from_str(addr), len.parse::<u8>()) {
+                if let Some(v) = Self::new(addr, len) {
+                    return Ok(v);
+                }
+            }
+        }
+
+        Err(Self::Err::InvalidPrefix(s.to_string()))
+    }
+}
+
+
+impl fmt::Display for IpPrefix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.len)
+    }
+}
+
+
+#[cfg(feature = "serde")]
+impl Serialize for IpPrefix {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(&self.to_string())
+    }
+}
+
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for IpPrefix {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Deserialize::deserialize(deserializer)?;
+
+        Self::from_str(value).map_err(DeError::custom)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "serde")]
+    use serde::{Deserialize, Serialize};
+
+    #[test]
+    fn test_ip_prefix_parse() {
+        let prefix = IpPrefix::from_str("10.1.2.3/15").unwrap();
+        assert_eq!(prefix.len(), 15);
+        assert_eq!(prefix.is_ipv4(), true);
+        assert_eq!(prefix.network().to_string(), "10.0.0.0");
+        assert_eq!(prefix.octets(), [10, 0]);
+        assert_eq!(prefix.to_string(), "10.1.2.3/15");
+
+        let prefix = IpPrefix::from_str("2001:db8::/32").unwrap();
+        assert_eq!(prefix.is_ipv6(), true);
+        assert_eq!(prefix.octets(), [0x20, 0x01, 0x0d, 0xb8]);
+
+        let prefix = IpPrefix::from_str("0.0.0.0/0").unwrap();
+        assert_eq!(prefix.is_empty(), true);
+        assert_eq!(prefix.octets(), Vec::<u8>::new());
+        assert_eq!(prefix.network().to_string(), "0.0.0.0");
+
+        assert_eq!(IpPrefix::from_str("10.0.0.0/33").is_err(), true);
+        assert_eq!(IpPrefix::from_str("10.0.0.0").is_err(), true);
+        assert_eq!(IpPrefix::from_str("10.0.0/8").is_err(), true);
+        assert_eq!(IpPrefix::from_octets(&[10, 0, 0, 0, 0], 8, false), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+    struct Example {
+        pub prefix: IpPrefix,
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_ip_prefix_serde() {
+        let example = Example {prefix: IpPrefix::from_str("10.1.2.3/15").unwrap()};
+
+        let example_string = serde_json::to_string(&example).unwrap();
+        assert_eq!(example_string, "{\"prefix\":\"10.1.2.3/15\"}");
+        let example_new: Example = serde_json::from_str(&example_string).unwrap();
+        assert_eq!(example, example_new);
+    }
+}
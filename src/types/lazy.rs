@@ -0,0 +1,189 @@
+use core::{
+    fmt,
+    any::Any,
+    cell::OnceCell,
+};
+use crate::{
+    JResult, Bytes, ByteOrder,
+    ByteDecode, ContainerAttrModifiers, FieldAttrModifiers,
+    args, ctx::ByteCtx,
+};
+
+
+/// A field which is decoded on demand, the decode only records the byte range of the field.
+///
+/// The range is taken by `length`/`byte_count`/`remaining`, otherwise the value is decoded immediately to find its end.
+/// The raw bytes are encoded verbatim unless the value is set or mutably borrowed,
+/// the modified value is padded with zeros to the `length` of the field.
+///
+/// # Example
+///
+/// ```
+/// use jbytes_derive::{BorrowByteDecode, BorrowByteEncode};
+/// use jbytes::prelude::*;
+/// use jbytes::types::Lazy;
+///
+///
+/// #[derive(Debug, Clone, BorrowByteDecode, BorrowByteEncode)]
+/// pub struct PacketExample<'a> {
+///     pub cmd: u8,
+///     #[jbytes(byte_count=1)]
+///     pub payload: Lazy<'a, (u16, u8)>,
+/// }
+///
+///
+/// let input = Bytes::new(b"\x01\x03\x00\x02\x03");
+/// let mut value: PacketExample = jbytes::decode_borrow(&input).unwrap();
+/// assert_eq!(value.payload.is_materialized(), false);
+/// assert_eq!(value.payload.get().unwrap(), &(2, 3));
+/// assert_eq!(*jbytes::encode_borrow(value.clone()).unwrap(), b"\x01\x03\x00\x02\x03");
+///
+/// value.payload.get_mut().unwrap().1 = 4;
+/// assert_eq!(*jbytes::encode_borrow(value).unwrap(), b"\x01\x03\x00\x02\x04");
+/// ```
+pub struct Lazy<'a, T> {
+    // The raw bytes of the field, including the prefix of `byte_count`.
+    pub(crate) raw: &'a [u8],
+    // The number of bytes of the `byte_count` prefix.
+    pub(crate) prefix_len: usize,
+    // The position of the raw bytes in the input.
+    pub(crate) position: usize,
+    pub(crate) byteorder: ByteOrder,
+    // The byte order of the subtree from the parent, eg: `#[jbytes(byteorder_from="expr")]`
+    pub(crate) byteorder_subtree: Option<ByteOrder>,
+    pub(crate) value: OnceCell<T>,
+    // The value is set or mutably borrowed, which is encoded instead of the raw bytes.
+    pub(crate) is_modified: bool,
+}
+
+
+impl<'a, T> Lazy<'a, T> {
+    /// Constructs a new `Lazy<T>` of the value, which is encoded as `T`.
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Self {
+            raw: &[],
+            prefix_len: 0,
+            position: 0,
+            byteorder: ByteOrder::Be,
+            byteorder_subtree: None,
+            value: OnceCell::from(value),
+            is_modified: true,
+        }
+    }
+
+    /// Returns the raw bytes of the value, the prefix of `byte_count` isn't included.
+    #[inline]
+    pub fn raw(&self) -> &'a [u8] {
+        &self.raw[self.prefix_len..]
+    }
+
+    /// Returns true if the value is decoded or set.
+    #[inline]
+    pub fn is_materialized(&self) -> bool {
+        self.value.get().is_some()
+    }
+
+    /// Replaces the value, which is encoded instead of the raw bytes.
+    #[inline]
+    pub fn set(&mut self, value: T) {
+        self.value = OnceCell::from(value);
+        self.is_modified = true;
+    }
+}
+
+
+impl<'a, T: ByteDecode> Lazy<'a, T> {
+    /// Decodes the value from the raw bytes on the first call, and returns the cached value after that.
+    #[inline]
+    pub fn get(&self) -> JResult<&T> {
+        self.get_inner(None)
+    }
+
+    /// Same as `get`, the user context of `decode_with_ctx` isn't kept by the lazy field and is passed again here.
+    #[inline]
+    pub fn get_with_ctx<C: Any>(&self, ctx: &C) -> JResult<&T>
+    where
+        T: ByteCtx<args::ByteDecode, Ctx = C>,
+    {
+        self.get_inner(Some(ctx))
+    }
+
+    fn get_inner(&self, ctx: Option<&dyn Any>) -> JResult<&T> {
+        if let Some(value) = self.value.get() {
+            return Ok(value);
+        }
+
+        // The value fills the raw bytes, eg: `Vec<T>` is decoded until the end.
+        let cattr = ContainerAttrModifiers { byteorder: Some(self.byteorder), byteorder_subtree: self.byteorder_subtree, ctx, ..Default::default() };
+        let fattr = FieldAttrModifiers { remaining: true, ..Default::default() };
+        let value = T::decode_inner(&Bytes::new(self.raw()), Some(&cattr), Some(&fattr)).map_err(|mut e| {
+            e.position += self.position + self.prefix_len;
+            e
+        })?;
+
+        Ok(self.value.get_or_init(|| value))
+    }
+
+    /// Decodes the value if it isn't materialized, the mutable value is encoded instead of the raw bytes.
+    pub fn get_mut(&mut self) -> JResult<&mut T> {
+        self.get()?;
+        self.is_modified = true;
+
+        match self.value.get_mut() {
+            Some(value) => Ok(value),
+            None => unreachable!(),
+        }
+    }
+
+    /// Consumes the `Lazy<T>`, returning the decoded value.
+    pub fn into_inner(self) -> JResult<T> {
+        self.get()?;
+
+        match self.value.into_inner() {
+            Some(value) => Ok(value),
+            None => unreachable!(),
+        }
+    }
+}
+
+
+impl<'a, T> From<T> for Lazy<'a, T> {
+    #[inline]
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+
+impl<'a, T: Default> Default for Lazy<'a, T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+
+impl<'a, T: Clone> Clone for Lazy<'a, T> {
+    fn clone(&self) -> Self {
+        Self {
+            raw: self.raw,
+            prefix_len: self.prefix_len,
+            position: self.position,
+            byteorder: self.byteorder,
+            byteorder_subtree: self.byteorder_subtree,
+            value: self.value.clone(),
+            is_modified: self.is_modified,
+        }
+    }
+}
+
+
+impl<'a, T: fmt::Debug> fmt::Debug for Lazy<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Lazy")
+            .field("raw", &self.raw())
+            .field("value", &self.value.get())
+            .finish()
+    }
+}
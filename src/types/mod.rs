@@ -1,14 +1,18 @@
 pub mod mac_address;
 pub mod net_address;
+pub mod ip_prefix;
 pub mod hex_string;
 pub mod hex_bytes;
 pub mod mark;
+pub mod lazy;
 
 pub use mac_address::{MacAddress, MacAddressParseError};
 pub use net_address::{NetAddress, NetAddressParseError};
+pub use ip_prefix::{IpPrefix, IpPrefixParseError};
 pub use hex_bytes::{HexBytes, Hex};
 pub use hex_string::{
     HexString, HexStringParseError,
     // encode, decode,
 };
-pub use mark::Mark;
\ No newline at end of file
+pub use mark::Mark;
+pub use lazy::Lazy;
\ No newline at end of file
@@ -0,0 +1,101 @@
+use core::convert::TryInto;
+use core::ops::Range;
+use crate::ByteOrder;
+
+
+/// This is a read function of the view accessors generated by `#[derive(ByteView)]`,
+/// which reads an unsigned integer of `nbytes` at the offset.
+///
+/// The length of the bytes is validated once by the view, so the accessors don't return errors.
+///
+/// # Example
+///
+/// ```
+/// use jbytes::ByteOrder;
+///
+/// assert_eq!(jbytes::view::read_uint(b"\x00\x01\x02\x03", 1, 2, ByteOrder::Be), 0x0102);
+/// assert_eq!(jbytes::view::read_uint(b"\x00\x01\x02\x03", 1, 3, ByteOrder::Le), 0x030201);
+/// ```
+#[inline]
+pub fn read_uint(data: &[u8], offset: usize, nbytes: usize, byteorder: ByteOrder) -> u128 {
+    let data = &data[offset..offset + nbytes];
+
+    match byteorder {
+        ByteOrder::Be => data.iter().fold(0, |value, v| (value << 8) | *v as u128),
+        ByteOrder::Le => data.iter().rev().fold(0, |value, v| (value << 8) | *v as u128),
+    }
+}
+
+
+/// This is a write function of the view setters generated by `#[derive(ByteViewMut)]`,
+/// which writes the low `nbytes` of an unsigned integer at the offset.
+///
+/// # Example
+///
+/// ```
+/// use jbytes::ByteOrder;
+///
+/// let mut data = [0; 4];
+/// jbytes::view::write_uint(&mut data, 1, 2, ByteOrder::Be, 0x0102);
+/// jbytes::view::write_uint(&mut data, 3, 1, ByteOrder::Le, 0x0304);
+/// assert_eq!(data, [0x00, 0x01, 0x02, 0x04]);
+/// ```
+#[inline]
+pub fn write_uint(data: &mut [u8], offset: usize, nbytes: usize, byteorder: ByteOrder, value: u128) {
+    for (index, v) in data[offset..offset + nbytes].iter_mut().enumerate() {
+        let shift = match byteorder {
+            ByteOrder::Be => (nbytes - 1 - index) * 8,
+            ByteOrder::Le => index * 8,
+        };
+
+        *v = (value >> shift) as u8;
+    }
+}
+
+
+/// This is an incremental update function of the internet checksum over `data[range]` after the bytes at
+/// the offset are changed from `old`, RFC 1624: `HC' = ~(~HC + ~m + m')`.
+///
+/// # Example
+///
+/// ```
+/// use jbytes::{ByteOrder, Checksum};
+///
+/// let mut data = *b"\x45\x00\x00\x73\x00\x00\x40\x00\x40\x11\xb8\x61\xc0\xa8\x00\x01\xc0\xa8\x00\xc7";
+/// data[8] = 0x3f;
+/// jbytes::view::update_internet_checksum(&mut data, 0..20, 10, ByteOrder::Be, 8, &[0x40]);
+/// assert_eq!(data[10..12], [0xb9, 0x61]);
+/// assert_eq!(Checksum::Internet.checksum_range(&data, 0..20, 10..12), Some(0xb961));
+/// ```
+pub fn update_internet_checksum(data: &mut [u8], range: Range<usize>, checksum_offset: usize, byteorder: ByteOrder, offset: usize, old: &[u8]) {
+    let mut sum = !(read_uint(data, checksum_offset, 2, byteorder) as u16) as u64;
+
+    for (index, value) in old.iter().enumerate() {
+        let position = offset + index;
+
+        if !range.contains(&position) {
+            continue;
+        }
+
+        // The bytes at the even positions of the range are the high bytes of the 16-bit words.
+        let shift = if (position - range.start) & 1 == 0 { 8 } else { 0 };
+        sum += !((*value as u16) << shift) as u64 + ((data[position] as u64) << shift);
+    }
+
+    while sum >> 16 != 0 {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+
+    write_uint(data, checksum_offset, 2, byteorder, (!sum & 0xffff) as u128);
+}
+
+
+/// This is a read function of the view accessors generated by `#[derive(ByteView)]`,
+/// which borrows an array of `N` bytes at the offset.
+#[inline]
+pub fn read_array<const N: usize>(data: &[u8], offset: usize) -> &[u8; N] {
+    match data[offset..offset + N].try_into() {
+        Ok(value) => value,
+        Err(_) => unreachable!(),
+    }
+}
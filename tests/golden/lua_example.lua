@@ -0,0 +1,214 @@
+-- This dissector is generated by jbytes from `LuaExample`.
+
+local lua_example = Proto("lua_example", "LuaExample")
+local fields = lua_example.fields
+
+fields.lua_example_magic = ProtoField.bytes("lua_example.magic", "magic")
+fields.lua_example_header = ProtoField.none("lua_example.header", "header")
+fields.lua_example_count = ProtoField.uint8("lua_example.count", "count", base.DEC)
+fields.lua_example_values = ProtoField.uint16("lua_example.values", "values", base.DEC)
+fields.lua_example_name = ProtoField.string("lua_example.name", "name")
+fields.lua_example_title_len = ProtoField.uint16("lua_example.title_len", "title_len", base.DEC)
+fields.lua_example_title = ProtoField.string("lua_example.title", "title")
+fields.lua_example_type = ProtoField.uint8("lua_example.type", "type", base.DEC)
+fields.lua_example_body = ProtoField.none("lua_example.body", "body")
+fields.lua_example_command = ProtoField.none("lua_example.command", "command")
+fields.lua_example_extra = ProtoField.uint8("lua_example.extra", "extra", base.DEC)
+fields.lua_example_payload = ProtoField.bytes("lua_example.payload", "payload")
+fields.lua_header_example_version = ProtoField.uint16("lua_example.lua_header_example.version", "version", base.DEC, nil, 0xf000)
+fields.lua_header_example_length = ProtoField.uint16("lua_example.lua_header_example.length", "length", base.DEC, nil, 0xfff)
+fields.lua_header_example_address = ProtoField.uint24("lua_example.lua_header_example.address", "address", base.DEC)
+fields.lua_header_example_src = ProtoField.ipv4("lua_example.lua_header_example.src", "src")
+fields.lua_body_example_branch = ProtoField.uint8("lua_example.lua_body_example.branch", "branch", base.DEC)
+fields.lua_body_example_read = ProtoField.none("lua_example.lua_body_example.read", "Read")
+fields.lua_body_example_read_address = ProtoField.uint8("lua_example.lua_body_example_read.address", "address", base.DEC)
+fields.lua_body_example_write = ProtoField.none("lua_example.lua_body_example.write", "Write")
+fields.lua_body_example_write_v1 = ProtoField.stringz("lua_example.lua_body_example_write.v1", "1")
+fields.lua_body_example_unknown = ProtoField.none("lua_example.lua_body_example.unknown", "Unknown")
+fields.lua_command_example_branch = ProtoField.uint16("lua_example.lua_command_example.branch", "branch", base.DEC)
+fields.lua_command_example_start = ProtoField.none("lua_example.lua_command_example.start", "Start")
+fields.lua_command_example_stop = ProtoField.none("lua_example.lua_command_example.stop", "Stop")
+fields.lua_command_example_stop_code = ProtoField.uint32("lua_example.lua_command_example_stop.code", "code", base.DEC)
+fields.lua_command_example_stop_headers = ProtoField.none("lua_example.lua_command_example_stop.headers", "headers")
+
+-- Returns the length of the bytes before `needle`, or the remaining length if it isn't found.
+local function find_needle(tvb, offset, needle)
+    local index = string.find(tvb:raw(offset), needle, 1, true)
+
+    if index == nil then
+        return tvb:len() - offset
+    end
+
+    return index - 1
+end
+
+local dissect_lua_example, dissect_lua_header_example, dissect_lua_body_example, dissect_lua_command_example
+
+function dissect_lua_example(tvb, tree, offset)
+    local v = {}
+
+    tree:add(fields.lua_example_magic, tvb(offset, 2))
+    offset = offset + 2
+    do
+        local subtree = tree:add(fields.lua_example_header, tvb(offset))
+        local start = offset
+        offset, v.header = dissect_lua_header_example(tvb, subtree, offset)
+        subtree:set_len(offset - start)
+    end
+    v.count = tvb(offset, 1):uint()
+    tree:add(fields.lua_example_count, tvb(offset, 1))
+    offset = offset + 1
+    do
+        local count = v.count
+        v.values = {}
+        for _ = 1, count do
+            v.values[#v.values + 1] = tvb(offset, 2):uint()
+            tree:add(fields.lua_example_values, tvb(offset, 2))
+            offset = offset + 2
+        end
+    end
+    do
+        local length = find_needle(tvb, offset, "\13\10")
+        v.name = tvb(offset, length):string(ENC_UTF_8)
+        tree:add(fields.lua_example_name, tvb(offset, length), v.name)
+        offset = offset + length + 2
+    end
+    do
+        tree:add(fields.lua_example_title_len, tvb(offset, 2))
+        local length = tvb(offset, 2):uint()
+        offset = offset + 2
+        v.title = tvb(offset, length):string(ENC_UTF_16 + ENC_LITTLE_ENDIAN)
+        tree:add(fields.lua_example_title, tvb(offset, length), v.title)
+        offset = offset + length
+    end
+    v.type = tvb(offset, 1):uint()
+    tree:add(fields.lua_example_type, tvb(offset, 1))
+    offset = offset + 1
+    do
+        local subtree = tree:add(fields.lua_example_body, tvb(offset))
+        local start = offset
+        offset, v.body = dissect_lua_body_example(tvb, subtree, offset, v.type)
+        subtree:set_len(offset - start)
+    end
+    do
+        local subtree = tree:add(fields.lua_example_command, tvb(offset))
+        local start = offset
+        offset, v.command = dissect_lua_command_example(tvb, subtree, offset)
+        subtree:set_len(offset - start)
+    end
+    if v.type == 1 and v.count > 0 then
+        v.extra = tvb(offset, 1):uint()
+        tree:add(fields.lua_example_extra, tvb(offset, 1))
+        offset = offset + 1
+    end
+    do
+        local count = tvb:len() - offset
+        v.payload = tvb(offset, count):bytes()
+        tree:add(fields.lua_example_payload, tvb(offset, count))
+        offset = offset + count
+    end
+
+    return offset, v
+end
+
+function dissect_lua_header_example(tvb, tree, offset)
+    local v = {}
+
+    do
+        local offset = offset
+        v.version = bit.rshift(bit.band(tvb(offset, 2):le_uint(), 0xf000), 12)
+        tree:add_le(fields.lua_header_example_version, tvb(offset, 2))
+        offset = offset + 2
+    end
+    v.length = bit.band(tvb(offset, 2):le_uint(), 0xfff)
+    tree:add_le(fields.lua_header_example_length, tvb(offset, 2))
+    offset = offset + 2
+    v.address = tvb(offset, 3):uint()
+    tree:add(fields.lua_header_example_address, tvb(offset, 3))
+    offset = offset + 3
+    v.src = tvb(offset, 4):le_ipv4()
+    tree:add_le(fields.lua_header_example_src, tvb(offset, 4))
+    offset = offset + 4
+
+    return offset, v
+end
+
+function dissect_lua_body_example(tvb, tree, offset, branch)
+    local v = {}
+
+    if branch == nil then
+        tree:add(fields.lua_body_example_branch, tvb(offset, 1))
+        branch = tvb(offset, 1):uint()
+        offset = offset + 1
+    end
+
+    if branch == 1 then
+        local branch_tree = tree:add(fields.lua_body_example_read, tvb(offset))
+        local start = offset
+
+        v.address = tvb(offset, 1):uint()
+        branch_tree:add(fields.lua_body_example_read_address, tvb(offset, 1))
+        offset = offset + 1
+
+        branch_tree:set_len(offset - start)
+    elseif branch >= 2 and branch <= 3 then
+        local branch_tree = tree:add(fields.lua_body_example_write, tvb(offset))
+        local start = offset
+
+        v.v0 = branch
+        do
+            local length = tvb(offset):strsize()
+            v.v1 = tvb(offset, length):stringz(ENC_UTF_8)
+            branch_tree:add(fields.lua_body_example_write_v1, tvb(offset, length), v.v1)
+            offset = offset + length
+        end
+
+        branch_tree:set_len(offset - start)
+    else
+        tree:add(fields.lua_body_example_unknown, tvb(offset, 0))
+    end
+
+    return offset, v
+end
+
+function dissect_lua_command_example(tvb, tree, offset, branch)
+    local v = {}
+
+    if branch == nil then
+        tree:add(fields.lua_command_example_branch, tvb(offset, 2))
+        branch = tvb(offset, 2):uint()
+        offset = offset + 2
+    end
+
+    if branch == 0 then
+        tree:add(fields.lua_command_example_start, tvb(offset, 0))
+    elseif branch == 1 then
+        local branch_tree = tree:add(fields.lua_command_example_stop, tvb(offset))
+        local start = offset
+
+        v.code = tvb(offset, 4):uint()
+        branch_tree:add(fields.lua_command_example_stop_code, tvb(offset, 4))
+        offset = offset + 4
+        v.headers = {}
+        for _ = 1, 2 do
+            do
+                local subtree = branch_tree:add(fields.lua_command_example_stop_headers, tvb(offset))
+                local start = offset
+                offset, v.headers[#v.headers + 1] = dissect_lua_header_example(tvb, subtree, offset)
+                subtree:set_len(offset - start)
+            end
+        end
+
+        branch_tree:set_len(offset - start)
+    end
+
+    return offset, v
+end
+
+function lua_example.dissector(tvb, pinfo, tree)
+    pinfo.cols.protocol = "LuaExample"
+    local subtree = tree:add(lua_example, tvb())
+    dissect_lua_example(tvb, subtree, 0)
+end
+
+-- Registers the dissector, eg: DissectorTable.get("udp.port"):add(9000, lua_example)
@@ -0,0 +1,34 @@
+-- This dissector is generated by jbytes from `LuaUnsupportedExample`.
+
+local lua_unsupported_example = Proto("lua_unsupported_example", "LuaUnsupportedExample")
+local fields = lua_unsupported_example.fields
+
+fields.lua_unsupported_example_host = ProtoField.bytes("lua_unsupported_example.host", "host")
+fields.lua_unsupported_example_data = ProtoField.bytes("lua_unsupported_example.data", "data")
+fields.lua_unsupported_example_values = ProtoField.bytes("lua_unsupported_example.values", "values")
+fields.lua_unsupported_example_value = ProtoField.bytes("lua_unsupported_example.value", "value")
+
+local dissect_lua_unsupported_example
+
+function dissect_lua_unsupported_example(tvb, tree, offset)
+    local v = {}
+
+    tree:add(fields.lua_unsupported_example_host, tvb(offset))
+    offset = tvb:len()
+    tree:add(fields.lua_unsupported_example_data, tvb(offset, 2))
+    offset = offset + 2
+    tree:add(fields.lua_unsupported_example_values, tvb(offset))
+    offset = tvb:len()
+    tree:add(fields.lua_unsupported_example_value, tvb(offset))
+    offset = tvb:len()
+
+    return offset, v
+end
+
+function lua_unsupported_example.dissector(tvb, pinfo, tree)
+    pinfo.cols.protocol = "LuaUnsupportedExample"
+    local subtree = tree:add(lua_unsupported_example, tvb())
+    dissect_lua_unsupported_example(tvb, subtree, 0)
+end
+
+-- Registers the dissector, eg: DissectorTable.get("udp.port"):add(9000, lua_unsupported_example)
@@ -0,0 +1,162 @@
+use std::borrow::Cow;
+use std::collections::{BTreeSet, VecDeque};
+use std::marker::PhantomData;
+use std::net::{SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::rc::Rc;
+use std::sync::Arc;
+use jbytes_derive::{ByteDecode, ByteEncode, Describe};
+use jbytes::prelude::*;
+use jbytes::schema::{Modifier, SchemaBody};
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, Describe)]
+pub struct DescribeHeaderExample {
+    #[jbytes(bits_start=0xf0, untake)]
+    pub version: u8,
+    #[jbytes(bits=0x0f)]
+    pub length: u8,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, Describe)]
+#[jbytes(byteorder="LE", magic=b"JB")]
+pub struct DescribeExample<T> {
+    pub header: DescribeHeaderExample,
+    pub count: u16,
+    #[jbytes(count="count", byte_size=2)]
+    pub headers: Vec<DescribeHeaderExample>,
+    #[jbytes(end_with=b"\r\n")]
+    pub name: String,
+    pub r#type: u8,
+    #[jbytes(branch="r#type")]
+    pub body: DescribeBodyExample,
+    pub value: T,
+    pub marker: PhantomData<T>,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, Describe)]
+#[repr(u16)]
+pub enum DescribeBodyExample {
+    Empty,
+    #[jbytes(branch_value=0x05)]
+    Read(u8, #[jbytes(length=3)] u32),
+    #[jbytes(branch_range="6..=8")]
+    Write {
+        address: [DescribeHeaderExample; 2],
+    },
+    #[jbytes(branch_default)]
+    Unknown,
+}
+
+
+#[test]
+fn test_derive_describe() {
+    let schema = &<DescribeExample<u8>>::SCHEMA;
+    assert_eq!(schema.name, "DescribeExample");
+    assert_eq!(schema.modifiers, [Modifier { key: "byteorder", value: Some("LE") }, Modifier { key: "magic", value: Some("b\"JB\"") }]);
+
+    let fields = schema.fields();
+    let names = fields.iter().map(|v| (v.name, v.rtype)).collect::<Vec<_>>();
+    assert_eq!(names, [
+        ("header", "DescribeHeaderExample"), ("count", "u16"), ("headers", "Vec<DescribeHeaderExample>"), ("name", "String"),
+        ("type", "u8"), ("body", "DescribeBodyExample"), ("value", "T"), ("marker", "PhantomData<T>"),
+    ]);
+
+    // The keys are canonical.
+    assert_eq!(fields[2].modifiers, [Modifier { key: "count", value: Some("count") }, Modifier { key: "byte_count", value: Some("2") }]);
+    assert_eq!(fields[3].get("linend").unwrap().value, Some("b\"\\r\\n\""));
+    assert_eq!(fields[5].get("branch").unwrap().value, Some("r#type"));
+    assert_eq!(fields[1].modifiers.is_empty(), true);
+
+    // The nested derived types are linked, the type parameters aren't.
+    let header = fields[0].schema.unwrap();
+    assert_eq!(fields[2].schema, Some(header));
+    assert_eq!(header.fields()[0].modifiers, [Modifier { key: "bits_start", value: Some("0xf0") }, Modifier { key: "untake", value: None }]);
+    assert_eq!(fields[6].schema, None);
+    assert_eq!(fields[7].schema, None);
+
+    let body = fields[5].schema.unwrap();
+    assert_eq!(body.get("byte_count").unwrap().value, Some("2"));
+    assert_eq!(body.fields().is_empty(), true);
+
+    let variants = body.variants();
+    let branches = variants.iter().map(|v| (v.name, v.branch)).collect::<Vec<_>>();
+    assert_eq!(branches, [("Empty", Some(0)), ("Read", Some(5)), ("Write", Some(8)), ("Unknown", None)]);
+    assert_eq!(variants[1].fields[1].name, "1");
+    assert_eq!(variants[1].fields[1].get("length").unwrap().value, Some("3"));
+    assert_eq!(variants[2].get("branch_range").unwrap().value, Some("6..=8"));
+    assert_eq!(variants[2].fields[0].schema, Some(header));
+    assert_eq!(variants[3].get("branch_default"), Some(&Modifier { key: "branch_default", value: None }));
+    assert_eq!(matches!(body.body, SchemaBody::Enum(_)), true);
+}
+
+
+#[derive(Describe)]
+pub struct DescribeForeignExample<'a> {
+    pub addr: SocketAddr,
+    pub addr_v4: SocketAddrV4,
+    pub addr_v6: SocketAddrV6,
+    pub name: Cow<'a, str>,
+    pub rc: Rc<DescribeHeaderExample>,
+    pub arc: Arc<DescribeHeaderExample>,
+    pub set: BTreeSet<u8>,
+    pub queue: VecDeque<DescribeHeaderExample>,
+    #[jbytes(with="foreign_codec")]
+    pub codec: ForeignCodecExample,
+    pub header: Option<DescribeHeaderExample>,
+}
+
+
+pub struct ForeignCodecExample;
+
+
+#[test]
+fn test_derive_describe_foreign() {
+    // The types which don't implement `Describe` aren't linked.
+    let fields = DescribeForeignExample::SCHEMA.fields();
+    let schemas = fields.iter().map(|v| (v.name, v.schema.is_some())).collect::<Vec<_>>();
+    assert_eq!(schemas, [
+        ("addr", false), ("addr_v4", false), ("addr_v6", false), ("name", false), ("rc", false), ("arc", false),
+        ("set", false), ("queue", false), ("codec", false), ("header", true),
+    ]);
+    assert_eq!(fields[8].get("with").unwrap().value, Some("foreign_codec"));
+}
+
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_derive_describe_serde() {
+    let value = serde_json::to_value(&DescribeHeaderExample::SCHEMA).unwrap();
+
+    assert_eq!(value, serde_json::json!({
+        "name": "DescribeHeaderExample",
+        "modifiers": [],
+        "body": {
+            "Struct": [
+                {
+                    "name": "version",
+                    "rtype": "u8",
+                    "modifiers": [{ "key": "bits_start", "value": "0xf0" }, { "key": "untake", "value": null }],
+                    "schema": null,
+                },
+                {
+                    "name": "length",
+                    "rtype": "u8",
+                    "modifiers": [{ "key": "bits", "value": "0x0f" }],
+                    "schema": null,
+                },
+            ],
+        },
+    }));
+}
+
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_derive_describe_serde_string() {
+    let value = serde_json::to_string(&DescribeExample::<u8>::SCHEMA).unwrap();
+
+    assert_eq!(value.starts_with(r#"{"name":"DescribeExample","modifiers":[{"key":"byteorder","value":"LE"}"#), true);
+    assert_eq!(value.contains(r#""schema":{"name":"DescribeHeaderExample""#), true);
+}
@@ -0,0 +1,66 @@
+use std::net::Ipv4Addr;
+use std::marker::PhantomData;
+use jbytes_derive::{ByteDecode, ByteEncode, FixedSize};
+use jbytes::prelude::*;
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, FixedSize)]
+pub struct FixedSizeHeaderExample {
+    #[jbytes(bits_start=0xf0, untake)]
+    pub version: u8,
+    #[jbytes(bits=0x0f)]
+    pub length: u8,
+    pub src: Ipv4Addr,
+    pub mac: MacAddress,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, FixedSize)]
+pub struct FixedSizeExample<T> {
+    pub header: FixedSizeHeaderExample,
+    #[jbytes(length=3)]
+    pub address: u32,
+    #[jbytes(length=4)]
+    pub name: String,
+    #[jbytes(count=2)]
+    pub values: Vec<u16>,
+    pub array: [(u8, u16); 2],
+    #[jbytes(offset=1)]
+    pub value: T,
+    pub marker: PhantomData<T>,
+    #[jbytes(skip)]
+    pub cache: u8,
+}
+
+
+#[test]
+fn test_derive_fixed_size() {
+    assert_eq!(FixedSizeHeaderExample::SIZE, 11);
+    assert_eq!(<FixedSizeExample<u16>>::SIZE, 11 + 3 + 4 + 4 + 6 + 1 + 2);
+    assert_eq!(<FixedSizeExample<u64>>::SIZE, 37);
+
+    let value = FixedSizeExample {
+        header: FixedSizeHeaderExample { version: 4, length: 5, src: Ipv4Addr::new(10, 0, 0, 1), mac: MacAddress::from_bits(1) },
+        address: 2,
+        name: "abcd".to_string(),
+        values: vec![3, 4],
+        array: [(5, 6), (7, 8)],
+        value: 9_u16,
+        marker: PhantomData,
+        cache: 0,
+    };
+    let buffer = jbytes::encode(value).unwrap();
+    assert_eq!(buffer.len(), <FixedSizeExample<u16>>::SIZE);
+    assert_eq!(jbytes::decode::<_, FixedSizeExample<u16>>(&buffer[..]).is_ok(), true);
+}
+
+
+#[test]
+fn test_fixed_size_types() {
+    assert_eq!(u8::SIZE, 1);
+    assert_eq!(i128::SIZE, 16);
+    assert_eq!(f64::SIZE, 8);
+    assert_eq!(<[u32; 3]>::SIZE, 12);
+    assert_eq!(<(u8, u16, [MacAddress; 2])>::SIZE, 15);
+    assert_eq!(std::net::Ipv6Addr::SIZE, 16);
+}
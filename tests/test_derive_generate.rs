@@ -0,0 +1,215 @@
+use std::net::Ipv4Addr;
+use jbytes_derive::{ByteDecode, ByteEncode, ByteGenerate};
+use jbytes::prelude::*;
+use jbytes::testing::{Rng, assert_roundtrip};
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, ByteGenerate)]
+#[jbytes(magic=b"JB")]
+pub struct GenerateHeaderExample {
+    #[jbytes(check_value="0x01")]
+    pub version: u8,
+    #[jbytes(magic=b"\x00\x00")]
+    pub reserved: (),
+    #[jbytes(bits_start=0xf0, untake)]
+    pub flags: u8,
+    #[jbytes(bits=0x0f)]
+    pub header_length: u8,
+    #[jbytes(length=3)]
+    pub address: u32,
+    pub src: Ipv4Addr,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, ByteGenerate)]
+pub struct GenerateExample {
+    pub header: GenerateHeaderExample,
+    pub length: u8,
+    #[jbytes(length="length")]
+    pub name: String,
+    pub count: u16,
+    #[jbytes(count="count")]
+    pub values: Vec<(u8, u16)>,
+    pub cmd: u8,
+    #[jbytes(branch="cmd")]
+    pub body: GenerateBodyExample,
+    #[jbytes(if_expr="cmd == 0x02")]
+    pub extra: Option<u16>,
+    #[jbytes(skip)]
+    pub cache: u32,
+    pub tail: Vec<MacAddress>,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, ByteGenerate)]
+pub enum GenerateBodyExample {
+    #[jbytes(branch_value=1)]
+    Read {
+        address: u8,
+    },
+    #[jbytes(branch_value=2)]
+    Write {
+        address: u8,
+        #[jbytes(length=2)]
+        value: String,
+    },
+    #[jbytes(branch_range="3..=5")]
+    Other(#[jbytes(discriminant)] u8, [u16; 2]),
+}
+
+
+#[test]
+fn test_derive_generate() {
+    let mut rng = Rng::new(0);
+    let mut branches = [false; 3];
+
+    for _ in 0..500 {
+        let value: GenerateExample = jbytes::testing::generate(&mut rng);
+
+        assert_eq!(value.header.version, 0x01);
+        assert_eq!(value.header.flags <= 0x0f, true);
+        assert_eq!(value.header.address <= 0xffffff, true);
+        assert_eq!(value.name.len(), value.length as usize);
+        assert_eq!(value.values.len(), value.count as usize);
+        assert_eq!(value.cache, 0);
+        assert_eq!(value.extra.is_some(), value.cmd == 0x02);
+
+        match &value.body {
+            GenerateBodyExample::Read { .. } => branches[0] = true,
+            GenerateBodyExample::Write { value, .. } => { branches[1] = true; assert_eq!(value.len(), 2); },
+            GenerateBodyExample::Other(cmd, _) => { branches[2] = true; assert_eq!(*cmd, value.cmd); },
+        }
+
+        assert_roundtrip(&value);
+    }
+
+    // All variants are generated.
+    assert_eq!(branches, [true; 3]);
+
+    // The same seed generates the same value.
+    let value: GenerateExample = jbytes::testing::generate(&mut Rng::new(1));
+    assert_eq!(jbytes::testing::generate::<GenerateExample>(&mut Rng::new(1)), value);
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, ByteGenerate)]
+pub enum GenerateEnumExample {
+    V0,
+    V1(u8),
+    #[jbytes(branch_value=5)]
+    V5 {
+        a: u16,
+        #[jbytes(byteorder="LE")]
+        b: u32,
+    },
+    #[jbytes(branch_default)]
+    Unknown,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, ByteGenerate)]
+#[jbytes(branch_starts_with)]
+pub enum GenerateStartsWithExample {
+    #[jbytes(branch_value=b"read")]
+    Read {
+        address: u8,
+    },
+    Write {
+        address: u8,
+        value: u16,
+    },
+    #[jbytes(branch_default)]
+    Unknown,
+}
+
+
+#[test]
+fn test_derive_generate_enum() {
+    let mut rng = Rng::new(0);
+
+    for _ in 0..100 {
+        let value: GenerateEnumExample = jbytes::testing::generate(&mut rng);
+        assert_eq!(value != GenerateEnumExample::Unknown, true);
+        assert_roundtrip(&value);
+
+        let value: GenerateStartsWithExample = jbytes::testing::generate(&mut rng);
+        assert_eq!(value != GenerateStartsWithExample::Unknown, true);
+        assert_roundtrip(&value);
+    }
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, ByteGenerate)]
+pub struct GeneratePatternExample {
+    pub cmd: u8,
+    #[jbytes(branch="cmd")]
+    pub body: GeneratePatternBodyExample,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, ByteGenerate)]
+pub enum GeneratePatternBodyExample {
+    #[jbytes(branch_value="1 | 2")]
+    Read(#[jbytes(discriminant)] u8),
+    #[jbytes(branch_range="3..5")]
+    Write(#[jbytes(discriminant)] u8, u16),
+}
+
+
+#[test]
+fn test_derive_generate_pattern() {
+    let mut rng = Rng::new(0);
+
+    for _ in 0..100 {
+        // The branch value is taken from the pattern, eg: `1 | 2` => `1`, `3..5` => `3`.
+        let value: GeneratePatternExample = jbytes::testing::generate(&mut rng);
+        assert_eq!(value.cmd == 1 || value.cmd == 3, true);
+        assert_roundtrip(&value);
+    }
+
+    // The unmatched branch value of the parent is replaced by a valid one.
+    let fattr = FieldAttrModifiers { branch: Some(0xff), ..Default::default() };
+    let value = <GeneratePatternBodyExample as jbytes::testing::ByteGenerate>::generate_inner(&mut rng, Some(&fattr));
+    assert_eq!(matches!(value, GeneratePatternBodyExample::Read(1) | GeneratePatternBodyExample::Write(3, _)), true);
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, ByteGenerate)]
+pub struct GenerateOffsetLengthExample {
+    pub total: u16,
+    #[jbytes(length="total - 2")]
+    pub name: String,
+    pub count: u8,
+    #[jbytes(count="count + 1")]
+    pub values: Vec<u8>,
+}
+
+
+#[test]
+fn test_derive_generate_offset_length() {
+    let mut rng = Rng::new(0);
+
+    for _ in 0..100 {
+        // `total - 2` is generated as `2 + n`, so the subtraction doesn't overflow.
+        let value: GenerateOffsetLengthExample = jbytes::testing::generate(&mut rng);
+        assert_eq!(value.total >= 2, true);
+        assert_eq!(value.name.len(), value.total as usize - 2);
+        assert_eq!(value.values.len(), value.count as usize + 1);
+        assert_roundtrip(&value);
+    }
+}
+
+
+#[test]
+#[should_panic(expected = "the decoded value isn't equal to the encoded value")]
+fn test_derive_generate_roundtrip_failed() {
+    // The length isn't matched the string.
+    #[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+    pub struct LengthExample {
+        pub length: u8,
+        #[jbytes(length="length")]
+        pub name: String,
+    }
+
+    assert_roundtrip(&LengthExample { length: 1, name: "ab".to_string() });
+}
@@ -0,0 +1,10 @@
+// The attributes are validated at compile time, the expected errors are checked by `tests/ui/*.stderr`.
+#[test]
+fn test_derive_ui() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+
+    // The legacy code pages need the `encoding_rs` feature.
+    #[cfg(not(feature = "encoding_rs"))]
+    cases.compile_fail("tests/ui/encoding_rs/*.rs");
+}
@@ -0,0 +1,182 @@
+use std::net::Ipv4Addr;
+use jbytes_derive::{ByteDecode, ByteEncode, ByteView, ByteViewMut};
+use jbytes::prelude::*;
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, ByteView)]
+pub struct ViewEthernetExample {
+    pub dmac: MacAddress,
+    pub smac: MacAddress,
+    pub r#type: u16,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, ByteView)]
+pub struct ViewIpv4Example {
+    #[jbytes(bits_start=0xf0, untake)]
+    pub version: u8,
+    #[jbytes(bits=0x0f)]
+    pub header_length: u8,
+    pub tos: u8,
+    pub total_length: u16,
+    pub identification: u16,
+    #[jbytes(bits_start=0xe000, untake)]
+    pub flags: u16,
+    #[jbytes(bits=0x1fff)]
+    pub fragment_offset: u16,
+    pub ttl: u8,
+    pub protocol: u8,
+    pub checksum: u16,
+    pub src: Ipv4Addr,
+    pub dst: Ipv4Addr,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, ByteView)]
+pub struct ViewPacketExample {
+    pub eth: ViewEthernetExample,
+    pub ipv4: ViewIpv4Example,
+}
+
+
+#[test]
+fn test_derive_view() {
+    let data = b"\x00\x0c\x29\xaf\xb6\xb1\x00\x50\x56\xe0\x14\x49\x08\x00\
+                 \x45\x00\x00\x40\xb5\xf2\x20\x05\x40\x06\xa9\x7c\x0a\x01\x01\xea\x0a\x0a\x05\x55";
+    let value: ViewPacketExample = jbytes::decode(data).unwrap();
+
+    assert_eq!(ViewEthernetExampleView::SIZE, 14);
+    assert_eq!(ViewIpv4ExampleView::SIZE, 20);
+    assert_eq!(ViewPacketExampleView::SIZE, 34);
+
+    let view = ViewPacketExampleView::new(data).unwrap();
+    assert_eq!(view.as_bytes(), data);
+    assert_eq!(view.eth().dmac(), value.eth.dmac);
+    assert_eq!(view.eth().r#type(), 0x0800);
+
+    let ipv4 = view.ipv4();
+    assert_eq!(ipv4.version(), value.ipv4.version);
+    assert_eq!(ipv4.header_length(), 5);
+    assert_eq!(ipv4.total_length(), 64);
+    assert_eq!(ipv4.flags(), 1);
+    assert_eq!(ipv4.fragment_offset(), 5);
+    assert_eq!(ipv4.protocol(), 6);
+    assert_eq!(ipv4.src(), Ipv4Addr::new(10, 1, 1, 234));
+    assert_eq!(ipv4.dst(), value.ipv4.dst);
+
+    let error = ViewPacketExampleView::new(&data[..33]).unwrap_err();
+    assert_eq!(error.code, ErrorKind::InvalidByteLength);
+}
+
+
+#[derive(Debug, PartialEq, ByteEncode, ByteDecode, ByteView)]
+#[jbytes(byteorder="LE")]
+pub struct ViewByteorderExample {
+    pub a: u16,
+    #[jbytes(byteorder="BE", length=3)]
+    pub b: u32,
+    #[jbytes(offset=1)]
+    pub c: i8,
+    pub d: [u8; 2],
+    pub e: bool,
+    pub f: f32,
+}
+
+
+#[test]
+fn test_derive_view_byteorder() {
+    let data = b"\x01\x02\x00\x00\x03\xff\xfe\x31\x32\x01\x00\x00\x80\x3f";
+    let value: ViewByteorderExample = jbytes::decode(data).unwrap();
+    let view = ViewByteorderExampleView::new(data).unwrap();
+
+    assert_eq!(ViewByteorderExampleView::SIZE, data.len());
+    assert_eq!(view.a(), value.a);
+    assert_eq!(view.b(), 3);
+    assert_eq!(view.c(), -2);
+    assert_eq!(view.d(), b"12");
+    assert_eq!(view.e(), true);
+    assert_eq!(view.f(), 1.0);
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, ByteView, ByteViewMut)]
+pub struct ViewMutIpv4Example {
+    #[jbytes(bits_start=0xf0, untake)]
+    pub version: u8,
+    #[jbytes(bits=0x0f)]
+    pub header_length: u8,
+    pub tos: u8,
+    pub total_length: u16,
+    pub identification: u16,
+    #[jbytes(bits_start=0xe000, untake)]
+    pub flags: u16,
+    #[jbytes(bits=0x1fff)]
+    pub fragment_offset: u16,
+    pub ttl: u8,
+    pub protocol: u8,
+    #[jbytes(checksum="internet", range="start..end")]
+    pub checksum: u16,
+    pub src: Ipv4Addr,
+    pub dst: Ipv4Addr,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, ByteViewMut)]
+pub struct ViewMutUdpExample {
+    pub src_port: u16,
+    pub dst_port: u16,
+    pub length: u16,
+    pub checksum: u16,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, ByteViewMut)]
+pub struct ViewMutPacketExample {
+    pub ipv4: ViewMutIpv4Example,
+    pub udp: ViewMutUdpExample,
+}
+
+
+#[test]
+fn test_derive_view_mut() {
+    let mut data = *b"\x45\x00\x00\x73\x00\x00\x40\x00\x40\x11\xb8\x61\xc0\xa8\x00\x01\xc0\xa8\x00\xc7\
+                      \x00\x35\x00\x35\x00\x08\x00\x00";
+    let data_ipv4 = data[..20].to_vec();
+    let mut view = ViewMutPacketExampleViewMut::new(&mut data).unwrap();
+
+    // The other bits of the bytes are kept.
+    let mut ipv4 = view.ipv4();
+    assert_eq!(ipv4.ttl(), 64);
+    ipv4.set_fragment_offset(5);
+    assert_eq!(ipv4.flags(), 2);
+    assert_eq!(ipv4.fragment_offset(), 5);
+    ipv4.set_flags(0);
+    ipv4.set_header_length(6);
+    assert_eq!(ipv4.version(), 4);
+    ipv4.set_header_length(5);
+    ipv4.set_flags(2);
+    ipv4.set_fragment_offset(0);
+    assert_eq!(ipv4.as_bytes()[..20], data_ipv4[..]);
+
+    // The checksum is updated incrementally, RFC 1624.
+    ipv4.set_ttl_with_checksum(63);
+    ipv4.set_dst_with_checksum(Ipv4Addr::new(10, 0, 0, 1));
+    ipv4.set_protocol_with_checksum(6);
+    ipv4.set_fragment_offset_with_checksum(0x1234);
+    let checksum = ipv4.checksum();
+    ipv4.update_checksum();
+    assert_eq!(ipv4.checksum(), checksum);
+
+    let mut udp = view.udp();
+    udp.set_dst_port(8080);
+    assert_eq!(udp.dst_port(), 8080);
+
+    let value: ViewMutPacketExample = jbytes::decode(view.as_bytes()).unwrap();
+    assert_eq!(value.ipv4.ttl, 63);
+    assert_eq!(value.ipv4.fragment_offset, 0x1234);
+    assert_eq!(value.ipv4.dst, Ipv4Addr::new(10, 0, 0, 1));
+    assert_eq!(value.udp.dst_port, 8080);
+
+    let error = ViewMutPacketExampleViewMut::new(&mut data[..27]).unwrap_err();
+    assert_eq!(error.code, ErrorKind::InvalidByteLength);
+}
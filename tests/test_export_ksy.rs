@@ -0,0 +1,236 @@
+use jbytes_derive::{ByteDecode, ByteEncode, Describe};
+use jbytes::prelude::*;
+use jbytes::export::{to_ksy, Unsupported};
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, Describe)]
+pub struct KsyHeaderExample {
+    #[jbytes(bits_start=0xf0, untake)]
+    pub version: u8,
+    #[jbytes(bits=0x0f)]
+    pub length: u8,
+    #[jbytes(length=3)]
+    pub address: u32,
+    #[jbytes(byteorder="LE")]
+    pub flags: u16,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, Describe)]
+#[jbytes(byteorder="LE", magic=b"JB")]
+pub struct KsyExample {
+    pub header: KsyHeaderExample,
+    pub count: u16,
+    #[jbytes(count="count")]
+    pub headers: Vec<KsyHeaderExample>,
+    #[jbytes(linend=b"\n")]
+    pub name: String,
+    #[jbytes(byte_count=2, encoding="utf16le")]
+    pub title: String,
+    pub r#type: u8,
+    #[jbytes(branch="r#type")]
+    pub body: KsyBodyExample,
+    pub command: KsyCommandExample,
+    #[jbytes(if_expr="r#type == 1 && count > 0", check_value="0x55")]
+    pub extra: u8,
+    #[jbytes(remaining)]
+    pub payload: Vec<u8>,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, Describe)]
+pub enum KsyBodyExample {
+    #[jbytes(branch_value=1)]
+    Read {
+        address: u8,
+    },
+    #[jbytes(branch_range="2..=3")]
+    Write(u8, #[jbytes(cstr)] String),
+    #[jbytes(branch_default)]
+    Unknown,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, Describe)]
+#[repr(u16)]
+pub enum KsyCommandExample {
+    Start,
+    Stop {
+        code: u32,
+    },
+}
+
+
+#[test]
+fn test_export_ksy() {
+    let export = to_ksy(&KsyExample::SCHEMA);
+
+    assert_eq!(export.document, "\
+meta:
+  id: ksy_example
+  endian: le
+seq:
+  - id: magic
+    contents: [0x4a, 0x42]
+  - id: header
+    type: ksy_header_example
+  - id: count
+    type: u2
+  - id: headers
+    type: ksy_header_example
+    repeat: expr
+    repeat-expr: count
+  - id: name
+    type: str
+    terminator: 10
+    encoding: UTF-8
+  - id: title_len
+    type: u2
+  - id: title
+    type: str
+    size: title_len
+    encoding: UTF-16LE
+  - id: type
+    type: u1
+  - id: body
+    type:
+      switch-on: type
+      cases:
+        1: ksy_body_example_read
+        2: ksy_body_example_write
+        3: ksy_body_example_write
+        _: ksy_body_example_unknown
+  - id: command
+    type: ksy_command_example
+  - id: extra
+    type: u1
+    valid: 0x55
+    if: type == 1 and count > 0
+  - id: payload
+    size-eos: true
+types:
+  ksy_header_example:
+    meta:
+      endian: be
+    seq:
+      - id: version
+        type: b4
+      - id: length
+        type: b4
+      - id: address
+        type: b24be
+      - id: flags
+        type: u2le
+  ksy_body_example_read:
+    meta:
+      endian: be
+    seq:
+      - id: address
+        type: u1
+  ksy_body_example_write:
+    meta:
+      endian: be
+    seq:
+      - id: v0
+        type: u1
+      - id: v1
+        type: strz
+        encoding: UTF-8
+  ksy_body_example_unknown: {}
+  ksy_command_example:
+    meta:
+      endian: be
+    seq:
+      - id: branch
+        type: u2be
+      - id: body
+        type:
+          switch-on: branch
+          cases:
+            0: ksy_command_example_start
+            1: ksy_command_example_stop
+  ksy_command_example_start: {}
+  ksy_command_example_stop:
+    meta:
+      endian: be
+    seq:
+      - id: code
+        type: u4
+");
+    assert_eq!(export.unsupported.is_empty(), true);
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, Describe)]
+pub struct KsyUnsupportedExample<T> {
+    #[jbytes(linend=b"\r\n")]
+    pub line: String,
+    #[jbytes(key=b"Host: ", linend=b"\n")]
+    pub host: String,
+    #[jbytes(offset=2, length_of="data")]
+    pub length: u8,
+    #[jbytes(length="length", bcd)]
+    pub data: u32,
+    pub value: T,
+}
+
+
+#[test]
+fn test_export_ksy_unsupported() {
+    let export = to_ksy(&<KsyUnsupportedExample<u8>>::SCHEMA);
+
+    // The unsupported modifiers are reported and added to the `doc`, the fields are exported without them.
+    assert_eq!(export.document, "\
+meta:
+  id: ksy_unsupported_example
+  endian: be
+seq:
+  - id: line
+    type: str
+    size-eos: true
+    encoding: UTF-8
+    doc: 'unsupported: linend'
+  - id: host
+    type: str
+    terminator: 10
+    encoding: UTF-8
+    doc: 'unsupported: key'
+  - size: 2
+  - id: length
+    type: u1
+  - id: data
+    type: u4
+    doc: 'unsupported: length, bcd'
+  - id: value
+    size-eos: true
+    doc: 'unsupported: type'
+");
+
+    let unsupported = |path: &str, key, value| Unsupported { path: path.to_string(), key, value };
+
+    assert_eq!(export.unsupported, [
+        unsupported("ksy_unsupported_example.line", "linend", Some("b\"\\r\\n\"")),
+        unsupported("ksy_unsupported_example.host", "key", Some("b\"Host: \"")),
+        unsupported("ksy_unsupported_example.data", "length", Some("length")),
+        unsupported("ksy_unsupported_example.data", "bcd", None),
+        unsupported("ksy_unsupported_example.value", "type", Some("T")),
+    ]);
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, Describe)]
+pub struct KsyCastExample {
+    pub len: u8,
+    #[jbytes(length="(len as usize) * 2")]
+    pub data: String,
+}
+
+
+#[test]
+fn test_export_ksy_cast() {
+    let export = to_ksy(&KsyCastExample::SCHEMA);
+
+    // The casts are dropped with the parentheses kept.
+    assert_eq!(export.document.contains("    size: (len) * 2\n"), true);
+    assert_eq!(export.unsupported.is_empty(), true);
+}
@@ -0,0 +1,134 @@
+use std::net::Ipv4Addr;
+use jbytes_derive::{ByteDecode, ByteEncode, Describe};
+use jbytes::prelude::*;
+use jbytes::export::{to_lua, Unsupported};
+
+
+/// Compares the generated dissector with the golden file in `tests/golden`,
+/// the golden file is rewritten with `JBYTES_UPDATE_GOLDEN=1`.
+fn assert_golden(name: &str, document: &str) {
+    let path = format!("{}/tests/golden/{name}", env!("CARGO_MANIFEST_DIR"));
+
+    if std::env::var("JBYTES_UPDATE_GOLDEN").is_ok() {
+        std::fs::write(&path, document).unwrap();
+    }
+
+    let golden = std::fs::read_to_string(&path).unwrap_or_default();
+    assert_eq!(document, golden, "the dissector isn't equal to the golden file `{path}`");
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, Describe)]
+#[jbytes(byteorder="LE")]
+pub struct LuaHeaderExample {
+    #[jbytes(bits_start=0xf000, untake)]
+    pub version: u16,
+    #[jbytes(bits=0x0fff)]
+    pub length: u16,
+    #[jbytes(length=3, byteorder="BE")]
+    pub address: u32,
+    pub src: Ipv4Addr,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, Describe)]
+#[jbytes(magic=b"JB")]
+pub struct LuaExample {
+    pub header: LuaHeaderExample,
+    pub count: u8,
+    #[jbytes(count="count")]
+    pub values: Vec<u16>,
+    #[jbytes(linend=b"\r\n")]
+    pub name: String,
+    #[jbytes(byte_count=2, encoding="utf16le")]
+    pub title: String,
+    pub r#type: u8,
+    #[jbytes(branch="r#type")]
+    pub body: LuaBodyExample,
+    pub command: LuaCommandExample,
+    #[jbytes(if_expr="r#type == 1 && count > 0")]
+    pub extra: Option<u8>,
+    #[jbytes(remaining)]
+    pub payload: Vec<u8>,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, Describe)]
+pub enum LuaBodyExample {
+    #[jbytes(branch_value=1)]
+    Read {
+        address: u8,
+    },
+    #[jbytes(branch_range="2..=3")]
+    Write(#[jbytes(discriminant)] u8, #[jbytes(cstr)] String),
+    #[jbytes(branch_default)]
+    Unknown,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, Describe)]
+#[repr(u16)]
+pub enum LuaCommandExample {
+    Start,
+    Stop {
+        code: u32,
+        headers: [LuaHeaderExample; 2],
+    },
+}
+
+
+#[test]
+fn test_export_lua() {
+    let export = to_lua(&LuaExample::SCHEMA);
+
+    assert_golden("lua_example.lua", &export.document);
+    assert_eq!(export.unsupported.is_empty(), true);
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, Describe)]
+pub struct LuaUnsupportedExample<T> {
+    #[jbytes(key=b"Host: ", linend=b"\n")]
+    pub host: String,
+    #[jbytes(length=2, bcd)]
+    pub data: u32,
+    #[jbytes(try_count=2)]
+    pub values: Vec<u8>,
+    pub value: T,
+}
+
+
+#[test]
+fn test_export_lua_unsupported() {
+    let export = to_lua(&<LuaUnsupportedExample<u8>>::SCHEMA);
+
+    // The fields which can't be expressed are raw bytes.
+    assert_golden("lua_unsupported_example.lua", &export.document);
+
+    let unsupported = |path: &str, key, value| Unsupported { path: path.to_string(), key, value };
+
+    assert_eq!(export.unsupported, [
+        unsupported("lua_unsupported_example.host", "key", Some("b\"Host: \"")),
+        unsupported("lua_unsupported_example.data", "bcd", None),
+        unsupported("lua_unsupported_example.values", "try_count", Some("2")),
+        unsupported("lua_unsupported_example.value", "type", Some("T")),
+    ]);
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode, Describe)]
+pub struct LuaCastExample {
+    pub len: u8,
+    #[jbytes(length="(len as usize) * 2")]
+    pub data: String,
+}
+
+
+#[test]
+fn test_export_lua_cast() {
+    let export = to_lua(&LuaCastExample::SCHEMA);
+
+    // The casts are dropped with the parentheses kept.
+    assert_eq!(export.document.contains("(v.len) * 2"), true);
+    assert_eq!(export.unsupported.is_empty(), true);
+}
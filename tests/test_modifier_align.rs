@@ -0,0 +1,99 @@
+use jbytes_derive::{ByteDecode, ByteEncode};
+use jbytes::prelude::*;
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+#[jbytes(align_fields=4)]
+pub struct AlignFieldsExample {
+    pub kind: u8,
+    #[jbytes(byte_count=4)]
+    pub name: String,
+    pub value: u32,
+}
+
+
+#[test]
+fn test_modifier_align_fields() {
+    let data = [
+        0x01, 0x00, 0x00, 0x00,       // kind + pad
+        0x00, 0x00, 0x00, 0x03,       // name length
+        0x61, 0x62, 0x63, 0x00,       // name + pad
+        0x00, 0x00, 0x00, 0x02,       // value
+    ];
+    let bytes = Bytes::new(data);
+    let value = AlignFieldsExample { kind: 1, name: "abc".to_string(), value: 2 };
+    assert_eq!(AlignFieldsExample::decode(&bytes).unwrap(), value);
+    assert_eq!(bytes.remaining_len(), 0);
+
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    // test insufficient pad bytes
+    let bytes = Bytes::new([0x01, 0x00, 0x00]);
+    assert_eq!(AlignFieldsExample::decode(&bytes).is_err(), true);
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+pub struct AlignInnerExample {
+    pub kind: u8,
+    #[jbytes(align=4, full=0xff)]
+    pub value: u16,
+    pub end: u8,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+pub struct AlignExample {
+    pub tag: u8,
+    pub inner: AlignInnerExample,
+}
+
+
+#[test]
+fn test_modifier_align() {
+    // The alignment is relative to the start of AlignInnerExample.
+    let data = [
+        0x01,                         // tag
+        0x02,                         // inner.kind
+        0x00, 0x03, 0xff,             // inner.value + pad
+        0x04,                         // inner.end
+    ];
+    let bytes = Bytes::new(data);
+    let value = AlignExample { tag: 1, inner: AlignInnerExample { kind: 2, value: 3, end: 4 } };
+    assert_eq!(AlignExample::decode(&bytes).unwrap(), value);
+    assert_eq!(bytes.remaining_len(), 0);
+
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+pub enum AlignEnumExample {
+    #[jbytes(branch_value=1)]
+    Read {
+        #[jbytes(align=4)]
+        address: u16,
+    },
+    #[jbytes(branch_value=2)]
+    Write(#[jbytes(align=2)] u8, u16),
+}
+
+
+#[test]
+fn test_modifier_align_enum() {
+    let data = [0x01, 0x00, 0x05, 0x00];
+    let bytes = Bytes::new(data);
+    let value = AlignEnumExample::Read { address: 5 };
+    assert_eq!(AlignEnumExample::decode(&bytes).unwrap(), value);
+    assert_eq!(bytes.remaining_len(), 0);
+
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    let data = [0x02, 0x05, 0x00, 0x06];
+    let bytes = Bytes::new(data);
+    let value = AlignEnumExample::Write(5, 6);
+    assert_eq!(AlignEnumExample::decode(&bytes).unwrap(), value);
+    assert_eq!(bytes.remaining_len(), 0);
+
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+}
@@ -0,0 +1,124 @@
+use jbytes_derive::{ByteDecode, ByteEncode};
+use jbytes::prelude::*;
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+pub struct ArgsHeaderExample {
+    pub alen: u8,
+    pub ver: u8,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+pub struct ArgsFrameExample {
+    pub hdr: ArgsHeaderExample,
+    #[jbytes(args(addr_len="hdr.alen as usize", version="hdr.ver"))]
+    pub body: ArgsBodyExample,
+    pub cmd: u8,
+    #[jbytes(branch="cmd", args(addr_len="hdr.alen as usize * 2"))]
+    pub item: ArgsItemExample,
+    #[jbytes(args(addr_len="1", version="hdr.ver"), count=2)]
+    pub bodies: Vec<ArgsBodyExample>,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+#[jbytes(args(addr_len: usize, version: u8))]
+pub struct ArgsBodyExample {
+    #[jbytes(count="addr_len")]
+    pub address: Vec<u8>,
+    #[jbytes(if_expr="version > 1")]
+    pub flags: Option<u16>,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+#[jbytes(args(addr_len: usize))]
+pub enum ArgsItemExample {
+    #[jbytes(branch_value=1)]
+    Read(#[jbytes(count="addr_len")] Vec<u8>),
+    #[jbytes(branch_value=2)]
+    Write {
+        #[jbytes(count="addr_len")]
+        address: Vec<u8>,
+        value: u8,
+    },
+}
+
+
+#[test]
+fn test_modifier_args() {
+    let data = b"\x01\x01\x0a\x01\x01\x02\x03\x04";
+    let value = ArgsFrameExample {
+        hdr: ArgsHeaderExample { alen: 1, ver: 1 },
+        body: ArgsBodyExample { address: vec![0x0a], flags: None },
+        cmd: 1,
+        item: ArgsItemExample::Read(vec![1, 2]),
+        bodies: vec![ArgsBodyExample { address: vec![3], flags: None }, ArgsBodyExample { address: vec![4], flags: None }],
+    };
+    assert_eq!(jbytes::decode::<_, ArgsFrameExample>(data).unwrap(), value);
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    // The arguments are passed to the enum and the elements of the list.
+    let data = b"\x02\x02\x0a\x0b\x00\x01\x02\x01\x02\x03\x04\xff\x05\x00\x02\x06\x00\x03";
+    let value = ArgsFrameExample {
+        hdr: ArgsHeaderExample { alen: 2, ver: 2 },
+        body: ArgsBodyExample { address: vec![0x0a, 0x0b], flags: Some(1) },
+        cmd: 2,
+        item: ArgsItemExample::Write { address: vec![1, 2, 3, 4], value: 0xff },
+        bodies: vec![ArgsBodyExample { address: vec![5], flags: Some(2) }, ArgsBodyExample { address: vec![6], flags: Some(3) }],
+    };
+    assert_eq!(jbytes::decode::<_, ArgsFrameExample>(data).unwrap(), value);
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+pub struct ArgsTypedFrameExample {
+    pub tag: [u8; 2],
+    pub offset: u64,
+    #[jbytes(args(tag="tag", offset="offset"))]
+    pub body: ArgsTypedBodyExample,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+#[jbytes(args(tag: [u8; 2], offset: u64))]
+pub struct ArgsTypedBodyExample {
+    #[jbytes(if_expr="tag == [0x01, 0x02]")]
+    pub flags: Option<u8>,
+    #[jbytes(count="(offset >> 32) as usize")]
+    pub value: Vec<u8>,
+}
+
+
+#[test]
+fn test_modifier_args_typed() {
+    let data = b"\x01\x02\x00\x00\x00\x02\x00\x00\x00\x00\xff\x0a\x0b";
+    let value = ArgsTypedFrameExample {
+        tag: [0x01, 0x02],
+        offset: 0x2_0000_0000,
+        body: ArgsTypedBodyExample { flags: Some(0xff), value: vec![0x0a, 0x0b] },
+    };
+    assert_eq!(jbytes::decode::<_, ArgsTypedFrameExample>(data).unwrap(), value);
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    let data = b"\x00\x00\x00\x00\x00\x01\x00\x00\x00\x00\x0a";
+    let value = ArgsTypedFrameExample {
+        tag: [0x00, 0x00],
+        offset: 0x1_0000_0000,
+        body: ArgsTypedBodyExample { flags: None, value: vec![0x0a] },
+    };
+    assert_eq!(jbytes::decode::<_, ArgsTypedFrameExample>(data).unwrap(), value);
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+}
+
+
+#[test]
+fn test_modifier_args_missing() {
+    let error = jbytes::decode::<_, ArgsBodyExample>(b"\x01\x02").unwrap_err();
+    assert_eq!(error.code, ErrorKind::MissingArgument("addr_len".to_string()));
+
+    let value = ArgsBodyExample { address: vec![1], flags: None };
+    assert_eq!(jbytes::encode(value).unwrap_err().code, ErrorKind::MissingArgument("addr_len".to_string()));
+}
@@ -0,0 +1,40 @@
+use jbytes_derive::{ByteDecode, ByteEncode};
+use jbytes::prelude::*;
+
+
+#[derive(Debug, PartialEq, Eq, ByteDecode, ByteEncode)]
+pub struct AsciiExample {
+    #[jbytes(ascii_dec, length=6)]
+    pub amount: u32,
+    #[jbytes(ascii_dec, length=4)]
+    pub offset: i16,
+    #[jbytes(ascii_hex)]
+    pub crc: u16,
+    #[jbytes(ascii_dec, linend=b"\r\n")]
+    pub count: u8,
+}
+
+
+#[test]
+fn test_modifier_ascii() {
+    let data = b"001234-0121AF0255\r\n";
+    let bytes = Bytes::new(data);
+    let value = AsciiExample::decode(&bytes).unwrap();
+    assert_eq!(value, AsciiExample { amount: 1234, offset: -12, crc: 0x1af0, count: 255 });
+    assert_eq!(bytes.remaining_len(), 0);
+
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    // space padding and lower-case hex digits are accepted.
+    let bytes = Bytes::new(b"  1234 -121af0255\r\n");
+    let value = AsciiExample::decode(&bytes).unwrap();
+    assert_eq!(value, AsciiExample { amount: 1234, offset: -12, crc: 0x1af0, count: 255 });
+
+    // invalid digit
+    let bytes = Bytes::new(b"0012x4-0121AF0255\r\n");
+    assert_eq!(AsciiExample::decode(&bytes), Err(make_error(6, ErrorKind::InvalidDigit(b'x'))));
+
+    // value too large
+    let bytes = Bytes::new(b"001234-0121AF0256\r\n");
+    assert_eq!(AsciiExample::decode(&bytes), Err(make_error(19, ErrorKind::InvalidValue("256".to_string()))));
+}
@@ -0,0 +1,37 @@
+use jbytes_derive::{ByteDecode, ByteEncode};
+use jbytes::prelude::*;
+
+
+#[derive(Debug, PartialEq, Eq, ByteDecode, ByteEncode)]
+pub struct BcdExample {
+    #[jbytes(bcd)]
+    pub a: u16,
+    #[jbytes(bcd, length=3)]
+    pub b: u32,
+    #[jbytes(bcd, length=2)]
+    pub c: i32,
+}
+
+
+#[test]
+fn test_modifier_bcd() {
+    let data = [
+        0x12, 0x34,                     // a
+        0x00, 0x56, 0x78,               // b
+        0x12, 0x3d,                     // c
+    ];
+    let bytes = Bytes::new(data);
+    let value = BcdExample::decode(&bytes).unwrap();
+    assert_eq!(value, BcdExample { a: 1234, b: 5678, c: -123 });
+    assert_eq!(bytes.remaining_len(), 0);
+
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    // invalid digit
+    let bytes = Bytes::new([0x12, 0x3a, 0x00, 0x56, 0x78, 0x12, 0x3d]);
+    assert_eq!(BcdExample::decode(&bytes), Err(make_error(2, ErrorKind::InvalidDigit(0x3a))));
+
+    // value too large
+    let value = BcdExample { a: 12345, b: 5678, c: -123 };
+    assert_eq!(jbytes::encode(value).is_err(), true);
+}
@@ -102,3 +102,79 @@ fn test_modifier_branch_default2() {
     assert_eq!(BranchExample2::decode(&bytes).is_err(), true);
     assert_eq!(bytes.remaining_len(), 0);
 }
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+#[jbytes(byte_count=2)]
+enum BranchDefaultExampleBody3 {
+    #[jbytes(branch_value=1)]
+    V0 {
+        value: u8,
+    },
+    #[jbytes(branch_default)]
+    Unknown {
+        #[jbytes(discriminant)]
+        code: u16,
+        #[jbytes(remaining)]
+        body: Vec<u8>,
+    },
+}
+
+
+#[test]
+fn test_modifier_branch_default3() {
+    let data = [
+        0x00, 0x01,                   // type
+        0x02,                         // value
+    ];
+    let bytes = Bytes::new(data);
+    let value = BranchDefaultExampleBody3::V0 { value: 2 };
+    assert_eq!(BranchDefaultExampleBody3::decode(&bytes).unwrap(), value);
+    assert_eq!(bytes.remaining_len(), 0);
+
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    // unknown type is forwarded byte-for-byte
+    let data = [
+        0x12, 0x34,                   // type
+        0x01, 0x02, 0x03,             // body
+    ];
+    let bytes = Bytes::new(data);
+    let value = BranchDefaultExampleBody3::Unknown { code: 0x1234, body: vec![0x01, 0x02, 0x03] };
+    assert_eq!(BranchDefaultExampleBody3::decode(&bytes).unwrap(), value);
+    assert_eq!(bytes.remaining_len(), 0);
+
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+struct BranchExample4 {
+    pub cmd: u16,
+    #[jbytes(branch="cmd")]
+    pub body: BranchDefaultExampleBody4,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+enum BranchDefaultExampleBody4 {
+    #[jbytes(branch_value=1)]
+    V0,
+    #[jbytes(branch_default)]
+    Unknown(#[jbytes(discriminant)] u16, #[jbytes(remaining)] Vec<u8>),
+}
+
+
+#[test]
+fn test_modifier_branch_default4() {
+    let data = [
+        0x00, 0x1f,                   // cmd
+        0x01, 0x02,                   // body
+    ];
+    let bytes = Bytes::new(data);
+    let value = BranchExample4 { cmd: 0x1f, body: BranchDefaultExampleBody4::Unknown(0x1f, vec![0x01, 0x02]) };
+    assert_eq!(BranchExample4::decode(&bytes).unwrap(), value);
+    assert_eq!(bytes.remaining_len(), 0);
+
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+}
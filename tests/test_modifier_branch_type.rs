@@ -0,0 +1,204 @@
+use jbytes_derive::{ByteDecode, ByteEncode};
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+struct HttpRequestExample {
+    #[jbytes(linend=b"\x20")]
+    pub method: String,
+    #[jbytes(branch="method")]
+    pub body: HttpBodyExample,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+#[jbytes(branch_type="str")]
+enum HttpBodyExample {
+    #[jbytes(branch_value="\"GET\" | \"HEAD\"")]
+    Empty,
+    #[jbytes(branch_value="\"POST\"")]
+    Post(#[jbytes(remaining)] Vec<u8>),
+    #[jbytes(branch_value="method if method.starts_with('X')")]
+    Extension(#[jbytes(discriminant)] String, #[jbytes(remaining)] Vec<u8>),
+}
+
+
+#[test]
+fn test_modifier_branch_type_str() {
+    let data = b"GET ";
+    let value = HttpRequestExample { method: "GET".to_string(), body: HttpBodyExample::Empty };
+    assert_eq!(jbytes::decode::<_, HttpRequestExample>(data).unwrap(), value);
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    let data = b"HEAD ";
+    assert_eq!(jbytes::decode::<_, HttpRequestExample>(data).unwrap().body, HttpBodyExample::Empty);
+
+    let data = b"POST abc";
+    let value = HttpRequestExample { method: "POST".to_string(), body: HttpBodyExample::Post(b"abc".to_vec()) };
+    assert_eq!(jbytes::decode::<_, HttpRequestExample>(data).unwrap(), value);
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    // guard and discriminant
+    let data = b"XPING 1";
+    let value = HttpRequestExample {
+        method: "XPING".to_string(),
+        body: HttpBodyExample::Extension("XPING".to_string(), b"1".to_vec()),
+    };
+    assert_eq!(jbytes::decode::<_, HttpRequestExample>(data).unwrap(), value);
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    assert_eq!(jbytes::decode::<_, HttpRequestExample>(b"PUT ").is_err(), true);
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+enum CommandExample {
+    Read,
+    Write,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+struct CommandFrameExample {
+    pub command: CommandExample,
+    #[jbytes(branch="command")]
+    pub body: CommandBodyExample,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+#[jbytes(branch_type="CommandExample")]
+enum CommandBodyExample {
+    #[jbytes(branch_value="CommandExample::Read")]
+    Read { address: u16 },
+    #[jbytes(branch_value="CommandExample::Write")]
+    Write { address: u16, value: u8 },
+}
+
+
+#[test]
+fn test_modifier_branch_type_enum() {
+    let data = b"\x00\x00\x10";
+    let value = CommandFrameExample { command: CommandExample::Read, body: CommandBodyExample::Read { address: 0x10 } };
+    assert_eq!(jbytes::decode::<_, CommandFrameExample>(data).unwrap(), value);
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    let data = b"\x01\x00\x10\xff";
+    let value = CommandFrameExample { command: CommandExample::Write, body: CommandBodyExample::Write { address: 0x10, value: 0xff } };
+    assert_eq!(jbytes::decode::<_, CommandFrameExample>(data).unwrap(), value);
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+struct DnsRecordExample {
+    pub rtype: u16,
+    pub class: u16,
+    #[jbytes(branch="(class, rtype)")]
+    pub data: DnsRecordDataExample,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+#[jbytes(branch_type="(u16, u16)")]
+enum DnsRecordDataExample {
+    #[jbytes(branch_value="(1, 1)")]
+    A([u8; 4]),
+    #[jbytes(branch_value="(_, 16)")]
+    Txt(#[jbytes(remaining)] String),
+    #[jbytes(branch_value="(class, _) if class >= 0xff00")]
+    Private(#[jbytes(remaining)] Vec<u8>),
+    #[jbytes(branch_default)]
+    Unknown,
+}
+
+
+#[test]
+fn test_modifier_branch_type_tuple() {
+    let data = b"\x00\x01\x00\x01\x7f\x00\x00\x01";
+    let value = DnsRecordExample { rtype: 1, class: 1, data: DnsRecordDataExample::A([127, 0, 0, 1]) };
+    assert_eq!(jbytes::decode::<_, DnsRecordExample>(data).unwrap(), value);
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    let data = b"\x00\x10\x00\x03abc";
+    let value = DnsRecordExample { rtype: 16, class: 3, data: DnsRecordDataExample::Txt("abc".to_string()) };
+    assert_eq!(jbytes::decode::<_, DnsRecordExample>(data).unwrap(), value);
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    let data = b"\x00\x02\xff\x01\x01";
+    let value = DnsRecordExample { rtype: 2, class: 0xff01, data: DnsRecordDataExample::Private(vec![1]) };
+    assert_eq!(jbytes::decode::<_, DnsRecordExample>(data).unwrap(), value);
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    let data = b"\x00\x02\x00\x01";
+    let value = DnsRecordExample { rtype: 2, class: 1, data: DnsRecordDataExample::Unknown };
+    assert_eq!(jbytes::decode::<_, DnsRecordExample>(data).unwrap(), value);
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+struct MagicFrameExample {
+    pub magic: [u8; 2],
+    #[jbytes(branch="magic")]
+    pub body: MagicBodyExample,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+#[jbytes(branch_type="[u8]")]
+enum MagicBodyExample {
+    #[jbytes(branch_value=b"\x1f\x8b")]
+    Gzip(u8),
+    #[jbytes(branch_value=b"PK")]
+    Zip(u16),
+}
+
+
+#[test]
+fn test_modifier_branch_type_bytes() {
+    let data = b"\x1f\x8b\x08";
+    let value = MagicFrameExample { magic: [0x1f, 0x8b], body: MagicBodyExample::Gzip(8) };
+    assert_eq!(jbytes::decode::<_, MagicFrameExample>(data).unwrap(), value);
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    let data = b"PK\x00\x14";
+    let value = MagicFrameExample { magic: *b"PK", body: MagicBodyExample::Zip(0x14) };
+    assert_eq!(jbytes::decode::<_, MagicFrameExample>(data).unwrap(), value);
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    assert_eq!(jbytes::decode::<_, MagicFrameExample>(b"\x00\x00\x00").is_err(), true);
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+struct TagFrameExample {
+    pub tag: [u8; 2],
+    #[jbytes(branch="tag")]
+    pub body: TagBodyExample,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+#[jbytes(branch_type="[u8; 2]")]
+enum TagBodyExample {
+    #[jbytes(branch_value="[0x01, 0x02]")]
+    Version(u8),
+    #[jbytes(branch_value="[0xff, tag] if tag >= 0x80")]
+    Vendor(#[jbytes(discriminant)] [u8; 2], u16),
+}
+
+
+#[test]
+fn test_modifier_branch_type_array() {
+    let data = b"\x01\x02\x03";
+    let value = TagFrameExample { tag: [0x01, 0x02], body: TagBodyExample::Version(3) };
+    assert_eq!(jbytes::decode::<_, TagFrameExample>(data).unwrap(), value);
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    let data = b"\xff\x81\x00\x01";
+    let value = TagFrameExample { tag: [0xff, 0x81], body: TagBodyExample::Vendor([0xff, 0x81], 1) };
+    assert_eq!(jbytes::decode::<_, TagFrameExample>(data).unwrap(), value);
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    assert_eq!(jbytes::decode::<_, TagFrameExample>(b"\xff\x01\x00\x01").is_err(), true);
+}
@@ -0,0 +1,114 @@
+use jbytes_derive::{ByteDecode, ByteEncode};
+use jbytes::prelude::*;
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+pub struct TiffExample {
+    pub order: [u8; 2],
+    #[jbytes(byteorder_from="if order == *b\"II\" { ByteOrder::Le } else { ByteOrder::Be }")]
+    pub header: TiffHeaderExample,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+pub struct TiffHeaderExample {
+    pub magic: u16,
+    pub offset: u32,
+    pub entry: TiffEntryExample,
+    #[jbytes(byteorder="BE")]
+    pub entry_be: TiffEntryExample,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+pub struct TiffEntryExample {
+    pub tag: u16,
+    #[jbytes(count=2)]
+    pub values: Vec<u16>,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+#[jbytes(byteorder="BE")]
+pub struct TiffEntryBeExample {
+    pub tag: u16,
+}
+
+
+#[test]
+fn test_modifier_byteorder_from() {
+    let value = |order: &[u8; 2]| TiffExample {
+        order: *order,
+        header: TiffHeaderExample {
+            magic: 42,
+            offset: 8,
+            entry: TiffEntryExample { tag: 0x0100, values: vec![1, 2] },
+            entry_be: TiffEntryExample { tag: 0x0100, values: vec![1, 2] },
+        },
+    };
+
+    // The byte order is passed to the whole subtree.
+    let data = b"II\x2a\x00\x08\x00\x00\x00\x00\x01\x01\x00\x02\x00\x01\x00\x00\x01\x00\x02";
+    assert_eq!(jbytes::decode::<_, TiffExample>(data).unwrap(), value(b"II"));
+    assert_eq!(*jbytes::encode(value(b"II")).unwrap(), data);
+
+    let data = b"MM\x00\x2a\x00\x00\x00\x08\x01\x00\x00\x01\x00\x02\x01\x00\x00\x01\x00\x02";
+    assert_eq!(jbytes::decode::<_, TiffExample>(data).unwrap(), value(b"MM"));
+    assert_eq!(*jbytes::encode(value(b"MM")).unwrap(), data);
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+pub struct SubtreeOverrideExample {
+    #[jbytes(byteorder_from="ByteOrder::Le")]
+    pub value: (u16, TiffEntryBeExample),
+}
+
+
+#[test]
+fn test_modifier_byteorder_from_override() {
+    // The explicit byte order of a nested type wins.
+    let data = b"\x01\x00\x00\x01";
+    let value = SubtreeOverrideExample { value: (1, TiffEntryBeExample { tag: 1 }) };
+    assert_eq!(jbytes::decode::<_, SubtreeOverrideExample>(data).unwrap(), value);
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+#[jbytes(byteorder="LE")]
+pub struct InheritExample {
+    pub inherit: InheritChildExample,
+    pub default: DefaultChildExample,
+    #[jbytes(byteorder="BE")]
+    pub inherit_be: InheritChildExample,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+#[jbytes(byteorder="inherit")]
+pub struct InheritChildExample {
+    pub value: u16,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+pub struct DefaultChildExample {
+    pub value: u16,
+}
+
+
+#[test]
+fn test_modifier_byteorder_inherit() {
+    let data = b"\x01\x00\x00\x01\x00\x01";
+    let value = InheritExample {
+        inherit: InheritChildExample { value: 1 },
+        default: DefaultChildExample { value: 1 },
+        inherit_be: InheritChildExample { value: 1 },
+    };
+    assert_eq!(jbytes::decode::<_, InheritExample>(data).unwrap(), value);
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    // The default byte order is used without the parent.
+    assert_eq!(jbytes::decode::<_, InheritChildExample>(b"\x00\x01").unwrap(), InheritChildExample { value: 1 });
+}
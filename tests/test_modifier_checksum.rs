@@ -0,0 +1,143 @@
+use std::net::Ipv4Addr;
+use jbytes_derive::{ByteDecode, ByteEncode};
+use jbytes::prelude::*;
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+pub struct Ipv4HeaderExample {
+    pub version_ihl: u8,
+    pub tos: u8,
+    pub total_length: u16,
+    pub identification: u16,
+    pub flags_fragment: u16,
+    pub ttl: u8,
+    pub protocol: u8,
+    #[jbytes(checksum="internet", range="start..end")]
+    pub checksum: u16,
+    pub src: Ipv4Addr,
+    pub dst: Ipv4Addr,
+}
+
+
+#[test]
+fn test_modifier_checksum_internet() {
+    let data = b"\x45\x00\x00\x73\x00\x00\x40\x00\x40\x11\xb8\x61\xc0\xa8\x00\x01\xc0\xa8\x00\xc7";
+    let bytes = Bytes::new(data);
+    let mut value = Ipv4HeaderExample {
+        version_ihl: 0x45,
+        tos: 0,
+        total_length: 0x73,
+        identification: 0,
+        flags_fragment: 0x4000,
+        ttl: 64,
+        protocol: 17,
+        checksum: 0xb861,
+        src: Ipv4Addr::new(192, 168, 0, 1),
+        dst: Ipv4Addr::new(192, 168, 0, 199),
+    };
+    assert_eq!(Ipv4HeaderExample::decode(&bytes).unwrap(), value);
+    assert_eq!(bytes.remaining_len(), 0);
+
+    // The checksum is calculated on encode.
+    value.checksum = 0;
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    // test checksum mismatch
+    let bytes = Bytes::new(b"\x45\x00\x00\x73\x00\x00\x40\x00\x40\x11\xb8\x62\xc0\xa8\x00\x01\xc0\xa8\x00\xc7");
+    let error = Ipv4HeaderExample::decode(&bytes).unwrap_err();
+    assert_eq!(error.code, ErrorKind::InvalidChecksum(0xb861, 0xb862));
+    assert_eq!(error.position, 10);
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+pub struct ModbusRtuExample {
+    pub slave_id: u8,
+    pub function: u8,
+    pub address: u16,
+    pub quantity: u16,
+    #[jbytes(checksum="crc16_modbus", byteorder="LE")]
+    pub crc: u16,
+}
+
+
+#[test]
+fn test_modifier_checksum_crc16() {
+    let data = b"\x01\x03\x00\x00\x00\x0a\xc5\xcd";
+    let bytes = Bytes::new(data);
+    let value = ModbusRtuExample { slave_id: 1, function: 3, address: 0, quantity: 10, crc: 0xcdc5 };
+    assert_eq!(ModbusRtuExample::decode(&bytes).unwrap(), value);
+    assert_eq!(bytes.remaining_len(), 0);
+
+    assert_eq!(*jbytes::encode(ModbusRtuExample { crc: 0, ..value }).unwrap(), data);
+
+    // The positions are relative to the start of the struct.
+    let mut buffer = Buffer::new();
+    buffer.push_bytes(b"\xff\xff").unwrap();
+    assert_eq!(ModbusRtuExample { slave_id: 1, function: 3, address: 0, quantity: 10, crc: 0 }.encode(&mut buffer).unwrap(), 8);
+    assert_eq!(*buffer, b"\xff\xff\x01\x03\x00\x00\x00\x0a\xc5\xcd");
+
+    let bytes = Bytes::new(b"\xff\x01\x03\x00\x00\x00\x0a\xc5\xcd");
+    bytes.advance(1);
+    assert_eq!(ModbusRtuExample::decode(&bytes).unwrap(), value);
+
+    // test checksum mismatch
+    let bytes = Bytes::new(b"\x01\x03\x00\x00\x00\x0b\xc5\xcd");
+    assert_eq!(ModbusRtuExample::decode(&bytes).unwrap_err().code, ErrorKind::InvalidChecksum(0x0d04, 0xcdc5));
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+pub struct XorHeaderExample {
+    pub length: u8,
+    #[jbytes(checksum="xor", range="data..end")]
+    pub xor: u8,
+    #[jbytes(count="length")]
+    pub data: Vec<u8>,
+}
+
+
+#[test]
+fn test_modifier_checksum_field_range() {
+    let data = b"\x05\x01\x01\x02\x03\x04\x05";
+    let bytes = Bytes::new(data);
+    let value = XorHeaderExample { length: 5, xor: 0x01, data: vec![1, 2, 3, 4, 5] };
+    assert_eq!(XorHeaderExample::decode(&bytes).unwrap(), value);
+    assert_eq!(*jbytes::encode(XorHeaderExample { xor: 0, ..value }).unwrap(), data);
+
+    let bytes = Bytes::new(b"\x05\x00\x01\x02\x03\x04\x05");
+    assert_eq!(XorHeaderExample::decode(&bytes).unwrap_err().code, ErrorKind::InvalidChecksum(0x01, 0x00));
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+pub enum ChecksumEnumExample {
+    #[jbytes(branch_value=1)]
+    Crc32(#[jbytes(length=9)] String, #[jbytes(checksum="crc32", range="0..1")] u32),
+    #[jbytes(branch_value=2)]
+    Sum8 {
+        value: u8,
+        #[jbytes(checksum="sum8")]
+        sum: u8,
+    },
+}
+
+
+#[test]
+fn test_modifier_checksum_enum() {
+    let data = b"\x01123456789\xcb\xf4\x39\x26";
+    let bytes = Bytes::new(data);
+    let value = ChecksumEnumExample::Crc32("123456789".to_string(), 0xcbf43926);
+    assert_eq!(ChecksumEnumExample::decode(&bytes).unwrap(), value);
+    assert_eq!(*jbytes::encode(ChecksumEnumExample::Crc32("123456789".to_string(), 0)).unwrap(), data);
+
+    // The default range starts with the enum discriminant.
+    let data = b"\x02\xff\x01";
+    let bytes = Bytes::new(data);
+    let value = ChecksumEnumExample::Sum8 { value: 0xff, sum: 0x01 };
+    assert_eq!(ChecksumEnumExample::decode(&bytes).unwrap(), value);
+    assert_eq!(*jbytes::encode(ChecksumEnumExample::Sum8 { value: 0xff, sum: 0 }).unwrap(), data);
+
+    let bytes = Bytes::new(b"\x02\xff\x00");
+    assert_eq!(ChecksumEnumExample::decode(&bytes).unwrap_err().code, ErrorKind::InvalidChecksum(0x01, 0x00));
+}
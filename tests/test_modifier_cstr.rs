@@ -0,0 +1,52 @@
+use jbytes_derive::{ByteDecode, ByteEncode};
+use jbytes::prelude::*;
+
+
+#[derive(Debug, PartialEq, Eq, ByteDecode, ByteEncode)]
+pub struct CStrExample {
+    #[jbytes(cstr)]
+    pub filename: String,
+    #[jbytes(cstr, length=8)]
+    pub mode: String,
+    pub flags: u8,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteDecode, ByteEncode)]
+pub struct CStrPadExample {
+    #[jbytes(length=6, cstr, pad=0x20)]
+    pub name: String,
+}
+
+
+#[test]
+fn test_modifier_cstr() {
+    let data = b"boot.bin\x00octet\x00\x00\x00\x01";
+    let bytes = Bytes::new(data);
+    let value = CStrExample::decode(&bytes).unwrap();
+    assert_eq!(value, CStrExample { filename: "boot.bin".to_string(), mode: "octet".to_string(), flags: 1 });
+    assert_eq!(bytes.remaining_len(), 0);
+
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    // bytes after the NUL byte of a fixed-width field are ignored.
+    let bytes = Bytes::new(b"boot.bin\x00octet\x00\xff\xff\x01");
+    let value = CStrExample::decode(&bytes).unwrap();
+    assert_eq!(value, CStrExample { filename: "boot.bin".to_string(), mode: "octet".to_string(), flags: 1 });
+
+    // missing NUL byte
+    let bytes = Bytes::new(b"boot.bin");
+    assert_eq!(CStrExample::decode(&bytes).is_err(), true);
+}
+
+
+#[test]
+fn test_modifier_cstr_pad() {
+    let bytes = Bytes::new(b"abc\x00\x20\x20");
+    let value = CStrPadExample::decode(&bytes).unwrap();
+    assert_eq!(value, CStrPadExample { name: "abc".to_string() });
+    assert_eq!(bytes.remaining_len(), 0);
+
+    // The NUL byte is written before the padding.
+    assert_eq!(*jbytes::encode(value).unwrap(), b"abc\x00\x20\x20");
+}
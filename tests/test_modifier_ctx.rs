@@ -0,0 +1,78 @@
+use jbytes_derive::{ByteDecode, ByteEncode};
+use jbytes::prelude::*;
+
+
+#[derive(Debug, Default)]
+pub struct ProtocolCtx {
+    pub version: u8,
+    pub name_len: usize,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+#[jbytes(ctx="ProtocolCtx")]
+pub struct CtxFrameExample {
+    pub cmd: u8,
+    #[jbytes(length="ctx.name_len")]
+    pub name: String,
+    #[jbytes(if_expr="ctx.version > 1")]
+    pub flags: Option<u16>,
+    pub body: CtxBodyExample,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+#[jbytes(ctx="ProtocolCtx")]
+pub struct CtxBodyExample {
+    #[jbytes(length="if ctx.version > 1 { 2 } else { 1 }")]
+    pub length: usize,
+    #[jbytes(count="length")]
+    pub items: Vec<CtxItemExample>,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+#[jbytes(ctx="ProtocolCtx")]
+pub enum CtxItemExample {
+    #[jbytes(branch_value=1)]
+    Short(#[jbytes(length="ctx.name_len")] String),
+    #[jbytes(branch_value=2)]
+    Long(u16),
+}
+
+
+#[test]
+fn test_modifier_ctx() {
+    let ctx = ProtocolCtx { version: 1, name_len: 2 };
+    let data = b"\x01ab\x02\x01cd\x02\x00\x03";
+    let value = CtxFrameExample {
+        cmd: 1,
+        name: "ab".to_string(),
+        flags: None,
+        body: CtxBodyExample { length: 2, items: vec![CtxItemExample::Short("cd".to_string()), CtxItemExample::Long(3)] },
+    };
+    assert_eq!(jbytes::decode_with_ctx::<CtxFrameExample, ProtocolCtx>(data, &ctx).unwrap(), value);
+    assert_eq!(*jbytes::encode_with_ctx(value, &ctx).unwrap(), data);
+
+    // The context is passed to the nested types.
+    let ctx = ProtocolCtx { version: 2, name_len: 3 };
+    let data = b"\x01abc\x00\x01\x00\x01\x01cde";
+    let value = CtxFrameExample {
+        cmd: 1,
+        name: "abc".to_string(),
+        flags: Some(1),
+        body: CtxBodyExample { length: 1, items: vec![CtxItemExample::Short("cde".to_string())] },
+    };
+    assert_eq!(jbytes::decode_with_ctx::<CtxFrameExample, ProtocolCtx>(data, &ctx).unwrap(), value);
+    assert_eq!(*jbytes::encode_with_ctx(value, &ctx).unwrap(), data);
+}
+
+
+#[test]
+fn test_modifier_ctx_missing() {
+    let error = jbytes::decode::<_, CtxFrameExample>(b"\x01ab\x02").unwrap_err();
+    assert_eq!(error.code, ErrorKind::MissingContext);
+
+    let value = CtxBodyExample { length: 0, items: vec![] };
+    assert_eq!(jbytes::encode(value).unwrap_err().code, ErrorKind::MissingContext);
+}
@@ -0,0 +1,62 @@
+use jbytes_derive::{ByteDecode, ByteEncode};
+use jbytes::prelude::*;
+
+
+#[derive(Debug, PartialEq, Eq, ByteDecode, ByteEncode)]
+pub struct EncodingExample {
+    #[jbytes(encoding="utf16le", byte_count=2, byteorder="LE")]
+    pub domain: String,
+    #[jbytes(encoding="utf16le", byte_count=2, byteorder="LE", code_units)]
+    pub user: String,
+    #[jbytes(encoding="utf16be", cstr)]
+    pub host: String,
+    #[jbytes(encoding="latin1", length=4)]
+    pub name: String,
+}
+
+
+#[test]
+fn test_modifier_encoding() {
+    let data = [
+        0x04, 0x00, b'a', 0x00, b'b', 0x00,         // domain, 4 bytes
+        0x02, 0x00, b'j', 0x00, b'k', 0x00,         // user, 2 code units
+        0x00, b'h', 0x00, 0x00,                     // host
+        b'c', b'a', b'f', 0xe9,                     // name
+    ];
+    let bytes = Bytes::new(data);
+    let value = EncodingExample::decode(&bytes).unwrap();
+    assert_eq!(value, EncodingExample {
+        domain: "ab".to_string(),
+        user: "jk".to_string(),
+        host: "h".to_string(),
+        name: "café".to_string(),
+    });
+    assert_eq!(bytes.remaining_len(), 0);
+
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    // unpaired surrogate
+    let bytes = Bytes::new([0x02, 0x00, 0x3d, 0xd8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, b'c', b'a', b'f', 0xe9]);
+    assert_eq!(EncodingExample::decode(&bytes).is_err(), true);
+}
+
+
+#[cfg(feature = "encoding_rs")]
+#[derive(Debug, PartialEq, Eq, ByteDecode, ByteEncode)]
+pub struct GbkExample {
+    #[jbytes(encoding="gbk")]
+    pub name: String,
+}
+
+
+#[cfg(feature = "encoding_rs")]
+#[test]
+fn test_modifier_encoding_gbk() {
+    let data = b"\x04\xd6\xd0\xce\xc4";
+    let bytes = Bytes::new(data);
+    let value = GbkExample::decode(&bytes).unwrap();
+    assert_eq!(value, GbkExample { name: "中文".to_string() });
+    assert_eq!(bytes.remaining_len(), 0);
+
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+}
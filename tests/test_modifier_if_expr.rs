@@ -56,4 +56,73 @@ fn test_modifier_if_expr_enum_example() {
     assert_eq!(bytes.remaining_len(), 0);
 
     assert_eq!(*jbytes::encode(value).unwrap(), b"\x01\x01\x00\x31");
-}
\ No newline at end of file
+}
+
+#[derive(Debug, PartialEq, Eq, ByteDecode, ByteEncode)]
+pub struct VersionExample {
+    pub version: u8,
+    #[jbytes(if_expr="version >= 2", default=0xff)]
+    pub flags: u8,
+    #[jbytes(if_expr="version >= 2 && flags & 0x01 != 0")]
+    pub length: u16,
+    pub value: u8,
+}
+
+
+#[test]
+fn test_modifier_if_expr_default() {
+    let bytes = Bytes::new(b"\x01\x31");
+    let value = VersionExample::decode(&bytes).unwrap();
+    assert_eq!(value, VersionExample { version: 1, flags: 0xff, length: 0, value: 0x31 });
+    assert_eq!(bytes.remaining_len(), 0);
+
+    assert_eq!(*jbytes::encode(value).unwrap(), b"\x01\x31");
+
+    let bytes = Bytes::new(b"\x02\x01\x00\x02\x31");
+    let value = VersionExample::decode(&bytes).unwrap();
+    assert_eq!(value, VersionExample { version: 2, flags: 0x01, length: 2, value: 0x31 });
+    assert_eq!(bytes.remaining_len(), 0);
+
+    assert_eq!(*jbytes::encode(value).unwrap(), b"\x02\x01\x00\x02\x31");
+
+    // The field is skipped on encode when the condition is false.
+    let value = VersionExample { version: 1, flags: 0x01, length: 2, value: 0x31 };
+    assert_eq!(*jbytes::encode(value).unwrap(), b"\x01\x31");
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteDecode, ByteEncode)]
+pub enum VersionEnumExample {
+    #[jbytes(branch_value=1)]
+    Read {
+        version: u8,
+        #[jbytes(if_expr="version >= 2")]
+        value: u16,
+    },
+    #[jbytes(branch_value=2)]
+    Write(u8, #[jbytes(if_expr="v0 >= 2", default="\"v1\".to_string()", length=2)] String),
+}
+
+
+#[test]
+fn test_modifier_if_expr_default_enum() {
+    let bytes = Bytes::new(b"\x01\x01");
+    let value = VersionEnumExample::decode(&bytes).unwrap();
+    assert_eq!(value, VersionEnumExample::Read { version: 1, value: 0 });
+    assert_eq!(*jbytes::encode(value).unwrap(), b"\x01\x01");
+
+    let bytes = Bytes::new(b"\x01\x02\x00\x31");
+    let value = VersionEnumExample::decode(&bytes).unwrap();
+    assert_eq!(value, VersionEnumExample::Read { version: 2, value: 0x31 });
+    assert_eq!(*jbytes::encode(value).unwrap(), b"\x01\x02\x00\x31");
+
+    let bytes = Bytes::new(b"\x02\x01");
+    let value = VersionEnumExample::decode(&bytes).unwrap();
+    assert_eq!(value, VersionEnumExample::Write(1, "v1".to_string()));
+    assert_eq!(*jbytes::encode(value).unwrap(), b"\x02\x01");
+
+    let bytes = Bytes::new(b"\x02\x02v2");
+    let value = VersionEnumExample::decode(&bytes).unwrap();
+    assert_eq!(value, VersionEnumExample::Write(2, "v2".to_string()));
+    assert_eq!(*jbytes::encode(value).unwrap(), b"\x02\x02v2");
+}
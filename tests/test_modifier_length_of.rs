@@ -0,0 +1,132 @@
+use jbytes_derive::{ByteDecode, ByteEncode};
+use jbytes::prelude::*;
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+pub struct LengthOfExample {
+    #[jbytes(length_of="value")]
+    pub length: u16,
+    #[jbytes(length="length")]
+    pub value: String,
+    #[jbytes(count_of="items")]
+    pub count: u8,
+    #[jbytes(count="count")]
+    pub items: Vec<u16>,
+}
+
+
+#[test]
+fn test_modifier_length_of() {
+    let data = b"\x00\x03abc\x02\x00\x01\x00\x02";
+    let bytes = Bytes::new(data);
+    let value = LengthOfExample { length: 3, value: "abc".to_string(), count: 2, items: vec![1, 2] };
+    assert_eq!(LengthOfExample::decode(&bytes).unwrap(), value);
+    assert_eq!(bytes.remaining_len(), 0);
+
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    // The stored length and count are ignored on encode.
+    let value = LengthOfExample { length: 0, value: "abc".to_string(), count: 10, items: vec![1, 2] };
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    // test length overflow
+    let value = LengthOfExample { length: 0, value: "a".repeat(0x10000), count: 0, items: vec![] };
+    assert_eq!(jbytes::encode(value).is_err(), true);
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+pub struct LengthOfBodyExample {
+    pub kind: u8,
+    #[jbytes(length=2)]
+    pub name: String,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+pub struct LengthOfNestedExample {
+    #[jbytes(length_of="body")]
+    pub length: u8,
+    pub body: LengthOfBodyExample,
+}
+
+
+#[test]
+fn test_modifier_length_of_nested() {
+    let data = b"\x03\x01ab";
+    let bytes = Bytes::new(data);
+    let value = LengthOfNestedExample { length: 3, body: LengthOfBodyExample { kind: 1, name: "ab".to_string() } };
+    assert_eq!(LengthOfNestedExample::decode(&bytes).unwrap(), value);
+    assert_eq!(bytes.remaining_len(), 0);
+
+    let value = LengthOfNestedExample { length: 0, body: LengthOfBodyExample { kind: 1, name: "ab".to_string() } };
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+pub enum LengthOfEnumExample {
+    #[jbytes(branch_value=1)]
+    Read {
+        #[jbytes(length_of="data")]
+        length: u8,
+        #[jbytes(length="length")]
+        data: String,
+    },
+    #[jbytes(branch_value=2)]
+    Write(#[jbytes(count_of="1")] u16, #[jbytes(remaining)] Vec<u8>),
+}
+
+
+#[test]
+fn test_modifier_length_of_enum() {
+    let data = b"\x01\x02ab";
+    let bytes = Bytes::new(data);
+    let value = LengthOfEnumExample::Read { length: 2, data: "ab".to_string() };
+    assert_eq!(LengthOfEnumExample::decode(&bytes).unwrap(), value);
+
+    let value = LengthOfEnumExample::Read { length: 0, data: "ab".to_string() };
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    let data = b"\x02\x00\x03\x01\x02\x03";
+    let bytes = Bytes::new(data);
+    let value = LengthOfEnumExample::Write(3, vec![1, 2, 3]);
+    assert_eq!(LengthOfEnumExample::decode(&bytes).unwrap(), value);
+
+    let value = LengthOfEnumExample::Write(0, vec![1, 2, 3]);
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+}
+
+
+static ENCODE_COUNT: core::sync::atomic::AtomicUsize = core::sync::atomic::AtomicUsize::new(0);
+
+
+fn counted_encode<B: BufWrite>(buffer: &mut B, _cattr: Option<&ContainerAttrModifiers>, _fattr: Option<&FieldAttrModifiers>, value: &[u8]) -> JResult<usize> {
+    ENCODE_COUNT.fetch_add(1, core::sync::atomic::Ordering::SeqCst);
+    buffer.push_bytes(value)
+}
+
+
+fn counted_decode<I: BufRead>(input: &I, _cattr: Option<&ContainerAttrModifiers>, _fattr: Option<&FieldAttrModifiers>) -> JResult<Vec<u8>> {
+    Ok(input.take_bytes(input.remaining_len())?.to_vec())
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+pub struct LengthOfNamesExample {
+    #[jbytes(length_of="data")]
+    pub length_value: u8,
+    #[jbytes(count_of="data")]
+    pub value: u8,
+    #[jbytes(decode_with="counted_decode", encode_with="counted_encode")]
+    pub data: Vec<u8>,
+}
+
+
+#[test]
+fn test_modifier_length_of_names() {
+    // The field names don't clash with the generated locals, and the referenced field is encoded once.
+    let value = LengthOfNamesExample { length_value: 0, value: 0, data: vec![1, 2] };
+    assert_eq!(*jbytes::encode(value).unwrap(), b"\x02\x02\x01\x02");
+    assert_eq!(ENCODE_COUNT.load(core::sync::atomic::Ordering::SeqCst), 1);
+}
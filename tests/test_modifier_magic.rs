@@ -0,0 +1,83 @@
+use core::marker::PhantomData;
+use jbytes_derive::{ByteDecode, ByteEncode};
+use jbytes::prelude::*;
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+#[jbytes(magic=b"\x89PNG\r\n\x1a\n")]
+pub struct PngExample {
+    pub length: u32,
+}
+
+
+#[test]
+fn test_modifier_magic_container() {
+    let data = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0d";
+    let bytes = Bytes::new(data);
+    let value = PngExample { length: 13 };
+    assert_eq!(PngExample::decode(&bytes).unwrap(), value);
+    assert_eq!(bytes.remaining_len(), 0);
+
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    // test magic mismatch
+    let bytes = Bytes::new(b"\x89PNX\r\n\x1a\n\x00\x00\x00\x0d");
+    let error = PngExample::decode(&bytes).unwrap_err();
+    assert_eq!(error.code, ErrorKind::InvalidMagic(b"\x89PNX\r\n\x1a\n".to_vec()));
+    assert_eq!(error.position, 0);
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+pub struct ModbusExample {
+    pub transaction_id: u16,
+    #[jbytes(magic=b"\x00\x00")]
+    pub protocol_id: (),
+    pub length: u16,
+    #[jbytes(magic=b"\xa1\xb2\xc3\xd4")]
+    pub _pcap: PhantomData<u32>,
+}
+
+
+#[test]
+fn test_modifier_magic_field() {
+    let data = b"\x00\x01\x00\x00\x00\x06\xa1\xb2\xc3\xd4";
+    let bytes = Bytes::new(data);
+    let value = ModbusExample { transaction_id: 1, protocol_id: (), length: 6, _pcap: PhantomData };
+    assert_eq!(ModbusExample::decode(&bytes).unwrap(), value);
+    assert_eq!(bytes.remaining_len(), 0);
+
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    // test magic mismatch
+    let bytes = Bytes::new(b"\x00\x01\x00\x01\x00\x06\xa1\xb2\xc3\xd4");
+    let error = ModbusExample::decode(&bytes).unwrap_err();
+    assert_eq!(error.code, ErrorKind::InvalidMagic(vec![0x00, 0x01]));
+    assert_eq!(error.position, 2);
+
+    // test insufficient bytes
+    let bytes = Bytes::new(b"\x00\x01\x00");
+    assert_eq!(ModbusExample::decode(&bytes).is_err(), true);
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+#[jbytes(magic=b"JB")]
+pub enum MagicEnumExample {
+    #[jbytes(branch_value=1)]
+    Read {
+        address: u8,
+    },
+}
+
+
+#[test]
+fn test_modifier_magic_enum() {
+    let data = b"JB\x01\x02";
+    let bytes = Bytes::new(data);
+    let value = MagicEnumExample::Read { address: 2 };
+    assert_eq!(MagicEnumExample::decode(&bytes).unwrap(), value);
+    assert_eq!(bytes.remaining_len(), 0);
+
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+}
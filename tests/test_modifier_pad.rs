@@ -0,0 +1,48 @@
+use jbytes_derive::{ByteDecode, ByteEncode, BorrowByteDecode, BorrowByteEncode};
+use jbytes::prelude::*;
+
+
+#[derive(Debug, PartialEq, Eq, ByteDecode, ByteEncode)]
+pub struct PadExample {
+    #[jbytes(length=8, pad=0x20)]
+    pub name: String,
+    #[jbytes(length=4, pad=0x00)]
+    pub model: String,
+}
+
+
+#[derive(Debug, PartialEq, Eq, BorrowByteDecode, BorrowByteEncode)]
+pub struct BorrowPadExample<'a> {
+    #[jbytes(length=8, pad=b' ')]
+    pub name: &'a str,
+    #[jbytes(length=4, pad=0xff)]
+    pub serial: &'a [u8],
+}
+
+
+#[test]
+fn test_modifier_pad() {
+    let data = b"device  ab\x00\x00";
+    let bytes = Bytes::new(data);
+    let value = PadExample::decode(&bytes).unwrap();
+    assert_eq!(value, PadExample { name: "device".to_string(), model: "ab".to_string() });
+    assert_eq!(bytes.remaining_len(), 0);
+
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    // the value is longer than the fixed width.
+    let value = PadExample { name: "device-01".to_string(), model: "ab".to_string() };
+    assert_eq!(jbytes::encode(value).is_err(), true);
+}
+
+
+#[test]
+fn test_modifier_pad_borrow() {
+    let data = b"device  \x01\x02\xff\xff";
+    let bytes = Bytes::new(data);
+    let value: BorrowPadExample = jbytes::decode_borrow(&bytes).unwrap();
+    assert_eq!(value, BorrowPadExample { name: "device", serial: &[0x01, 0x02] });
+    assert_eq!(bytes.remaining_len(), 0);
+
+    assert_eq!(*jbytes::encode_borrow(value).unwrap(), data);
+}
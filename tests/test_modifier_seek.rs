@@ -0,0 +1,123 @@
+use jbytes_derive::{ByteDecode, ByteEncode};
+use jbytes::prelude::*;
+
+
+#[derive(Debug, PartialEq, Eq, Clone, ByteEncode, ByteDecode)]
+pub struct SeekHeaderExample {
+    pub magic: u16,
+    pub ifd_offset: u32,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+pub struct SeekIfdExample {
+    pub count: u16,
+    #[jbytes(count="count")]
+    pub entries: Vec<u16>,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+pub struct SeekFileExample {
+    pub hdr: SeekHeaderExample,
+    #[jbytes(seek="abs(hdr.ifd_offset)", restore)]
+    pub ifd: SeekIfdExample,
+    pub version: u8,
+}
+
+
+#[test]
+fn test_modifier_seek() {
+    let data = b"\x00\x2a\x00\x00\x00\x07\x01\x00\x02\x00\x01\x00\x02";
+    let value = SeekFileExample {
+        hdr: SeekHeaderExample { magic: 42, ifd_offset: 7 },
+        ifd: SeekIfdExample { count: 2, entries: vec![1, 2] },
+        version: 1,
+    };
+    assert_eq!(jbytes::decode::<_, SeekFileExample>(data).unwrap(), value);
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    // The pointed-to data is laid out after the inline fields and the offset is back-patched.
+    let value = SeekFileExample {
+        hdr: SeekHeaderExample { magic: 42, ifd_offset: 0 },
+        ifd: SeekIfdExample { count: 2, entries: vec![1, 2] },
+        version: 1,
+    };
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    assert_eq!(jbytes::decode::<_, SeekFileExample>(b"\x00\x2a\x00\x00\x00\xff\x01").is_err(), true);
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+pub struct SeekPointerExample {
+    pub offset: u16,
+    #[jbytes(seek="abs(offset)")]
+    pub value: u8,
+}
+
+
+#[test]
+fn test_modifier_seek_pointer() {
+    let data = b"\x00\x02\xff";
+    let value = SeekPointerExample { offset: 2, value: 0xff };
+    assert_eq!(jbytes::decode::<_, SeekPointerExample>(data).unwrap(), value);
+    assert_eq!(*jbytes::encode(SeekPointerExample { offset: 0, value: 0xff }).unwrap(), data);
+
+    // The data before the position is skipped.
+    assert_eq!(jbytes::decode::<_, SeekPointerExample>(b"\x00\x03\x00\xff").unwrap(), SeekPointerExample { offset: 3, value: 0xff });
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+pub struct SeekFromEndExample {
+    #[jbytes(seek_from_end="2", restore)]
+    pub count: u16,
+    #[jbytes(count="count")]
+    pub items: Vec<u8>,
+}
+
+
+#[test]
+fn test_modifier_seek_from_end() {
+    let data = b"\x01\x02\x00\x02";
+    let value = SeekFromEndExample { count: 2, items: vec![1, 2] };
+    assert_eq!(jbytes::decode::<_, SeekFromEndExample>(data).unwrap(), value);
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    let error = jbytes::decode::<_, SeekFromEndExample>(b"\x01").unwrap_err();
+    assert_eq!(error.code, ErrorKind::InvalidPosition(2));
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+pub struct SeekNestedInnerExample {
+    pub offset: u8,
+    #[jbytes(seek="abs(offset)", restore)]
+    pub pointed: u8,
+    pub tail: u8,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+pub struct SeekNestedExample {
+    pub inner: SeekNestedInnerExample,
+    pub after: u8,
+}
+
+
+#[test]
+fn test_modifier_seek_nested() {
+    // The parent continues after the pointed-to data which is laid out after the inline fields.
+    let data = b"\x02\x11\xaa\x22";
+    let value = SeekNestedExample {
+        inner: SeekNestedInnerExample { offset: 2, pointed: 0xaa, tail: 0x11 },
+        after: 0x22,
+    };
+    assert_eq!(jbytes::decode::<_, SeekNestedExample>(data).unwrap(), value);
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    // The pointed-to data before the inline fields doesn't move the position back.
+    let value = jbytes::decode::<_, SeekNestedExample>(b"\x00\x11\x22").unwrap();
+    assert_eq!(value, SeekNestedExample { inner: SeekNestedInnerExample { offset: 0, pointed: 0x00, tail: 0x11 }, after: 0x22 });
+}
@@ -0,0 +1,141 @@
+use jbytes_derive::{ByteDecode, ByteEncode, BorrowByteDecode, BorrowByteEncode};
+use jbytes::prelude::*;
+
+
+#[derive(Debug, PartialEq, Eq, ByteDecode, ByteEncode)]
+pub struct Header {
+    pub version: u8,
+    pub length: u8,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteDecode, ByteEncode)]
+pub struct Frame<T> {
+    pub header: Header,
+    #[jbytes(length="header.length")]
+    pub payload: T,
+}
+
+
+#[test]
+fn test_type_generics_struct() {
+    let data = b"\x01\x03\x31\x32\x33";
+    let bytes = Bytes::new(data);
+    let value = Frame::<String>::decode(&bytes).unwrap();
+    assert_eq!(value, Frame { header: Header { version: 1, length: 3 }, payload: "123".to_string() });
+    assert_eq!(bytes.remaining_len(), 0);
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    let data = b"\x01\x02\x00\x31";
+    let bytes = Bytes::new(data);
+    let value = Frame::<u16>::decode(&bytes).unwrap();
+    assert_eq!(value, Frame { header: Header { version: 1, length: 2 }, payload: 0x31 });
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteDecode, ByteEncode)]
+pub struct ArrayExample<T, const N: usize> {
+    pub values: [T; N],
+}
+
+
+#[test]
+fn test_type_generics_const() {
+    let data = b"\x00\x01\x00\x02\x00\x03";
+    let bytes = Bytes::new(data);
+    let value = ArrayExample::<u16, 3>::decode(&bytes).unwrap();
+    assert_eq!(value, ArrayExample { values: [1, 2, 3] });
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteDecode, ByteEncode)]
+pub enum Message<T, U> {
+    #[jbytes(branch_value=1)]
+    Request(T),
+    #[jbytes(branch_value=2)]
+    Response {
+        code: u8,
+        value: U,
+    },
+}
+
+
+#[test]
+fn test_type_generics_enum() {
+    let data = b"\x01\x00\x31";
+    let value: Message<u16, u32> = jbytes::decode(data).unwrap();
+    assert_eq!(value, Message::Request(0x31));
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    let data = b"\x02\x00\x00\x00\x00\x31";
+    let value: Message<u16, u32> = jbytes::decode(data).unwrap();
+    assert_eq!(value, Message::Response { code: 0, value: 0x31 });
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteDecode, ByteEncode)]
+#[jbytes(bound="T: jbytes::ByteDecode + jbytes::ByteEncode + Default")]
+pub struct BoundExample<T> {
+    pub flags: u8,
+    #[jbytes(if_expr="flags != 0")]
+    pub value: T,
+}
+
+
+#[test]
+fn test_type_generics_bound() {
+    let value: BoundExample<u16> = jbytes::decode(b"\x00").unwrap();
+    assert_eq!(value, BoundExample { flags: 0, value: 0 });
+    assert_eq!(*jbytes::encode(value).unwrap(), b"\x00");
+
+    let value: BoundExample<u16> = jbytes::decode(b"\x01\x00\x31").unwrap();
+    assert_eq!(value, BoundExample { flags: 1, value: 0x31 });
+    assert_eq!(*jbytes::encode(value).unwrap(), b"\x01\x00\x31");
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteDecode, ByteEncode)]
+#[jbytes(bound_decode="T: Default", bound_encode="T: jbytes::ByteEncode")]
+pub struct SplitBoundExample<T> {
+    pub flags: u8,
+    #[jbytes(skip_decode)]
+    pub value: T,
+}
+
+
+// It's only encoded, so `SplitBoundExample<EncodeOnly>` doesn't need `EncodeOnly: ByteDecode`.
+#[derive(Debug, Default, PartialEq, Eq, ByteEncode)]
+pub struct EncodeOnly {
+    pub value: u8,
+}
+
+
+#[test]
+fn test_type_generics_split_bound() {
+    let value: SplitBoundExample<EncodeOnly> = jbytes::decode(b"\x01").unwrap();
+    assert_eq!(value, SplitBoundExample { flags: 1, value: EncodeOnly { value: 0 } });
+
+    let value = SplitBoundExample { flags: 1, value: EncodeOnly { value: 2 } };
+    assert_eq!(*jbytes::encode(value).unwrap(), b"\x01\x02");
+}
+
+
+#[derive(Debug, PartialEq, Eq, BorrowByteDecode, BorrowByteEncode)]
+pub struct BorrowFrame<'a, T> {
+    #[jbytes(length=3)]
+    pub name: &'a str,
+    pub payload: T,
+}
+
+
+#[test]
+fn test_type_generics_borrow() {
+    let data = b"abc\x00\x31";
+    let bytes = Bytes::new(data);
+    let value = BorrowFrame::<u16>::decode(&bytes).unwrap();
+    assert_eq!(value, BorrowFrame { name: "abc", payload: 0x31 });
+    assert_eq!(*jbytes::encode_borrow(value).unwrap(), data);
+}
@@ -0,0 +1,44 @@
+use std::{
+    str::FromStr,
+    net::{SocketAddr, SocketAddrV4},
+};
+use jbytes_derive::{ByteEncode, ByteDecode};
+use jbytes::prelude::*;
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+pub struct RouteExample {
+    pub peer: SocketAddrV4,
+    #[jbytes(length=18)]
+    pub peer_v6: SocketAddr,
+    #[jbytes(count=2)]
+    pub prefixes: Vec<IpPrefix>,
+    #[jbytes(length=16)]
+    pub prefix_v6: IpPrefix,
+}
+
+
+#[test]
+fn test_type_ip_prefix() {
+    let data = [
+        192, 168, 1, 1, 0x00, 0xb3,
+        0xfe, 0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0x00, 0xb3,
+        24, 10, 1, 2,
+        0,
+        48, 0x20, 0x01, 0x0d, 0xb8, 0x00, 0x01,
+    ];
+    let bytes = Bytes::new(data);
+    let value = RouteExample {
+        peer: SocketAddrV4::from_str("192.168.1.1:179").unwrap(),
+        peer_v6: SocketAddr::from_str("[fe80::1]:179").unwrap(),
+        prefixes: vec![IpPrefix::from_str("10.1.2.0/24").unwrap(), IpPrefix::from_str("0.0.0.0/0").unwrap()],
+        prefix_v6: IpPrefix::from_str("2001:db8:1::/48").unwrap(),
+    };
+    assert_eq!(RouteExample::decode(&bytes).unwrap(), value);
+    assert_eq!(bytes.remaining_len(), 0);
+    assert_eq!(*jbytes::encode(value).unwrap(), data);
+
+    // test prefix length error
+    let bytes = Bytes::new([192, 168, 1, 1, 0x00, 0xb3, 0xfe, 0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0x00, 0xb3, 40, 10, 1, 2, 3, 4]);
+    assert_eq!(RouteExample::decode(&bytes).is_err(), true);
+}
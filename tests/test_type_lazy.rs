@@ -0,0 +1,128 @@
+use jbytes_derive::{BorrowByteDecode, BorrowByteEncode, ByteDecode, ByteEncode};
+use jbytes::prelude::*;
+use jbytes::types::Lazy;
+
+
+#[derive(Debug, PartialEq, Eq, ByteEncode, ByteDecode)]
+pub struct LazyBodyExample {
+    pub address: u16,
+    #[jbytes(length=2)]
+    pub name: String,
+}
+
+
+#[derive(Debug, BorrowByteEncode, BorrowByteDecode)]
+pub struct LazyPacketExample<'a> {
+    pub length: u8,
+    #[jbytes(length="length")]
+    pub body: Lazy<'a, LazyBodyExample>,
+    pub trailer: Lazy<'a, u16>,
+    #[jbytes(remaining)]
+    pub payload: Lazy<'a, Vec<u8>>,
+}
+
+
+#[test]
+fn test_type_lazy() {
+    let data = b"\x05\x00\x01ab\xff\x00\x02\x01\x02\x03";
+    let bytes = Bytes::new(data);
+    let mut value: LazyPacketExample = jbytes::decode_borrow(&bytes).unwrap();
+
+    // The fields with a byte range are decoded on demand.
+    assert_eq!(value.body.is_materialized(), false);
+    assert_eq!(value.body.raw(), b"\x00\x01ab\xff");
+    assert_eq!(value.trailer.is_materialized(), true);
+    assert_eq!(*value.trailer.get().unwrap(), 2);
+    assert_eq!(value.payload.raw(), b"\x01\x02\x03");
+    assert_eq!(value.body.get().unwrap(), &LazyBodyExample { address: 1, name: "ab".to_string() });
+
+    // The untouched raw bytes are encoded verbatim, eg: the trailing byte of the body.
+    let mut buffer = Buffer::new();
+    assert_eq!(value.encode(&mut buffer).unwrap(), data.len());
+    assert_eq!(*buffer, data);
+
+    // The modified value is padded to the length of the field.
+    value.body.get_mut().unwrap().address = 3;
+    value.payload.set(vec![4]);
+    let buffer = jbytes::encode_borrow(value).unwrap();
+    assert_eq!(*buffer, b"\x05\x00\x03ab\x00\x00\x02\x04");
+
+    let bytes = Bytes::new(&*buffer);
+    let value: LazyPacketExample = jbytes::decode_borrow(&bytes).unwrap();
+    assert_eq!(value.body.get().unwrap(), &LazyBodyExample { address: 3, name: "ab".to_string() });
+    assert_eq!(*value.trailer.get().unwrap(), 2);
+    assert_eq!(value.payload.get().unwrap(), &vec![4]);
+}
+
+
+#[derive(Debug, BorrowByteEncode, BorrowByteDecode)]
+pub struct LazyLengthExample<'a> {
+    #[jbytes(length=2)]
+    pub value: Lazy<'a, Vec<u8>>,
+}
+
+
+#[test]
+fn test_type_lazy_length() {
+    let bytes = Bytes::new(b"\x01\x02");
+    let mut value: LazyLengthExample = jbytes::decode_borrow(&bytes).unwrap();
+    value.value.set(vec![3]);
+    assert_eq!(*jbytes::encode_borrow(value).unwrap(), b"\x03\x00");
+
+    // The modified value can't exceed the length of the field.
+    let bytes = Bytes::new(b"\x01\x02");
+    let mut value: LazyLengthExample = jbytes::decode_borrow(&bytes).unwrap();
+    value.value.set(vec![1, 2, 3]);
+    assert_eq!(jbytes::encode_borrow(value).unwrap_err().code, ErrorKind::InvalidByteLength);
+}
+
+
+#[test]
+fn test_type_lazy_error() {
+    let data = b"\x01\x00\x00\x02";
+    let bytes = Bytes::new(data);
+    let value: LazyPacketExample = jbytes::decode_borrow(&bytes).unwrap();
+    assert_eq!(value.body.get().unwrap_err().position, 1);
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteDecode)]
+pub struct LazyOrderBodyExample {
+    pub value: u16,
+}
+
+
+pub struct LazyCtx {
+    pub name_len: usize,
+}
+
+
+#[derive(Debug, PartialEq, Eq, ByteDecode)]
+#[jbytes(ctx="LazyCtx")]
+pub struct LazyCtxBodyExample {
+    #[jbytes(length="ctx.name_len")]
+    pub name: String,
+}
+
+
+#[derive(Debug, BorrowByteDecode)]
+pub struct LazyStateExample<'a> {
+    #[jbytes(byteorder_from="ByteOrder::Le", length=2)]
+    pub body: Lazy<'a, LazyOrderBodyExample>,
+    #[jbytes(remaining)]
+    pub name: Lazy<'a, LazyCtxBodyExample>,
+}
+
+
+#[test]
+fn test_type_lazy_state() {
+    let bytes = Bytes::new(b"\x01\x00ab");
+    let value: LazyStateExample = jbytes::decode_borrow(&bytes).unwrap();
+
+    // The byte order of the subtree is kept for the nested fields.
+    assert_eq!(value.body.get().unwrap(), &LazyOrderBodyExample { value: 1 });
+
+    // The user context is passed again to the lazy field.
+    assert_eq!(value.name.get().unwrap_err().code, ErrorKind::MissingContext);
+    assert_eq!(value.name.get_with_ctx(&LazyCtx { name_len: 2 }).unwrap(), &LazyCtxBodyExample { name: "ab".to_string() });
+}
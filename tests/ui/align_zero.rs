@@ -0,0 +1,17 @@
+#[derive(jbytes_derive::ByteDecode)]
+pub struct AlignExample {
+    pub length: u8,
+    #[jbytes(align=0)] // `align` must be greater than 0
+    pub value: u16,
+}
+
+
+#[derive(jbytes_derive::ByteEncode)]
+#[jbytes(align_fields=0)] // `align_fields` must be greater than 0
+pub struct AlignFieldsExample {
+    pub length: u8,
+    pub value: u16,
+}
+
+
+fn main() {}
@@ -0,0 +1,17 @@
+#[derive(jbytes_derive::ByteDecode)]
+pub struct ArgsFrameExample {
+    pub tag: u8,
+    #[jbytes(args(tag="tag"))] // the arguments are checked against the declared types, expected `[u8; 2]`
+    pub body: ArgsBodyExample,
+}
+
+
+#[derive(jbytes_derive::ByteDecode)]
+#[jbytes(args(tag: [u8; 2]))]
+pub struct ArgsBodyExample {
+    #[jbytes(if_expr="tag == [0x01, 0x02]")]
+    pub value: Option<u8>,
+}
+
+
+fn main() {}
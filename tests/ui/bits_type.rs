@@ -0,0 +1,8 @@
+#[derive(jbytes_derive::ByteDecode)]
+pub struct BitsExample {
+    #[jbytes(bits=0x0f)] // `bits` only supports integer types, found `String`
+    pub value: String,
+}
+
+
+fn main() {}
@@ -0,0 +1,8 @@
+#[derive(jbytes_derive::ByteDecode)]
+pub struct ConflictExample {
+    #[jbytes(length=2, byte_count=1)] // `byte_count` can't be used together with `length`
+    pub value: String,
+}
+
+
+fn main() {}
@@ -0,0 +1,26 @@
+pub struct Config {
+    pub version: u8,
+}
+
+
+pub struct OtherConfig {
+    pub version: u8,
+}
+
+
+#[derive(jbytes_derive::ByteDecode)]
+#[jbytes(ctx="Config")]
+pub struct CtxFrameExample {
+    pub body: CtxBodyExample, // the nested type declares another context
+}
+
+
+#[derive(jbytes_derive::ByteDecode)]
+#[jbytes(ctx="OtherConfig")]
+pub struct CtxBodyExample {
+    #[jbytes(if_expr="ctx.version > 1")]
+    pub value: Option<u8>,
+}
+
+
+fn main() {}
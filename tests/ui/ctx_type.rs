@@ -0,0 +1,17 @@
+pub struct Config {
+    pub version: u8,
+}
+
+
+#[derive(jbytes_derive::ByteDecode)]
+#[jbytes(ctx="Config")]
+pub struct CtxExample {
+    #[jbytes(if_expr="ctx.version > 1")]
+    pub value: Option<u8>,
+}
+
+
+fn main() {
+    // the context type is checked against `#[jbytes(ctx="Config")]`
+    let _ = jbytes::decode_with_ctx::<CtxExample, u8>(b"\x01", &1);
+}
@@ -0,0 +1,8 @@
+#[derive(jbytes_derive::ByteDecode)]
+pub struct LegacyEncodingExample {
+    #[jbytes(encoding="gbk", length=4)] // the encoding `gbk` requires the `encoding_rs` feature of `jbytes`
+    pub name: String,
+}
+
+
+fn main() {}
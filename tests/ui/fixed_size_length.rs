@@ -0,0 +1,9 @@
+#[derive(jbytes_derive::FixedSize)]
+pub struct FixedSizeExample {
+    pub length: u8,
+    #[jbytes(length="length")] // `FixedSize` only supports the literal `length`, eg: `length=2`
+    pub value: String,
+}
+
+
+fn main() {}
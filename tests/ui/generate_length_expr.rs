@@ -0,0 +1,9 @@
+#[derive(jbytes_derive::ByteGenerate)]
+pub struct LengthExprGenerateExample {
+    pub length: u8,
+    #[jbytes(length="length * 2")] // `ByteGenerate` only supports a field with a constant offset
+    pub name: String,
+}
+
+
+fn main() {}
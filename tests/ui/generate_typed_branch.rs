@@ -0,0 +1,9 @@
+#[derive(jbytes_derive::ByteGenerate)]
+#[jbytes(branch_type="str")] // `ByteGenerate` only supports the integer `branch_type`, found `str`
+pub enum TypedBranchGenerateExample {
+    #[jbytes(branch_value="\"GET\"")]
+    Get,
+}
+
+
+fn main() {}
@@ -0,0 +1,8 @@
+#[derive(jbytes_derive::ByteDecode)]
+pub struct UnknownEncodingExample {
+    #[jbytes(encoding="utf7", length=4)] // unknown encoding `utf7`
+    pub name: String,
+}
+
+
+fn main() {}
@@ -0,0 +1,9 @@
+#[derive(jbytes_derive::ByteDecode)]
+pub struct UnknownFieldExample {
+    pub length: u16,
+    #[jbytes(length="lenght")] // `length` references an unknown field `lenght`
+    pub value: String,
+}
+
+
+fn main() {}
@@ -0,0 +1,17 @@
+#[derive(jbytes_derive::ByteDecode)]
+pub struct UntypedBranchExample {
+    #[jbytes(length=3)]
+    pub name: String,
+    #[jbytes(branch="name")] // the non-integer branch requires `#[jbytes(branch_type="str")]` on the enum
+    pub body: UntypedBranchBodyExample,
+}
+
+
+#[derive(jbytes_derive::ByteDecode)]
+pub enum UntypedBranchBodyExample {
+    #[jbytes(branch_value=1)]
+    V0,
+}
+
+
+fn main() {}
@@ -0,0 +1,8 @@
+#[derive(jbytes_derive::ByteView)]
+pub struct ViewExample {
+    #[jbytes(length=2)] // `ByteView` only supports fixed-size fields, found `String`
+    pub value: String,
+}
+
+
+fn main() {}